@@ -27,10 +27,38 @@ fn enable_vt_processing() -> std::io::Result<()> {
 }
 
 static SUPPORTS_ANSI_ESCAPE_CODES: AtomicBool = AtomicBool::new(false);
+static ASSUME_ANSI: AtomicBool = AtomicBool::new(false);
 static INITIALIZER: Once = Once::new();
 
+/// Sets whether the terminal is assumed to support ANSI escape sequences.
+///
+/// With `true`, [supports_ansi] returns `true` without checking the console, so commands are
+/// always written as ANSI escape sequences instead of falling back to WinAPI calls. This skips
+/// the detection and the WinAPI branch when the application only runs on consoles with virtual
+/// terminal processing, e.g. Windows Terminal. Pass `false` to detect the support again.
+///
+/// Commands which always use the WinAPI, because their ANSI escape sequences don't work with the
+/// console input crossterm reads events from, like
+/// [EnableMouseCapture](crate::event::EnableMouseCapture), still do so.
+///
+/// # Notes
+///
+/// Virtual terminal processing isn't enabled by this function. If it's set to `true` on a
+/// legacy console, or a console without virtual terminal processing enabled, the escape
+/// sequences are printed as text instead of being interpreted: colors, cursor movement and
+/// clearing stop working and garbage appears on the screen.
+pub fn assume_ansi(assume: bool) {
+    ASSUME_ANSI.store(assume, Ordering::SeqCst);
+}
+
 /// Checks if the current terminal supports ANSI escape sequences
+///
+/// Always returns `true` after [assume_ansi] was called with `true`.
 pub fn supports_ansi() -> bool {
+    if ASSUME_ANSI.load(Ordering::Relaxed) {
+        return true;
+    }
+
     INITIALIZER.call_once(|| {
         // Some terminals on Windows like GitBash can't use WinAPI calls directly
         // so when we try to enable the ANSI-flag for Windows this won't work.
@@ -1,8 +1,56 @@
 use std::fmt;
 use std::io::{self, Write};
+use std::time::Instant;
 
 use crate::terminal::{BeginSynchronizedUpdate, EndSynchronizedUpdate};
 
+#[cfg(feature = "events")]
+pub use self::query::{run_queries, Query, QueryReply};
+
+#[cfg(feature = "events")]
+mod query;
+
+/// A function observing the commands written by crossterm, see [`set_trace_hook`].
+#[cfg(feature = "trace")]
+type TraceHook = Box<dyn Fn(&str, usize) + Send + Sync>;
+
+#[cfg(feature = "trace")]
+static TRACE_HOOK: parking_lot::RwLock<Option<TraceHook>> = parking_lot::const_rwlock(None);
+
+/// Sets a function which is called every time a command is written, replacing the previous one.
+///
+/// The function is called with the type name of the command, e.g.
+/// `crossterm::cursor::MoveTo`, and the number of bytes its ANSI representation took, after
+/// the command was queued or executed. This helps to find out which commands make a frame slow
+/// to transmit. The hook is shared by all threads and called on the thread writing the command,
+/// so it should be quick, and mustn't write commands itself.
+///
+/// Commands executed with the WinAPI and commands formatted with `Display` aren't traced.
+///
+/// This is only available with the `trace` feature, without it tracing costs nothing.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::{cursor::MoveTo, execute, set_trace_hook};
+///
+/// fn main() -> io::Result<()> {
+///     set_trace_hook(|command, bytes| eprintln!("{}: {} bytes", command, bytes));
+///     execute!(io::stdout(), MoveTo(10, 5))
+/// }
+/// ```
+#[cfg(feature = "trace")]
+pub fn set_trace_hook(hook: impl Fn(&str, usize) + Send + Sync + 'static) {
+    *TRACE_HOOK.write() = Some(Box::new(hook));
+}
+
+/// Removes the function set with [`set_trace_hook`].
+#[cfg(feature = "trace")]
+pub fn remove_trace_hook() {
+    *TRACE_HOOK.write() = None;
+}
+
 /// An interface for a command that performs an action on the terminal.
 ///
 /// Crossterm provides a set of commands,
@@ -92,8 +140,8 @@ impl<T: Write + ?Sized> QueueableCommand for T {
     ///
     ///     // `Print` will executed executed when `flush` is called.
     ///     stdout
-    ///         .queue(Print("foo 1\n".to_string()))?
-    ///         .queue(Print("foo 2".to_string()))?;
+    ///         .queue(Print("foo 1\n"))?
+    ///         .queue(Print("foo 2"))?;
     ///
     ///     // some other code (no execution happening here) ...
     ///
@@ -154,7 +202,7 @@ impl<T: Write + ?Sized> ExecutableCommand for T {
     /// fn main() -> io::Result<()> {
     ///      // will be executed directly
     ///       io::stdout()
-    ///         .execute(Print("sum:\n".to_string()))?
+    ///         .execute(Print("sum:\n"))?
     ///         .execute(Print(format!("1 + 1= {} ", 1 + 1)))?;
     ///
     ///       Ok(())
@@ -182,6 +230,468 @@ impl<T: Write + ?Sized> ExecutableCommand for T {
     }
 }
 
+/// Queues all given commands and flushes the writer once afterwards.
+///
+/// The commands are queued in iteration order, so this is equal to calling
+/// [queue](./trait.QueueableCommand.html) for every command followed by a single `flush`.
+/// Use this instead of calling [execute](./trait.ExecutableCommand.html) several times in a row,
+/// which would flush after every command.
+///
+/// All commands must be of the same type, use the [execute](./macro.execute.html) macro to
+/// execute commands of different types.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io;
+/// use crossterm::{batch, style::Print};
+///
+/// fn main() -> io::Result<()> {
+///     batch(&mut io::stdout(), [Print("foo 1\n"), Print("foo 2\n")])
+///
+///     // ==== Output ====
+///     // foo 1
+///     // foo 2
+/// }
+/// ```
+///
+/// # Notes
+///
+/// In case of Windows versions lower than 10, commands are executed one by one with a direct
+/// WinAPI call, exactly like [queue](./trait.QueueableCommand.html) does.
+pub fn batch<C: Command>(
+    writer: &mut (impl Write + ?Sized),
+    commands: impl IntoIterator<Item = C>,
+) -> io::Result<()> {
+    for command in commands {
+        writer.queue(command)?;
+    }
+    writer.flush()
+}
+
+/// Returns the ANSI representation of a command as a `String`.
+///
+/// This always returns the escape sequences, even on Windows versions lower than 10 where
+/// executing the command would use a WinAPI call. It is useful to assert the exact bytes a
+/// program emits in its own tests.
+///
+/// # Examples
+///
+/// ```rust
+/// use crossterm::{ansi_string, cursor::MoveTo};
+///
+/// assert_eq!(ansi_string(MoveTo(3, 4)), "\x1B[5;4H");
+/// ```
+pub fn ansi_string<C: Command>(command: C) -> String {
+    let mut ansi = String::new();
+    if command.write_ansi(&mut ansi).is_err() {
+        panic!(
+            "<{}>::write_ansi incorrectly errored",
+            std::any::type_name::<C>()
+        );
+    }
+    ansi
+}
+
+/// A command that writes the given escape sequence verbatim.
+///
+/// This is an escape hatch for terminal features crossterm doesn't model yet. The sequence
+/// bypasses all of crossterm's capability checks, it's written as is, regardless of whether the
+/// terminal understands it. Any type implementing `AsRef<str>` can be used, e.g. `&str`, `String`
+/// or `Cow<'static, str>`. Sequences that aren't valid UTF-8 can't be written with a command,
+/// write them to the writer directly instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::{execute, Raw};
+///
+/// fn main() -> io::Result<()> {
+///     // Set the cursor color to red, OSC 12.
+///     execute!(io::stdout(), Raw("\x1B]12;red\x07"))
+/// }
+/// ```
+///
+/// # Notes
+///
+/// - On Windows versions lower than 10, which don't support ANSI escape sequences, nothing is
+///   written.
+/// - Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Raw<T: AsRef<str>>(pub T);
+
+impl<T: AsRef<str>> Command for Raw<T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(self.0.as_ref())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A writer that duplicates everything written to it to a second writer.
+///
+/// Queue or execute commands on a `Tee` to see the exact bytes crossterm emits, for example by
+/// logging them to a file, while the commands still take effect on the primary writer. Being a
+/// [`Write`](std::io::Write) implementation, it supports [`QueueableCommand`] and
+/// [`ExecutableCommand`] like any other writer.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::{fs::File, io};
+/// use crossterm::{cursor::MoveTo, execute, Tee};
+///
+/// fn main() -> io::Result<()> {
+///     let mut tee = Tee::new(io::stdout(), File::create("escape-sequences.log")?);
+///     execute!(tee, MoveTo(10, 5))
+/// }
+/// ```
+///
+/// # Errors
+///
+/// A failure to write the log doesn't affect the primary writer, the bytes written to it are
+/// still reported as written. Logging stops at the first failure and the error is returned by
+/// the next call to [`flush`](std::io::Write::flush).
+///
+/// # Notes
+///
+/// On Windows versions lower than 10 commands are executed with direct WinAPI calls, which
+/// don't write anything. These commands aren't captured by the second writer.
+#[derive(Debug)]
+pub struct Tee<W, L> {
+    writer: W,
+    log: L,
+    logging: bool,
+    log_error: Option<io::Error>,
+}
+
+impl<W: Write, L: Write> Tee<W, L> {
+    /// Creates a new `Tee` writing to `writer` and duplicating all written bytes to `log`.
+    pub fn new(writer: W, log: L) -> Self {
+        Tee {
+            writer,
+            log,
+            logging: true,
+            log_error: None,
+        }
+    }
+
+    /// Returns a reference to the primary writer.
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a reference to the writer receiving the duplicated bytes.
+    pub fn log(&self) -> &L {
+        &self.log
+    }
+
+    /// Returns both writers.
+    pub fn into_inner(self) -> (W, L) {
+        (self.writer, self.log)
+    }
+}
+
+impl<W: Write, L: Write> Write for Tee<W, L> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        if !self.logging {
+            return Ok(written);
+        }
+
+        if let Err(e) = self.log.write_all(&buf[..written]) {
+            self.logging = false;
+            self.log_error = Some(e);
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()?;
+        if let Some(e) = self.log_error.take() {
+            return Err(e);
+        }
+        self.log.flush()
+    }
+}
+
+/// A writer that records everything written to it with timestamps, for replaying a session.
+///
+/// Like [`Tee`], everything is written to the primary writer and duplicated to a log, but every
+/// write is recorded along with the time it happened, so a player can reproduce the output at
+/// its original pace, like an asciinema recording. The log is flushed when the `Recorder` is
+/// dropped.
+///
+/// # Format
+///
+/// The log is UTF-8 text with one record per line, a record per write to the `Recorder`:
+///
+/// ```text
+/// [0.000000] \e[11;6HHello
+/// [0.016250] World\r\n
+/// ```
+///
+/// * The bracketed number is the time since the `Recorder` was created, in seconds with
+///   microsecond precision.
+/// * A single space separates it from the written bytes.
+/// * The bytes are escaped so a record fits on one line: `\\` is a backslash, `\e` is ESC, `\n`
+///   is a line feed, `\r` a carriage return, `\t` a tab and `\xNN` any other byte that isn't
+///   printable ASCII, with two hexadecimal digits. Every other byte is written as is.
+///
+/// Commands can be written in several parts, so a single command may span multiple records.
+///
+/// # Errors
+///
+/// A failure to write the log doesn't affect the primary writer, the bytes written to it are
+/// still reported as written. Recording stops at the first failure and the error is returned by
+/// the next call to [`flush`](std::io::Write::flush).
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::{fs::File, io};
+/// use crossterm::{cursor::MoveTo, execute, style::Print, Recorder};
+///
+/// fn main() -> io::Result<()> {
+///     let mut recorder = Recorder::new(io::stdout(), File::create("session.rec")?);
+///     execute!(recorder, MoveTo(5, 10), Print("Hello"))
+/// }
+/// ```
+///
+/// # Notes
+///
+/// On Windows versions lower than 10 commands are executed with direct WinAPI calls, which
+/// don't write anything. These commands aren't recorded.
+#[derive(Debug)]
+pub struct Recorder<W: Write, L: Write> {
+    writer: W,
+    log: L,
+    start: Instant,
+    recording: bool,
+    log_error: Option<io::Error>,
+}
+
+impl<W: Write, L: Write> Recorder<W, L> {
+    /// Creates a new `Recorder` writing to `writer` and recording all written bytes to `log`.
+    ///
+    /// Timestamps are relative to the moment the `Recorder` is created.
+    pub fn new(writer: W, log: L) -> Self {
+        Recorder {
+            writer,
+            log,
+            start: Instant::now(),
+            recording: true,
+            log_error: None,
+        }
+    }
+
+    /// Returns a reference to the primary writer.
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a reference to the writer receiving the records.
+    pub fn log(&self) -> &L {
+        &self.log
+    }
+}
+
+impl<W: Write, L: Write> Write for Recorder<W, L> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        if !self.recording {
+            return Ok(written);
+        }
+
+        let elapsed = self.start.elapsed();
+        let record = format!(
+            "[{}.{:06}] {}\n",
+            elapsed.as_secs(),
+            elapsed.subsec_micros(),
+            escape_record(&buf[..written])
+        );
+        if let Err(e) = self.log.write_all(record.as_bytes()) {
+            self.recording = false;
+            self.log_error = Some(e);
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()?;
+        if let Some(e) = self.log_error.take() {
+            return Err(e);
+        }
+        self.log.flush()
+    }
+}
+
+impl<W: Write, L: Write> Drop for Recorder<W, L> {
+    fn drop(&mut self) {
+        let _ = self.log.flush();
+    }
+}
+
+/// Escapes written bytes for a [`Recorder`] record.
+fn escape_record(bytes: &[u8]) -> String {
+    let mut escaped = String::with_capacity(bytes.len());
+    for &byte in bytes {
+        match byte {
+            b'\\' => escaped.push_str("\\\\"),
+            b'\x1B' => escaped.push_str("\\e"),
+            b'\n' => escaped.push_str("\\n"),
+            b'\r' => escaped.push_str("\\r"),
+            b'\t' => escaped.push_str("\\t"),
+            b' '..=b'~' => escaped.push(char::from(byte)),
+            _ => escaped.push_str(&format!("\\x{:02x}", byte)),
+        }
+    }
+    escaped
+}
+
+/// A writer that records everything written to it, in addition to writing it to another writer.
+///
+/// Unlike [`Tee`], the recorded bytes are kept in memory by the `SplitWriter` itself, so they can
+/// be inspected while writing, for example to assert the output of a program in tests.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::{execute, style::Print, SplitWriter};
+///
+/// fn main() -> io::Result<()> {
+///     let mut writer = SplitWriter::new(io::stdout());
+///     execute!(writer, Print("Hello"))?;
+///     assert_eq!(writer.captured(), b"Hello");
+///
+///     writer.clear_captured();
+///     assert!(writer.captured().is_empty());
+///     Ok(())
+/// }
+/// ```
+///
+/// # Notes
+///
+/// On Windows versions lower than 10 commands are executed with direct WinAPI calls, which
+/// don't write anything. These commands aren't recorded.
+#[derive(Debug)]
+pub struct SplitWriter<W> {
+    writer: W,
+    captured: Vec<u8>,
+}
+
+impl<W: Write> SplitWriter<W> {
+    /// Creates a new `SplitWriter` writing to `writer`.
+    pub fn new(writer: W) -> Self {
+        SplitWriter {
+            writer,
+            captured: Vec::new(),
+        }
+    }
+
+    /// Returns the bytes recorded since the `SplitWriter` was created or last cleared.
+    pub fn captured(&self) -> &[u8] {
+        &self.captured
+    }
+
+    /// Returns the recorded bytes and clears them.
+    pub fn take_captured(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.captured)
+    }
+
+    /// Clears the recorded bytes.
+    pub fn clear_captured(&mut self) {
+        self.captured.clear();
+    }
+
+    /// Returns a reference to the writer.
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns the writer and the recorded bytes.
+    pub fn into_inner(self) -> (W, Vec<u8>) {
+        (self.writer, self.captured)
+    }
+}
+
+impl<W: Write> Write for SplitWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        self.captured.extend_from_slice(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// A writer that flushes after every write.
+///
+/// Queued commands usually only reach the terminal once the writer is flushed, or when its
+/// buffer is full. Wrapping the writer in an `AutoFlushWriter` makes
+/// [`queue`](./trait.QueueableCommand.html) take effect right away like
+/// [`execute`](./trait.ExecutableCommand.html) does, which is convenient for scripts and simple
+/// interactive programs.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::{cursor::MoveTo, queue, style::Print, AutoFlushWriter};
+///
+/// fn main() -> io::Result<()> {
+///     let mut stdout = AutoFlushWriter::new(io::stdout());
+///     // Visible immediately, without flushing stdout.
+///     queue!(stdout, MoveTo(0, 0), Print("Hello"))
+/// }
+/// ```
+///
+/// # Notes
+///
+/// Flushing after every write means a system call per command, or even several for commands
+/// written in parts, and the terminal may render half-finished frames. Programs which draw a
+/// lot should queue commands on a buffered writer and flush once per frame instead.
+#[derive(Debug)]
+pub struct AutoFlushWriter<W> {
+    writer: W,
+}
+
+impl<W: Write> AutoFlushWriter<W> {
+    /// Creates a new `AutoFlushWriter` flushing `writer` after every write.
+    pub fn new(writer: W) -> Self {
+        AutoFlushWriter { writer }
+    }
+
+    /// Returns a reference to the writer.
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> Write for AutoFlushWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        self.writer.flush()?;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
 /// An interface for types that support synchronized updates.
 pub trait SynchronizedUpdate {
     /// Performs a set of actions against the given type.
@@ -210,8 +720,8 @@ impl<W: std::io::Write + ?Sized> SynchronizedUpdate for W {
     ///     let mut stdout = io::stdout();
     ///
     ///     stdout.sync_update(|stdout| {
-    ///         stdout.execute(Print("foo 1\n".to_string()))?;
-    ///         stdout.execute(Print("foo 2".to_string()))?;
+    ///         stdout.execute(Print("foo 1\n"))?;
+    ///         stdout.execute(Print("foo 2"))?;
     ///         // The effects of the print command will not be present in the terminal
     ///         // buffer, but not visible in the terminal.
     ///         std::io::Result::Ok(())
@@ -257,10 +767,16 @@ fn write_command_ansi<C: Command>(
     struct Adapter<T> {
         inner: T,
         res: io::Result<()>,
+        #[cfg(feature = "trace")]
+        written: usize,
     }
 
     impl<T: Write> fmt::Write for Adapter<T> {
         fn write_str(&mut self, s: &str) -> fmt::Result {
+            #[cfg(feature = "trace")]
+            {
+                self.written += s.len();
+            }
             self.inner.write_all(s.as_bytes()).map_err(|e| {
                 self.res = Err(e);
                 fmt::Error
@@ -271,6 +787,8 @@ fn write_command_ansi<C: Command>(
     let mut adapter = Adapter {
         inner: io,
         res: Ok(()),
+        #[cfg(feature = "trace")]
+        written: 0,
     };
 
     command
@@ -281,7 +799,14 @@ fn write_command_ansi<C: Command>(
                 std::any::type_name::<C>()
             ),
             Err(e) => e,
-        })
+        })?;
+
+    #[cfg(feature = "trace")]
+    if let Some(hook) = TRACE_HOOK.read().as_ref() {
+        hook(std::any::type_name::<C>(), adapter.written);
+    }
+
+    Ok(())
 }
 
 /// Executes the ANSI representation of a command, using the given `fmt::Write`.
@@ -293,3 +818,255 @@ pub(crate) fn execute_fmt(f: &mut impl fmt::Write, command: impl Command) -> fmt
 
     command.write_ansi(f)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ansi_string, escape_record, Recorder, Tee};
+    use crate::{cursor, style, terminal};
+
+    #[test]
+    fn test_cursor_ansi() {
+        use cursor::*;
+
+        assert_eq!(ansi_string(MoveTo(0, 0)), "\x1B[1;1H");
+        assert_eq!(ansi_string(MoveTo(3, 4)), "\x1B[5;4H");
+        assert_eq!(ansi_string(MoveHome), "\x1B[H");
+        assert_eq!(ansi_string(MoveToNextLine(2)), "\x1B[2E");
+        assert_eq!(ansi_string(MoveToPreviousLine(2)), "\x1B[2F");
+        assert_eq!(ansi_string(MoveToColumn(0)), "\x1B[1G");
+        assert_eq!(ansi_string(MoveToNextTab(2)), "\x1B[2I");
+        assert_eq!(ansi_string(MoveToPreviousTab(2)), "\x1B[2Z");
+        assert_eq!(ansi_string(MoveToRow(0)), "\x1B[1d");
+        assert_eq!(ansi_string(MoveUp(3)), "\x1B[3A");
+        assert_eq!(ansi_string(MoveDown(3)), "\x1B[3B");
+        assert_eq!(ansi_string(MoveRight(3)), "\x1B[3C");
+        assert_eq!(ansi_string(MoveLeft(3)), "\x1B[3D");
+        assert_eq!(ansi_string(SavePosition), "\x1B7");
+        assert_eq!(ansi_string(RestorePosition), "\x1B8");
+        assert_eq!(ansi_string(Hide), "\x1B[?25l");
+        assert_eq!(ansi_string(Show), "\x1B[?25h");
+        assert_eq!(ansi_string(EnableBlinking), "\x1B[?12h");
+        assert_eq!(ansi_string(DisableBlinking), "\x1B[?12l");
+        assert_eq!(ansi_string(SetCursorStyle::DefaultUserShape), "\x1B[0 q");
+        assert_eq!(ansi_string(SetCursorStyle::BlinkingBlock), "\x1B[1 q");
+        assert_eq!(ansi_string(SetCursorStyle::SteadyBlock), "\x1B[2 q");
+        assert_eq!(ansi_string(SetCursorStyle::BlinkingUnderScore), "\x1B[3 q");
+        assert_eq!(ansi_string(SetCursorStyle::SteadyUnderScore), "\x1B[4 q");
+        assert_eq!(ansi_string(SetCursorStyle::BlinkingBar), "\x1B[5 q");
+        assert_eq!(ansi_string(SetCursorStyle::SteadyBar), "\x1B[6 q");
+    }
+
+    #[test]
+    fn test_raw_ansi() {
+        use std::borrow::Cow;
+
+        use super::Raw;
+
+        assert_eq!(ansi_string(Raw("\x1B]12;red\x07")), "\x1B]12;red\x07");
+        assert_eq!(ansi_string(Raw(String::from("\x1B[5n"))), "\x1B[5n");
+        assert_eq!(
+            ansi_string(Raw(Cow::Borrowed("\x1B[?2026h"))),
+            "\x1B[?2026h"
+        );
+    }
+
+    #[test]
+    fn test_escape_record() {
+        assert_eq!(escape_record(b"Hello, World!"), "Hello, World!");
+        assert_eq!(escape_record(b"\x1B[1;1H\r\n\t\\"), "\\e[1;1H\\r\\n\\t\\\\");
+        assert_eq!(escape_record("\u{e9}\x07".as_bytes()), "\\xc3\\xa9\\x07");
+    }
+
+    /// A log writer which fails every write.
+    struct FailingLog;
+
+    impl std::io::Write for FailingLog {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(std::io::ErrorKind::Other, "log failed"))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_tee_log_error() {
+        use std::io::Write;
+
+        let mut tee = Tee::new(Vec::new(), FailingLog);
+        assert_eq!(tee.write(b"abc").unwrap(), 3);
+        assert_eq!(tee.write(b"def").unwrap(), 3);
+        assert_eq!(tee.writer(), b"abcdef");
+
+        // The failure is reported once, by the next flush.
+        assert_eq!(tee.flush().unwrap_err().to_string(), "log failed");
+        assert!(tee.flush().is_ok());
+    }
+
+    #[test]
+    fn test_recorder_log_error() {
+        use std::io::Write;
+
+        let mut recorder = Recorder::new(Vec::new(), FailingLog);
+        assert_eq!(recorder.write(b"abc").unwrap(), 3);
+        assert_eq!(recorder.write(b"def").unwrap(), 3);
+        assert_eq!(recorder.writer(), b"abcdef");
+
+        // The failure is reported once, by the next flush.
+        assert_eq!(recorder.flush().unwrap_err().to_string(), "log failed");
+        assert!(recorder.flush().is_ok());
+    }
+
+    #[test]
+    fn test_terminal_ansi() {
+        use terminal::*;
+
+        assert_eq!(ansi_string(DisableLineWrap), "\x1B[?7l");
+        assert_eq!(ansi_string(EnableLineWrap), "\x1B[?7h");
+        assert_eq!(ansi_string(EnableOriginMode), "\x1B[?6h");
+        assert_eq!(ansi_string(DisableOriginMode), "\x1B[?6l");
+        assert_eq!(ansi_string(EnableLeftRightMargins), "\x1B[?69h");
+        assert_eq!(ansi_string(DisableLeftRightMargins), "\x1B[?69l");
+        assert_eq!(
+            ansi_string(SetLeftRightMargins { left: 4, right: 9 }),
+            "\x1B[5;10s"
+        );
+        assert_eq!(ansi_string(SetPrivateMode(1)), "\x1B[?1h");
+        assert_eq!(ansi_string(ResetPrivateMode(1)), "\x1B[?1l");
+        assert_eq!(ansi_string(EnterAlternateScreen), "\x1B[?1049h");
+        assert_eq!(ansi_string(LeaveAlternateScreen), "\x1B[?1049l");
+        assert_eq!(ansi_string(ScrollUp(2)), "\x1B[2S");
+        assert_eq!(ansi_string(ScrollDown(2)), "\x1B[2T");
+        assert_eq!(ansi_string(ScrollUp(0)), "");
+        assert_eq!(ansi_string(InsertLines(2)), "\x1B[2L");
+        assert_eq!(ansi_string(DeleteLines(2)), "\x1B[2M");
+        assert_eq!(ansi_string(InsertChars(2)), "\x1B[2@");
+        assert_eq!(ansi_string(DeleteChars(2)), "\x1B[2P");
+        assert_eq!(ansi_string(EraseChars(2)), "\x1B[2X");
+        assert_eq!(ansi_string(Clear(ClearType::All)), "\x1B[2J");
+        assert_eq!(ansi_string(Clear(ClearType::Purge)), "\x1B[3J");
+        assert_eq!(ansi_string(Clear(ClearType::FromCursorDown)), "\x1B[J");
+        assert_eq!(ansi_string(Clear(ClearType::FromCursorUp)), "\x1B[1J");
+        assert_eq!(ansi_string(Clear(ClearType::CurrentLine)), "\x1B[2K");
+        assert_eq!(ansi_string(Clear(ClearType::UntilNewLine)), "\x1B[K");
+        assert_eq!(ansi_string(ClearAll), "\x1B[2J\x1B[H\x1B[r");
+        assert_eq!(ansi_string(SoftReset), "\x1B[!p");
+        assert_eq!(ansi_string(HardReset), "\x1Bc");
+        assert_eq!(ansi_string(SetSize(80, 24)), "\x1B[8;24;80t");
+        assert_eq!(ansi_string(SetTitle("title")), "\x1B]0;title\x07");
+        assert_eq!(ansi_string(PushTitle), "\x1B[22;0t");
+        assert_eq!(ansi_string(PopTitle), "\x1B[23;0t");
+        assert_eq!(ansi_string(SetTabStop), "\x1BH");
+        assert_eq!(ansi_string(ClearTabStop), "\x1B[0g");
+        assert_eq!(ansi_string(ClearAllTabStops), "\x1B[3g");
+        assert_eq!(ansi_string(Bell), "\x07");
+        assert_eq!(ansi_string(VisualBell), "\x1Bg");
+        assert_eq!(ansi_string(EnableReverseScreen), "\x1B[?5h");
+        assert_eq!(ansi_string(DisableReverseScreen), "\x1B[?5l");
+        assert_eq!(ansi_string(BeginSynchronizedUpdate), "\x1B[?2026h");
+        assert_eq!(ansi_string(EndSynchronizedUpdate), "\x1B[?2026l");
+    }
+
+    #[test]
+    fn test_style_ansi() {
+        use style::*;
+
+        assert_eq!(ansi_string(SetForegroundColor(Color::Red)), "\x1B[38;5;9m");
+        assert_eq!(ansi_string(SetBackgroundColor(Color::Reset)), "\x1B[49m");
+        assert_eq!(
+            ansi_string(SetUnderlineColor(Color::Rgb { r: 1, g: 2, b: 3 })),
+            "\x1B[58;2;1;2;3m"
+        );
+        assert_eq!(
+            ansi_string(SetColors(Colors::new(Color::Red, Color::AnsiValue(17)))),
+            "\x1B[38;5;9m\x1B[48;5;17m"
+        );
+        assert_eq!(ansi_string(SetAttribute(Attribute::Bold)), "\x1B[1m");
+        assert_eq!(
+            ansi_string(SetAttributes(
+                Attributes::from(Attribute::Bold) | Attribute::Italic
+            )),
+            "\x1B[1m\x1B[3m"
+        );
+        assert_eq!(
+            ansi_string(PrintStyledContent("a".red())),
+            "\x1B[38;5;9ma\x1B[39m"
+        );
+        assert_eq!(ansi_string(LineSize::DoubleWidth), "\x1B#6");
+        assert_eq!(ansi_string(EnableLineDrawing), "\x1B(0");
+        assert_eq!(ansi_string(DisableLineDrawing), "\x1B(B");
+        assert_eq!(ansi_string(ResetColor), "\x1B[0m");
+        assert_eq!(ansi_string(Print("a")), "a");
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn test_trace_hook() {
+        use std::sync::{Arc, Mutex};
+
+        use crate::{cursor::MoveTo, style::Print, QueueableCommand};
+
+        let traced = Arc::new(Mutex::new(Vec::new()));
+        let hook_traced = Arc::clone(&traced);
+        // The hook is global, other tests running in parallel write commands as well.
+        let test_thread = std::thread::current().id();
+        super::set_trace_hook(move |command, bytes| {
+            if std::thread::current().id() == test_thread {
+                hook_traced
+                    .lock()
+                    .unwrap()
+                    .push((command.to_string(), bytes))
+            }
+        });
+
+        let mut out = Vec::new();
+        out.queue(MoveTo(3, 4))
+            .unwrap()
+            .queue(Print("abc"))
+            .unwrap();
+        super::remove_trace_hook();
+        out.queue(MoveTo(0, 0)).unwrap();
+
+        assert_eq!(
+            *traced.lock().unwrap(),
+            [
+                ("crossterm::cursor::MoveTo".to_string(), 6),
+                ("crossterm::style::Print<&str>".to_string(), 3),
+            ]
+        );
+    }
+
+    #[cfg(feature = "events")]
+    #[test]
+    fn test_event_ansi() {
+        use crate::event::*;
+
+        assert_eq!(ansi_string(EnableFocusChange), "\x1B[?1004h");
+        assert_eq!(ansi_string(DisableFocusChange), "\x1B[?1004l");
+        assert_eq!(ansi_string(EnableApplicationKeypad), "\x1B=");
+        assert_eq!(ansi_string(DisableApplicationKeypad), "\x1B>");
+        #[cfg(feature = "bracketed-paste")]
+        {
+            assert_eq!(ansi_string(EnableBracketedPaste), "\x1B[?2004h");
+            assert_eq!(ansi_string(DisableBracketedPaste), "\x1B[?2004l");
+        }
+        assert_eq!(
+            ansi_string(PushKeyboardEnhancementFlags(
+                KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+            )),
+            "\x1B[>1u"
+        );
+        assert_eq!(ansi_string(PopKeyboardEnhancementFlags), "\x1B[<1u");
+        assert_eq!(ansi_string(EnableModifyOtherKeys(2)), "\x1B[>4;2m");
+        assert_eq!(ansi_string(DisableModifyOtherKeys), "\x1B[>4m");
+        assert_eq!(
+            ansi_string(EnableMouseCapture),
+            "\x1B[?1000h\x1B[?1002h\x1B[?1003h\x1B[?1015h\x1B[?1006h"
+        );
+        assert_eq!(
+            ansi_string(DisableMouseCapture),
+            "\x1B[?1006l\x1B[?1015l\x1B[?1003l\x1B[?1002l\x1B[?1000l"
+        );
+    }
+}
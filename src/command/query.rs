@@ -0,0 +1,421 @@
+//! Terminal queries which are sent together, to wait for a single round trip.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::Arc;
+
+use crate::event::KeyboardEnhancementFlags;
+use crate::terminal::ModeReport;
+use crate::{csi, Command};
+
+/// A function picking the response to a query out of a reply, see [`Query::new`].
+type Parser<T> = Arc<dyn Fn(&QueryReply) -> Option<T> + Send + Sync>;
+
+/// A query which asks the terminal for some of its state, and parses the reply into a `T`.
+///
+/// A query writes its request sequence like any other command, so it can be queued, and
+/// registers a parser which picks its answer out of the replies of the terminal. The replies can
+/// only be read by [`run_queries`], which sends several queries at once and waits for all their
+/// replies together.
+///
+/// The queries answered by most terminals have their own constructors, like
+/// [`Query::cursor_position`]. Other queries are created with [`Query::new`] from their request
+/// sequence and a parser. Use [`Query::map`] to run queries with different response types in a
+/// single batch.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::{Query, QueryReply};
+///
+/// // Asks for the background color (OSC 11), the reply is e.g. `11;rgb:0000/0000/0000`.
+/// let background = Query::new("\x1B]11;?\x1B\\", |reply| match reply {
+///     QueryReply::Osc(osc) => osc.strip_prefix("11;").map(str::to_string),
+///     _ => None,
+/// });
+/// ```
+///
+/// # Notes
+///
+/// Queries aren't supported by the legacy Windows API, see [`run_queries`] for what it answers
+/// there.
+pub struct Query<T> {
+    request: Cow<'static, str>,
+    parse: Parser<T>,
+    /// Answers the query with the WinAPI instead.
+    #[cfg(windows)]
+    winapi: Option<fn() -> io::Result<QueryReply>>,
+}
+
+/// A reply of the terminal, as handed to the parser of a [`Query`].
+///
+/// The terminal doesn't tell which query a reply answers, the parser of a query has to
+/// recognize its reply, e.g. by the mode number of a private mode report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QueryReply {
+    /// A cursor position report `(column, row)`, the top left cell is represented as `(0, 0)`.
+    CursorPosition(u16, u16),
+    /// The enabled progressive keyboard enhancement flags.
+    KeyboardEnhancementFlags(KeyboardEnhancementFlags),
+    /// The name and version of the terminal, as reported by XTVERSION.
+    TerminalVersion(String),
+    /// The state of the private mode with the given number, as reported by DECRPM.
+    PrivateMode(u16, ModeReport),
+    /// The value of a control function setting as reported by DECRPSS, `None` if the request
+    /// wasn't valid.
+    Setting(Option<String>),
+    /// The reply to an operating system command without the introducer and the terminator,
+    /// e.g. `4;1;rgb:cdcd/0000/0000` for a palette color.
+    Osc(String),
+}
+
+impl<T> Query<T> {
+    /// Creates a query writing `request` and picking its answer with `parse`.
+    ///
+    /// `parse` is called with every reply until it returns a response. Replies it doesn't
+    /// recognize must return `None`, they may answer other queries of the same batch.
+    pub fn new(
+        request: impl Into<Cow<'static, str>>,
+        parse: impl Fn(&QueryReply) -> Option<T> + Send + Sync + 'static,
+    ) -> Self {
+        Query {
+            request: request.into(),
+            parse: Arc::new(parse),
+            #[cfg(windows)]
+            winapi: None,
+        }
+    }
+
+    /// Converts the response of the query with `f`.
+    ///
+    /// This is how queries with different responses are run together, by mapping their
+    /// responses into a shared type.
+    pub fn map<U>(self, f: impl Fn(T) -> U + Send + Sync + 'static) -> Query<U>
+    where
+        T: 'static,
+    {
+        let parse = self.parse;
+        Query {
+            request: self.request,
+            parse: Arc::new(move |reply| parse(reply).map(&f)),
+            #[cfg(windows)]
+            winapi: self.winapi,
+        }
+    }
+
+    /// Returns the response if `reply` answers this query.
+    fn parse(&self, reply: &QueryReply) -> Option<T> {
+        (self.parse)(reply)
+    }
+}
+
+impl Query<(u16, u16)> {
+    /// Queries the cursor position `(column, row)` (`ESC [ 6 n`), the top left cell is
+    /// represented as `(0, 0)`.
+    pub fn cursor_position() -> Self {
+        let query = Query::new(csi!("6n"), |reply| match *reply {
+            QueryReply::CursorPosition(column, row) => Some((column, row)),
+            _ => None,
+        });
+        #[cfg(windows)]
+        let query = Query {
+            winapi: Some(|| {
+                let (column, row) = crate::cursor::position()?;
+                Ok(QueryReply::CursorPosition(column, row))
+            }),
+            ..query
+        };
+        query
+    }
+}
+
+impl Query<KeyboardEnhancementFlags> {
+    /// Queries the enabled progressive keyboard enhancement flags (`ESC [ ? u`).
+    pub fn keyboard_enhancement_flags() -> Self {
+        Query::new(csi!("?u"), |reply| match *reply {
+            QueryReply::KeyboardEnhancementFlags(flags) => Some(flags),
+            _ => None,
+        })
+    }
+}
+
+impl Query<String> {
+    /// Queries the name and version of the terminal (XTVERSION, `ESC [ > 0 q`).
+    pub fn terminal_version() -> Self {
+        Query::new(csi!(">0q"), |reply| match reply {
+            QueryReply::TerminalVersion(version) => Some(version.clone()),
+            _ => None,
+        })
+    }
+}
+
+impl Query<ModeReport> {
+    /// Queries the state of the DEC private mode with the given number (DECRQM,
+    /// `ESC [ ? n $ p`).
+    pub fn private_mode(mode: u16) -> Self {
+        Query::new(format!(csi!("?{}$p"), mode), move |reply| match *reply {
+            QueryReply::PrivateMode(reported, report) if reported == mode => Some(report),
+            _ => None,
+        })
+    }
+}
+
+impl<T> Clone for Query<T> {
+    fn clone(&self) -> Self {
+        Query {
+            request: self.request.clone(),
+            parse: self.parse.clone(),
+            #[cfg(windows)]
+            winapi: self.winapi,
+        }
+    }
+}
+
+impl<T> fmt::Debug for Query<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Query")
+            .field("request", &self.request)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Command for Query<T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(&self.request)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Queries are not implemented for the legacy Windows API.",
+        ))
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        false
+    }
+}
+
+/// Sends the queries to the terminal at once and collects the responses.
+///
+/// All queries are written to `out`, followed by a primary device attributes query, which every
+/// terminal answers. Because terminals answer in order, all replies have been read once the
+/// answer to the last query arrived, so probing several capabilities only takes a single round
+/// trip instead of one per query.
+///
+/// Every reply is handed to the parsers of the queries in order, the first query without a
+/// response yet whose parser accepts the reply gets it. The returned responses are in the same
+/// order as the queries. A response is `None` if the terminal doesn't support the query or
+/// didn't answer within two seconds. Raw mode is enabled while waiting for the replies, if it
+/// isn't enabled already.
+///
+/// On Windows, only [`Query::cursor_position`] is answered, using the WinAPI, and nothing is
+/// written to `out`.
+///
+/// On unix systems, this function will block and possibly time out while
+/// [`event::read`](crate::event::read) or [`event::poll`](crate::event::poll) are being called.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::{run_queries, terminal::ModeReport, Query};
+///
+/// enum Response {
+///     Version(String),
+///     Mode(ModeReport),
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let responses = run_queries(
+///         &mut io::stdout(),
+///         &[
+///             Query::terminal_version().map(Response::Version),
+///             Query::private_mode(2026).map(Response::Mode),
+///         ],
+///     )?;
+///
+///     if let Some(Response::Version(version)) = &responses[0] {
+///         println!("running in {}", version);
+///     }
+///     let synchronized_output = matches!(
+///         responses[1],
+///         Some(Response::Mode(report)) if report.is_supported()
+///     );
+///     println!("synchronized output: {}", synchronized_output);
+///     Ok(())
+/// }
+/// ```
+pub fn run_queries<T>(out: &mut impl Write, queries: &[Query<T>]) -> io::Result<Vec<Option<T>>> {
+    #[cfg(unix)]
+    {
+        use crate::terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode_enabled};
+
+        if is_raw_mode_enabled()? {
+            run_queries_raw(out, queries)
+        } else {
+            enable_raw_mode()?;
+            let responses = run_queries_raw(out, queries);
+            disable_raw_mode()?;
+            responses
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        let _ = out;
+        queries
+            .iter()
+            .map(|query| match query.winapi {
+                Some(reply) => Ok(query.parse(&reply()?)),
+                None => Ok(None),
+            })
+            .collect()
+    }
+}
+
+#[cfg(unix)]
+fn run_queries_raw<T>(out: &mut impl Write, queries: &[Query<T>]) -> io::Result<Vec<Option<T>>> {
+    use std::time::{Duration, Instant};
+
+    use crate::event::{filter::QueryResponseFilter, poll_internal, read_internal, InternalEvent};
+    use crate::QueueableCommand;
+
+    for query in queries {
+        out.queue(query)?;
+    }
+    // ESC [ c          Query primary device attributes.
+    out.write_all(csi!("c").as_bytes())?;
+    out.flush()?;
+
+    let mut responses: Vec<Option<T>> = queries.iter().map(|_| None).collect();
+    let deadline = Instant::now() + Duration::from_millis(2000);
+
+    loop {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        match poll_internal(Some(timeout), &QueryResponseFilter) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(_) => continue,
+        }
+
+        let reply = match read_internal(&QueryResponseFilter)? {
+            InternalEvent::PrimaryDeviceAttributes(_) => break,
+            InternalEvent::CursorPosition(column, row) => QueryReply::CursorPosition(column, row),
+            InternalEvent::KeyboardEnhancementFlags(flags) => {
+                QueryReply::KeyboardEnhancementFlags(flags)
+            }
+            InternalEvent::TerminalVersion(version) => QueryReply::TerminalVersion(version),
+            InternalEvent::PrivateModeReport(mode, value) => {
+                QueryReply::PrivateMode(mode, ModeReport::from_value(value))
+            }
+            InternalEvent::SettingReport(setting) => QueryReply::Setting(setting),
+            InternalEvent::OscReply(osc) => QueryReply::Osc(osc),
+            InternalEvent::Event(_) => continue,
+        };
+
+        answer(queries, &mut responses, &reply);
+    }
+
+    Ok(responses)
+}
+
+/// Hands `reply` to the first query without a response which accepts it.
+///
+/// The same query can be sent more than once, the replies answer them in order.
+#[cfg(unix)]
+fn answer<T>(queries: &[Query<T>], responses: &mut [Option<T>], reply: &QueryReply) {
+    for (query, slot) in queries.iter().zip(responses.iter_mut()) {
+        if slot.is_some() {
+            continue;
+        }
+
+        if let Some(response) = query.parse(reply) {
+            *slot = Some(response);
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Query, QueryReply};
+    use crate::terminal::ModeReport;
+    use crate::Command;
+
+    fn ansi<T>(query: Query<T>) -> String {
+        let mut ansi = String::new();
+        query.write_ansi(&mut ansi).unwrap();
+        ansi
+    }
+
+    #[test]
+    fn test_query_ansi() {
+        assert_eq!(ansi(Query::cursor_position()), "\x1B[6n");
+        assert_eq!(ansi(Query::keyboard_enhancement_flags()), "\x1B[?u");
+        assert_eq!(ansi(Query::terminal_version()), "\x1B[>0q");
+        assert_eq!(ansi(Query::private_mode(2004)), "\x1B[?2004$p");
+        assert_eq!(
+            ansi(Query::new("\x1B]11;?\x1B\\", |_| Some(()))),
+            "\x1B]11;?\x1B\\"
+        );
+    }
+
+    #[test]
+    fn test_query_parse() {
+        let mode = Query::private_mode(2004);
+        assert_eq!(
+            mode.parse(&QueryReply::PrivateMode(2004, ModeReport::Set)),
+            Some(ModeReport::Set)
+        );
+        assert_eq!(
+            mode.parse(&QueryReply::PrivateMode(1049, ModeReport::Set)),
+            None
+        );
+        assert_eq!(mode.parse(&QueryReply::CursorPosition(1, 2)), None);
+
+        let cursor = Query::cursor_position().map(|(column, row)| column + row);
+        assert_eq!(cursor.parse(&QueryReply::CursorPosition(1, 2)), Some(3));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_answer() {
+        #[derive(Debug, PartialEq)]
+        enum Response {
+            Mode(ModeReport),
+            Version(String),
+        }
+
+        let queries = [
+            Query::private_mode(1049).map(Response::Mode),
+            Query::terminal_version().map(Response::Version),
+            Query::private_mode(25).map(Response::Mode),
+            Query::private_mode(25).map(Response::Mode),
+        ];
+        let mut responses = [None, None, None, None];
+        let replies = [
+            QueryReply::PrivateMode(25, ModeReport::Reset),
+            QueryReply::Osc("11;rgb:0000/0000/0000".to_string()),
+            QueryReply::PrivateMode(1049, ModeReport::Set),
+            QueryReply::PrivateMode(25, ModeReport::Set),
+        ];
+        for reply in &replies {
+            super::answer(&queries, &mut responses, reply);
+        }
+
+        assert_eq!(
+            responses,
+            [
+                Some(Response::Mode(ModeReport::Set)),
+                None,
+                Some(Response::Mode(ModeReport::Reset)),
+                Some(Response::Mode(ModeReport::Set)),
+            ]
+        );
+    }
+}
@@ -42,14 +42,116 @@
 //!
 //! For manual execution control check out [crossterm::queue](../macro.queue.html).
 
+use std::cmp::Ordering;
 use std::fmt;
+#[cfg(any(windows, feature = "events"))]
+use std::io;
 
 use crate::{csi, impl_display, Command};
 
 pub(crate) mod sys;
 
-#[cfg(feature = "events")]
-pub use sys::position;
+/// The position of the cursor, as returned by [`position_report`].
+///
+/// Both coordinates are zero-based, the top left cell is at column 0 and row 0. More fields,
+/// e.g. the position in pixels, may be added in the future, use the accessor methods to read
+/// the position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CursorPosition {
+    column: u16,
+    row: u16,
+}
+
+impl CursorPosition {
+    /// Returns the zero-based column of the cursor.
+    pub fn column(&self) -> u16 {
+        self.column
+    }
+
+    /// Returns the zero-based row of the cursor.
+    pub fn row(&self) -> u16 {
+        self.row
+    }
+}
+
+impl From<CursorPosition> for (u16, u16) {
+    fn from(position: CursorPosition) -> Self {
+        (position.column, position.row)
+    }
+}
+
+/// Returns the cursor position (column, row).
+///
+/// The top left cell is represented as `(0, 0)`. See [`position_report`] for a variant that
+/// returns a [`CursorPosition`].
+///
+/// On unix systems, this function will block and possibly time out while
+/// [`crossterm::event::read`](crate::event::read) or [`crossterm::event::poll`](crate::event::poll) are being called.
+#[cfg(any(windows, feature = "events"))]
+pub fn position() -> io::Result<(u16, u16)> {
+    position_report().map(Into::into)
+}
+
+/// Returns the cursor position.
+///
+/// Both coordinates of the returned [`CursorPosition`] are zero-based, the top left cell is at
+/// column 0 and row 0.
+///
+/// On unix systems, the terminal is asked for the position with `ESC [ 6 n`. This function will
+/// block and possibly time out while [`crossterm::event::read`](crate::event::read) or
+/// [`crossterm::event::poll`](crate::event::poll) are being called.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::cursor::position_report;
+///
+/// fn main() -> io::Result<()> {
+///     let position = position_report()?;
+///     println!("column {}, row {}", position.column(), position.row());
+///     Ok(())
+/// }
+/// ```
+#[cfg(any(windows, feature = "events"))]
+pub fn position_report() -> io::Result<CursorPosition> {
+    let (column, row) = sys::position()?;
+    Ok(CursorPosition { column, row })
+}
+
+/// Moves the cursor to the start of the next line, unless it's already in the first column.
+///
+/// This is useful when concatenating output that may or may not end with a newline, it starts a
+/// new line without leaving a blank one behind. `out` is flushed before the cursor position is
+/// queried, so the position reflects everything written so far, and again after the newline.
+///
+/// If the position can't be queried, the newline is written anyway.
+///
+/// On unix systems, this function will block and possibly time out while
+/// [`crossterm::event::read`](crate::event::read) or [`crossterm::event::poll`](crate::event::poll) are being called.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{self, Write};
+/// use crossterm::cursor::ensure_column_zero;
+///
+/// fn main() -> io::Result<()> {
+///     let mut stdout = io::stdout();
+///     write!(stdout, "output without a trailing newline")?;
+///     ensure_column_zero(&mut stdout)
+/// }
+/// ```
+#[cfg(any(windows, feature = "events"))]
+pub fn ensure_column_zero(out: &mut impl io::Write) -> io::Result<()> {
+    out.flush()?;
+    if let Ok((0, _)) = position() {
+        return Ok(());
+    }
+
+    out.write_all(b"\r\n")?;
+    out.flush()
+}
 
 /// A command that moves the terminal cursor to the given position (column, row).
 ///
@@ -70,6 +172,27 @@ impl Command for MoveTo {
     }
 }
 
+/// A command that moves the terminal cursor to the top left cell.
+///
+/// This is the same as `MoveTo(0, 0)` in a shorter sequence, which adds up for renderers that
+/// home the cursor often.
+///
+/// # Notes
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveHome;
+
+impl Command for MoveHome {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("H"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        sys::move_to(0, 0)
+    }
+}
+
 /// A command that moves the terminal cursor down the given number of lines,
 /// and moves it to the first column.
 ///
@@ -140,6 +263,61 @@ impl Command for MoveToColumn {
     }
 }
 
+/// A command that moves the terminal cursor forward to the next tab stop, the given number of
+/// times.
+///
+/// Tab stops are every 8 columns by default and can be changed with
+/// [`SetTabStop`](../terminal/struct.SetTabStop.html) and
+/// [`ClearTabStop`](../terminal/struct.ClearTabStop.html). The cursor stops at the last column if
+/// there are no more tab stops.
+///
+/// # Notes
+/// * This command is 1 based, meaning `MoveToNextTab(1)` moves the cursor to the next tab stop.
+/// * The Windows console has no tab stops, the WinAPI implementation assumes a tab stop every 8
+///   columns.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveToNextTab(pub u16);
+
+impl Command for MoveToNextTab {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, csi!("{}I"), self.0)?;
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        sys::move_to_next_tab(self.0)
+    }
+}
+
+/// A command that moves the terminal cursor backward to the previous tab stop, the given number of
+/// times.
+///
+/// This is the counterpart of [`MoveToNextTab`](struct.MoveToNextTab.html). The cursor stops at
+/// the first column if there are no more tab stops.
+///
+/// # Notes
+/// * This command is 1 based, meaning `MoveToPreviousTab(1)` moves the cursor to the previous
+///   tab stop.
+/// * The Windows console has no tab stops, the WinAPI implementation assumes a tab stop every 8
+///   columns.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveToPreviousTab(pub u16);
+
+impl Command for MoveToPreviousTab {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, csi!("{}Z"), self.0)?;
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        sys::move_to_previous_tab(self.0)
+    }
+}
+
 /// A command that moves the terminal cursor to the given row on the current column.
 ///
 /// # Notes
@@ -370,6 +548,10 @@ impl Command for DisableBlinking {
 /// # Note
 ///
 /// - Commands must be executed/queued for execution otherwise they do nothing.
+/// - The legacy Windows API can only change how much of the cell the cursor fills, from the
+///   bottom. Block shapes fill the whole cell, underscore and bar shapes a thin line at the
+///   bottom, both look like an underscore, and `DefaultUserShape` restores the console default
+///   of a quarter of the cell. Blinking can't be changed.
 #[derive(Clone, Copy)]
 pub enum SetCursorStyle {
     /// Default cursor shape configured by the user.
@@ -403,12 +585,184 @@ impl Command for SetCursorStyle {
 
     #[cfg(windows)]
     fn execute_winapi(&self) -> std::io::Result<()> {
+        let size = match self {
+            SetCursorStyle::DefaultUserShape => 25,
+            SetCursorStyle::BlinkingBlock | SetCursorStyle::SteadyBlock => 100,
+            SetCursorStyle::BlinkingUnderScore
+            | SetCursorStyle::SteadyUnderScore
+            | SetCursorStyle::BlinkingBar
+            | SetCursorStyle::SteadyBar => 10,
+        };
+        sys::set_cursor_size(size)
+    }
+}
+
+/// Returns the command that moves the cursor from `from` to `to` (column, row) with the least
+/// amount of bytes.
+///
+/// Depending on the positions this is either an absolute [MoveTo](./struct.MoveTo.html), a
+/// relative movement, a carriage return or a combination of those. This is useful for renderers
+/// which move the cursor a lot, for example to update only the cells which changed.
+///
+/// # Notes
+/// * Both positions are 0 based, like for [MoveTo](./struct.MoveTo.html).
+/// * The returned command assumes the cursor is at `from`. If it isn't, the cursor might not end
+///   up at `to`.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+pub fn move_command(from: (u16, u16), to: (u16, u16)) -> impl Command {
+    let mut best = CursorMovement {
+        steps: [Some(MovementStep::MoveTo(to.0, to.1)), None],
+        to,
+    };
+
+    if from == to {
+        best.steps = [None, None];
+        return best;
+    }
+
+    let vertical = match from.1.cmp(&to.1) {
+        Ordering::Less => Some(MovementStep::MoveDown(to.1 - from.1)),
+        Ordering::Greater => Some(MovementStep::MoveUp(from.1 - to.1)),
+        Ordering::Equal => None,
+    };
+
+    let mut horizontals = [None; 3];
+    if from.0 != to.0 {
+        horizontals[0] = Some(MovementStep::MoveToColumn(to.0));
+        horizontals[1] = Some(if from.0 < to.0 {
+            MovementStep::MoveRight(to.0 - from.0)
+        } else {
+            MovementStep::MoveLeft(from.0 - to.0)
+        });
+        if to.0 == 0 {
+            horizontals[2] = Some(MovementStep::CarriageReturn);
+        }
+    }
+
+    let mut candidates = Vec::with_capacity(5);
+    if from.0 == to.0 {
+        candidates.push([vertical, None]);
+    }
+    candidates.extend(
+        horizontals
+            .iter()
+            .flatten()
+            .map(|horizontal| [vertical, Some(*horizontal)]),
+    );
+    if to.0 == 0 {
+        match vertical {
+            Some(MovementStep::MoveDown(n)) => {
+                candidates.push([Some(MovementStep::MoveToNextLine(n)), None])
+            }
+            Some(MovementStep::MoveUp(n)) => {
+                candidates.push([Some(MovementStep::MoveToPreviousLine(n)), None])
+            }
+            _ => {}
+        }
+    }
+
+    for steps in candidates {
+        let candidate = CursorMovement { steps, to };
+        if candidate.len() < best.len() {
+            best = candidate;
+        }
+    }
+
+    best
+}
+
+/// The command returned by [move_command].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CursorMovement {
+    steps: [Option<MovementStep>; 2],
+    to: (u16, u16),
+}
+
+impl CursorMovement {
+    /// Returns the length of the ANSI representation in bytes.
+    fn len(&self) -> usize {
+        self.steps.iter().flatten().map(MovementStep::len).sum()
+    }
+}
+
+impl Command for CursorMovement {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        for step in self.steps.iter().flatten() {
+            step.write_ansi(f)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        if self.steps.iter().any(Option::is_some) {
+            sys::move_to(self.to.0, self.to.1)?;
+        }
         Ok(())
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MovementStep {
+    MoveTo(u16, u16),
+    MoveToColumn(u16),
+    MoveUp(u16),
+    MoveDown(u16),
+    MoveLeft(u16),
+    MoveRight(u16),
+    MoveToNextLine(u16),
+    MoveToPreviousLine(u16),
+    CarriageReturn,
+}
+
+impl MovementStep {
+    /// Returns the length of the ANSI representation in bytes.
+    fn len(&self) -> usize {
+        fn digits(mut n: u32) -> usize {
+            let mut digits = 1;
+            while n >= 10 {
+                n /= 10;
+                digits += 1;
+            }
+            digits
+        }
+
+        // Every CSI sequence consists of `ESC [`, the parameters and a final byte.
+        match *self {
+            MovementStep::MoveTo(column, row) => {
+                4 + digits(column as u32 + 1) + digits(row as u32 + 1)
+            }
+            MovementStep::MoveToColumn(column) => 3 + digits(column as u32 + 1),
+            MovementStep::MoveUp(n)
+            | MovementStep::MoveDown(n)
+            | MovementStep::MoveLeft(n)
+            | MovementStep::MoveRight(n)
+            | MovementStep::MoveToNextLine(n)
+            | MovementStep::MoveToPreviousLine(n) => 3 + digits(n as u32),
+            MovementStep::CarriageReturn => 1,
+        }
+    }
+
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        match *self {
+            MovementStep::MoveTo(column, row) => MoveTo(column, row).write_ansi(f),
+            MovementStep::MoveToColumn(column) => MoveToColumn(column).write_ansi(f),
+            MovementStep::MoveUp(n) => MoveUp(n).write_ansi(f),
+            MovementStep::MoveDown(n) => MoveDown(n).write_ansi(f),
+            MovementStep::MoveLeft(n) => MoveLeft(n).write_ansi(f),
+            MovementStep::MoveRight(n) => MoveRight(n).write_ansi(f),
+            MovementStep::MoveToNextLine(n) => MoveToNextLine(n).write_ansi(f),
+            MovementStep::MoveToPreviousLine(n) => MoveToPreviousLine(n).write_ansi(f),
+            MovementStep::CarriageReturn => f.write_str("\r"),
+        }
+    }
+}
+
 impl_display!(for MoveTo);
+impl_display!(for MoveHome);
 impl_display!(for MoveToColumn);
+impl_display!(for MoveToNextTab);
+impl_display!(for MoveToPreviousTab);
 impl_display!(for MoveToRow);
 impl_display!(for MoveToNextLine);
 impl_display!(for MoveToPreviousLine);
@@ -432,8 +786,11 @@ mod tests {
     use crate::execute;
 
     use super::{
-        sys::position, MoveDown, MoveLeft, MoveRight, MoveTo, MoveUp, RestorePosition, SavePosition,
+        ensure_column_zero, move_command, position, position_report, CursorPosition, MoveDown,
+        MoveLeft, MoveRight, MoveTo, MoveToNextLine, MoveToNextTab, MoveToPreviousLine,
+        MoveToPreviousTab, MoveUp, RestorePosition, SavePosition,
     };
+    use crate::Command;
 
     // Test is disabled, because it's failing on Travis
     #[test]
@@ -448,6 +805,22 @@ mod tests {
         assert_eq!(position().unwrap(), (saved_x, saved_y));
     }
 
+    #[test]
+    fn test_cursor_position_into_tuple() {
+        let position = CursorPosition { column: 3, row: 7 };
+        assert_eq!(position.column(), 3);
+        assert_eq!(position.row(), 7);
+        assert_eq!(<(u16, u16)>::from(position), (3, 7));
+    }
+
+    // Test is disabled, because it's failing on Travis
+    #[test]
+    #[ignore]
+    fn test_position_report() {
+        let report = position_report().unwrap();
+        assert_eq!(position().unwrap(), (report.column(), report.row()));
+    }
+
     // Test is disabled, because it's failing on Travis
     #[test]
     #[ignore]
@@ -482,6 +855,71 @@ mod tests {
         assert_eq!(position().unwrap(), (0, 2));
     }
 
+    // Test is disabled, because it's failing on Travis
+    #[test]
+    #[ignore]
+    fn test_ensure_column_zero() {
+        execute!(stdout(), MoveTo(3, 0)).unwrap();
+        ensure_column_zero(&mut stdout()).unwrap();
+        assert_eq!(position().unwrap(), (0, 1));
+
+        ensure_column_zero(&mut stdout()).unwrap();
+        assert_eq!(position().unwrap(), (0, 1));
+    }
+
+    fn ansi(command: impl Command) -> String {
+        let mut ansi = String::new();
+        command.write_ansi(&mut ansi).unwrap();
+        ansi
+    }
+
+    #[test]
+    fn test_move_command() {
+        // same position
+        assert_eq!(ansi(move_command((5, 5), (5, 5))), "");
+        // same row
+        assert_eq!(ansi(move_command((5, 5), (0, 5))), "\r");
+        assert_eq!(ansi(move_command((5, 5), (7, 5))), "\x1B[8G");
+        assert_eq!(ansi(move_command((95, 5), (96, 5))), "\x1B[1C");
+        assert_eq!(ansi(move_command((50, 5), (3, 5))), "\x1B[4G");
+        // same column
+        assert_eq!(ansi(move_command((5, 5), (5, 2))), "\x1B[3A");
+        assert_eq!(ansi(move_command((5, 5), (5, 6))), "\x1B[1B");
+        // start of another line
+        assert_eq!(ansi(move_command((5, 5), (0, 6))), "\x1B[1E");
+        assert_eq!(ansi(move_command((5, 5), (0, 4))), "\x1B[1F");
+        // both row and column change
+        assert_eq!(ansi(move_command((5, 5), (6, 6))), "\x1B[7;7H");
+        assert_eq!(ansi(move_command((995, 995), (996, 996))), "\x1B[1B\x1B[1C");
+        assert_eq!(ansi(move_command((995, 995), (0, 997))), "\x1B[2E");
+        assert_eq!(ansi(move_command((100, 100), (1, 1))), "\x1B[2;2H");
+        assert_eq!(ansi(move_command((0, 0), (9, 9))), "\x1B[10;10H");
+    }
+
+    #[test]
+    fn test_move_to_next_line_ansi() {
+        assert_eq!(MoveToNextLine(1).to_string(), "\x1B[1E");
+        assert_eq!(MoveToNextLine(12).to_string(), "\x1B[12E");
+    }
+
+    #[test]
+    fn test_move_to_next_tab_ansi() {
+        assert_eq!(MoveToNextTab(1).to_string(), "\x1B[1I");
+        assert_eq!(MoveToNextTab(3).to_string(), "\x1B[3I");
+    }
+
+    #[test]
+    fn test_move_to_previous_tab_ansi() {
+        assert_eq!(MoveToPreviousTab(1).to_string(), "\x1B[1Z");
+        assert_eq!(MoveToPreviousTab(3).to_string(), "\x1B[3Z");
+    }
+
+    #[test]
+    fn test_move_to_previous_line_ansi() {
+        assert_eq!(MoveToPreviousLine(1).to_string(), "\x1B[1F");
+        assert_eq!(MoveToPreviousLine(12).to_string(), "\x1B[12F");
+    }
+
     // Test is disabled, because it's failing on Travis
     #[test]
     #[ignore]
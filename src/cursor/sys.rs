@@ -7,8 +7,9 @@ pub use self::unix::position;
 pub use self::windows::position;
 #[cfg(windows)]
 pub(crate) use self::windows::{
-    move_down, move_left, move_right, move_to, move_to_column, move_to_next_line,
-    move_to_previous_line, move_to_row, move_up, restore_position, save_position, show_cursor,
+    move_down, move_left, move_right, move_to, move_to_column, move_to_next_line, move_to_next_tab,
+    move_to_previous_line, move_to_previous_tab, move_to_row, move_up, restore_position,
+    save_position, set_cursor_size, show_cursor,
 };
 
 #[cfg(windows)]
@@ -4,12 +4,17 @@ use std::convert::TryFrom;
 use std::io;
 use std::sync::atomic::{AtomicU64, Ordering};
 
-use crossterm_winapi::{result, Coord, Handle, HandleType, ScreenBuffer};
+use crossterm_winapi::{result, Coord, Handle, ScreenBuffer};
 use winapi::{
     shared::minwindef::{FALSE, TRUE},
-    um::wincon::{SetConsoleCursorInfo, SetConsoleCursorPosition, CONSOLE_CURSOR_INFO, COORD},
+    um::wincon::{
+        GetConsoleCursorInfo, SetConsoleCursorInfo, SetConsoleCursorPosition, CONSOLE_CURSOR_INFO,
+        COORD,
+    },
 };
 
+use crate::terminal::sys::{output_handle, output_screen_buffer};
+
 /// The position of the cursor, written when you save the cursor's position.
 ///
 /// This is `u64::MAX` initially. Otherwise, it stores the cursor's x position bit-shifted left 16
@@ -20,7 +25,7 @@ static SAVED_CURSOR_POS: AtomicU64 = AtomicU64::new(u64::MAX);
 // We can calculate the relative cursor position by subtracting the top position of the terminal window from the y position.
 // This results in an 1-based coord zo subtract 1 to make cursor position 0-based.
 pub fn parse_relative_y(y: i16) -> std::io::Result<i16> {
-    let window = ScreenBuffer::current()?.info()?;
+    let window = output_screen_buffer()?.info()?;
 
     let window_size = window.terminal_window();
     let screen_size = window.terminal_size();
@@ -45,7 +50,17 @@ pub fn position() -> io::Result<(u16, u16)> {
 }
 
 pub(crate) fn show_cursor(show_cursor: bool) -> std::io::Result<()> {
-    ScreenBufferCursor::from(Handle::current_out_handle()?).set_visibility(show_cursor)
+    ScreenBufferCursor::from(output_handle()?).set_visibility(show_cursor)
+}
+
+/// Sets the percentage (1-100) of the cell the cursor fills, from the bottom.
+pub(crate) fn set_cursor_size(size: u32) -> std::io::Result<()> {
+    ScreenBufferCursor::output()?.set_size(size)
+}
+
+/// Returns the percentage (1-100) of the cell the cursor fills, from the bottom.
+pub(crate) fn cursor_size() -> std::io::Result<u32> {
+    Ok(ScreenBufferCursor::output()?.info()?.dwSize)
 }
 
 pub(crate) fn move_to(column: u16, row: u16) -> std::io::Result<()> {
@@ -84,6 +99,34 @@ pub(crate) fn move_to_column(new_column: u16) -> std::io::Result<()> {
     Ok(())
 }
 
+/// Moves the cursor forward by `count` tab stops.
+///
+/// The console has no tab stops, so this assumes the default tab stops every 8 columns.
+pub(crate) fn move_to_next_tab(count: u16) -> std::io::Result<()> {
+    const TAB_WIDTH: u16 = 8;
+
+    let (column, row) = position()?;
+    let last_column = output_screen_buffer()?.info()?.buffer_size().width.max(1) as u16 - 1;
+    let next_tab = (column / TAB_WIDTH)
+        .saturating_add(count)
+        .saturating_mul(TAB_WIDTH);
+    move_to(next_tab.min(last_column), row)
+}
+
+/// Moves the cursor backward by `count` tab stops.
+///
+/// The console has no tab stops, so this assumes the default tab stops every 8 columns.
+pub(crate) fn move_to_previous_tab(count: u16) -> std::io::Result<()> {
+    const TAB_WIDTH: u16 = 8;
+
+    let (column, row) = position()?;
+    // The tab stop at or after the cursor, moving back from there.
+    let previous_tab = (column.saturating_add(TAB_WIDTH - 1) / TAB_WIDTH)
+        .saturating_sub(count)
+        .saturating_mul(TAB_WIDTH);
+    move_to(previous_tab, row)
+}
+
 pub(crate) fn move_to_row(new_row: u16) -> std::io::Result<()> {
     let (col, _) = position()?;
     move_to(col, new_row)?;
@@ -98,7 +141,7 @@ pub(crate) fn move_to_next_line(count: u16) -> std::io::Result<()> {
 
 pub(crate) fn move_to_previous_line(count: u16) -> std::io::Result<()> {
     let (_, row) = position()?;
-    move_to(0, row - count)?;
+    move_to(0, row.saturating_sub(count))?;
     Ok(())
 }
 
@@ -120,7 +163,7 @@ struct ScreenBufferCursor {
 impl ScreenBufferCursor {
     fn output() -> std::io::Result<ScreenBufferCursor> {
         Ok(ScreenBufferCursor {
-            screen_buffer: ScreenBuffer::from(Handle::new(HandleType::CurrentOutputHandle)?),
+            screen_buffer: output_screen_buffer()?,
         })
     }
 
@@ -158,16 +201,46 @@ impl ScreenBufferCursor {
         Ok(())
     }
 
+    fn info(&self) -> std::io::Result<CONSOLE_CURSOR_INFO> {
+        let mut cursor_info = CONSOLE_CURSOR_INFO {
+            dwSize: 0,
+            bVisible: FALSE,
+        };
+
+        unsafe {
+            if result(GetConsoleCursorInfo(
+                **self.screen_buffer.handle(),
+                &mut cursor_info,
+            ))
+            .is_err()
+            {
+                return Err(io::Error::last_os_error());
+            }
+        }
+        Ok(cursor_info)
+    }
+
+    fn set_size(&self, size: u32) -> std::io::Result<()> {
+        let cursor_info = CONSOLE_CURSOR_INFO {
+            dwSize: size.clamp(1, 100),
+            ..self.info()?
+        };
+        self.set_info(&cursor_info)
+    }
+
     fn set_visibility(&self, visible: bool) -> std::io::Result<()> {
         let cursor_info = CONSOLE_CURSOR_INFO {
-            dwSize: 100,
             bVisible: if visible { TRUE } else { FALSE },
+            ..self.info()?
         };
+        self.set_info(&cursor_info)
+    }
 
+    fn set_info(&self, cursor_info: &CONSOLE_CURSOR_INFO) -> std::io::Result<()> {
         unsafe {
             if result(SetConsoleCursorInfo(
                 **self.screen_buffer.handle(),
-                &cursor_info,
+                cursor_info,
             ))
             .is_err()
             {
@@ -208,9 +281,24 @@ impl From<Handle> for ScreenBufferCursor {
 #[cfg(test)]
 mod tests {
     use super::{
-        move_down, move_left, move_right, move_to, move_to_column, move_to_next_line,
+        cursor_size, move_down, move_left, move_right, move_to, move_to_column, move_to_next_line,
         move_to_previous_line, move_to_row, move_up, position, restore_position, save_position,
     };
+    use crate::{cursor::SetCursorStyle, Command};
+
+    #[test]
+    fn test_set_cursor_style_winapi() {
+        let original_size = cursor_size().unwrap();
+
+        SetCursorStyle::SteadyBlock.execute_winapi().unwrap();
+        assert_eq!(cursor_size().unwrap(), 100);
+
+        SetCursorStyle::BlinkingBar.execute_winapi().unwrap();
+        assert_eq!(cursor_size().unwrap(), 10);
+
+        super::set_cursor_size(original_size).unwrap();
+        assert_eq!(cursor_size().unwrap(), original_size);
+    }
 
     #[test]
     fn test_move_to_winapi() {
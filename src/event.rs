@@ -37,6 +37,7 @@
 //!             Event::FocusGained => println!("FocusGained"),
 //!             Event::FocusLost => println!("FocusLost"),
 //!             Event::Key(event) => println!("{:?}", event),
+//!             Event::KeyWithText(event, text) => println!("{:?} {:?}", event, text),
 //!             Event::Mouse(event) => println!("{:?}", event),
 //!             #[cfg(feature = "bracketed-paste")]
 //!             Event::Paste(data) => println!("{:?}", data),
@@ -64,6 +65,7 @@
 //!                 Event::FocusGained => println!("FocusGained"),
 //!                 Event::FocusLost => println!("FocusLost"),
 //!                 Event::Key(event) => println!("{:?}", event),
+//!                 Event::KeyWithText(event, text) => println!("{:?} {:?}", event, text),
 //!                 Event::Mouse(event) => println!("{:?}", event),
 //!                 #[cfg(feature = "bracketed-paste")]
 //!                 Event::Paste(data) => println!("Pasted {:?}", data),
@@ -81,6 +83,8 @@
 //! them (`event-*`).
 
 pub(crate) mod filter;
+#[cfg(feature = "event-stream")]
+pub mod probe;
 pub(crate) mod read;
 pub(crate) mod source;
 #[cfg(feature = "event-stream")]
@@ -89,25 +93,41 @@ pub(crate) mod sys;
 pub(crate) mod timeout;
 
 #[cfg(feature = "event-stream")]
-pub use stream::EventStream;
+pub use stream::{EventStream, NextBatch};
 
+#[cfg(unix)]
+use crate::event::source::unix::UnixInternalEventSource;
 use crate::event::{
-    filter::{EventFilter, Filter},
+    filter::{EventFilter, Filter, KeyEventFilter},
     read::InternalEventReader,
     timeout::PollTimeout,
 };
-use crate::{csi, Command};
+#[cfg(unix)]
+use crate::terminal::sys::file_descriptor::FileDesc;
+use crate::terminal::ModeReport;
+use crate::{csi, Command, ExecutableCommand, Query};
 use parking_lot::{MappedMutexGuard, Mutex, MutexGuard};
 use std::fmt;
-use std::time::Duration;
+#[cfg(unix)]
+use std::os::unix::io::RawFd;
+use std::time::{Duration, Instant};
 
 use bitflags::bitflags;
 use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Static instance of `InternalEventReader`.
 /// This needs to be static because there can be one event reader.
 static INTERNAL_EVENT_READER: Mutex<Option<InternalEventReader>> = parking_lot::const_mutex(None);
 
+/// Time to wait for the rest of an escape sequence after a lone `ESC`, see `set_escape_timeout`.
+#[cfg(unix)]
+static ESCAPE_TIMEOUT: Mutex<Duration> = parking_lot::const_mutex(Duration::from_millis(10));
+
+/// Window in which key presses are coalesced into a paste event, see `coalesce_fast_input`.
+#[cfg(feature = "bracketed-paste")]
+static PASTE_WINDOW: Mutex<Option<Duration>> = parking_lot::const_mutex(None);
+
 pub(crate) fn lock_internal_event_reader() -> MappedMutexGuard<'static, InternalEventReader> {
     MutexGuard::map(INTERNAL_EVENT_READER.lock(), |reader| {
         reader.get_or_insert_with(InternalEventReader::default)
@@ -214,10 +234,503 @@ pub fn read() -> std::io::Result<Event> {
     }
 }
 
+/// Returns an iterator over the events, reading them with [`read`](fn.read.html).
+///
+/// Every call to `next` blocks until an [`Event`](enum.Event.html) is available, like `read`
+/// does. Errors of kind [`Interrupted`](std::io::ErrorKind::Interrupted) are yielded and reading
+/// continues afterwards. Any other error is yielded once and ends the iterator, e.g. when the
+/// terminal is closed.
+///
+/// See [`EventStream`](struct.EventStream.html) for a non-blocking, asynchronous alternative.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+///
+/// use crossterm::event::{events, Event, KeyCode};
+///
+/// fn main() -> io::Result<()> {
+///     for event in events() {
+///         match event? {
+///             Event::Key(key) if key.code == KeyCode::Esc => break,
+///             event => println!("{:?}", event),
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn events() -> impl Iterator<Item = std::io::Result<Event>> {
+    read_until_error(read)
+}
+
+/// Calls `read` until it returns an error other than `Interrupted`, and yields its results.
+fn read_until_error(
+    mut read: impl FnMut() -> std::io::Result<Event>,
+) -> impl Iterator<Item = std::io::Result<Event>> {
+    let mut done = false;
+    std::iter::from_fn(move || {
+        if done {
+            return None;
+        }
+
+        let result = read();
+        if let Err(e) = &result {
+            done = e.kind() != std::io::ErrorKind::Interrupted;
+        }
+        Some(result)
+    })
+}
+
+/// Waits for a key event matching `predicate`.
+///
+/// Reads events until a [`KeyEvent`](struct.KeyEvent.html) for which `predicate` returns `true`
+/// is read, and returns it. All other events, like resize and mouse events or non-matching key
+/// events, are read and discarded while waiting.
+///
+/// Returns `Ok(None)` if no matching key event was read before `timeout` elapsed. A `timeout` of
+/// `None` waits until a matching key event is read.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+///
+/// use crossterm::event::{wait_for_key, KeyCode};
+///
+/// fn confirm() -> io::Result<bool> {
+///     println!("Continue? [y/n]");
+///
+///     let key = wait_for_key(
+///         |key| matches!(key.code, KeyCode::Char('y' | 'n')),
+///         None,
+///     )?;
+///     Ok(matches!(key, Some(key) if key.code == KeyCode::Char('y')))
+/// }
+/// ```
+///
+/// # Notes
+///
+/// With [`KeyboardEnhancementFlags::REPORT_EVENT_TYPES`] enabled, the predicate also receives
+/// release and repeat events. Check [`KeyEvent::kind`] to only match key presses.
+pub fn wait_for_key(
+    predicate: impl Fn(&KeyEvent) -> bool,
+    timeout: Option<Duration>,
+) -> std::io::Result<Option<KeyEvent>> {
+    let timeout = PollTimeout::new(timeout);
+
+    loop {
+        if !poll_internal(timeout.leftover(), &EventFilter)? {
+            if timeout.elapsed() {
+                return Ok(None);
+            }
+            continue;
+        }
+
+        // Other events are read as well and discarded.
+        let event = read_internal(&EventFilter)?;
+        if !KeyEventFilter.eval(&event) {
+            continue;
+        }
+
+        if let InternalEvent::Event(Event::Key(key) | Event::KeyWithText(key, _)) = event {
+            if predicate(&key) {
+                return Ok(Some(key));
+            }
+        }
+    }
+}
+
+/// Reads a line of text from stdin using the line editing of the terminal.
+///
+/// If [raw mode](../terminal/index.html#raw-mode) is enabled, it's disabled while reading so the
+/// user sees what they type and can edit it as usual, and enabled again afterwards, also if
+/// reading fails. The returned line doesn't contain the trailing line break.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{self, Write};
+///
+/// use crossterm::event::read_line_cooked;
+///
+/// fn main() -> io::Result<()> {
+///     print!("File name: ");
+///     io::stdout().flush()?;
+///     let name = read_line_cooked()?;
+///     println!("Saving to {}", name);
+///     Ok(())
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * Raw mode is enabled again with the default configuration, a configuration passed to
+///   [`enable_raw_mode_with`](../terminal/fn.enable_raw_mode_with.html) is not restored.
+/// * Input which was already read by [`poll`](fn.poll.html) or [`read`](fn.read.html) but not
+///   returned as an event yet isn't part of the line.
+pub fn read_line_cooked() -> std::io::Result<String> {
+    use crate::terminal;
+    use std::io::BufRead;
+
+    let raw_mode = terminal::is_raw_mode_enabled()?;
+    if raw_mode {
+        terminal::disable_raw_mode()?;
+    }
+
+    let mut line = String::new();
+    let result = std::io::stdin().lock().read_line(&mut line);
+
+    let restored = if raw_mode {
+        terminal::enable_raw_mode()
+    } else {
+        Ok(())
+    };
+    result?;
+    restored?;
+
+    if line.ends_with('\n') {
+        line.pop();
+        if line.ends_with('\r') {
+            line.pop();
+        }
+    }
+    Ok(line)
+}
+
+/// Waits for a single key press and returns the character it types.
+///
+/// If [raw mode](../terminal/index.html#raw-mode) isn't enabled, it's enabled while waiting, so
+/// the key is neither echoed nor does the terminal wait for a line break, and disabled again
+/// afterwards, also if reading fails. Other events and key releases are skipped.
+///
+/// Returns `None` for keys which don't type a character, like `Enter` or the arrow keys, and for
+/// characters typed while `Ctrl` or `Alt` is held, e.g. `Ctrl+C`, which doesn't send a signal
+/// in raw mode.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{self, Write};
+///
+/// use crossterm::event::read_char;
+///
+/// fn main() -> io::Result<()> {
+///     print!("Overwrite the file? [y/N] ");
+///     io::stdout().flush()?;
+///     let overwrite = matches!(read_char()?, Some('y' | 'Y'));
+///     println!();
+///     if overwrite {
+///         println!("Overwriting");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn read_char() -> std::io::Result<Option<char>> {
+    use crate::terminal;
+
+    let raw_mode = terminal::is_raw_mode_enabled()?;
+    if !raw_mode {
+        terminal::enable_raw_mode()?;
+    }
+
+    let result = read_key_press();
+
+    let restored = if raw_mode {
+        Ok(())
+    } else {
+        terminal::disable_raw_mode()
+    };
+    let key = result?;
+    restored?;
+
+    Ok(key_char(&key))
+}
+
+/// Reads events until a key is pressed.
+fn read_key_press() -> std::io::Result<KeyEvent> {
+    loop {
+        if let Event::Key(key) | Event::KeyWithText(key, _) = read()? {
+            if key.kind != KeyEventKind::Release {
+                return Ok(key);
+            }
+        }
+    }
+}
+
+/// Returns the character typed by the key, see [`read_char`].
+fn key_char(key: &KeyEvent) -> Option<char> {
+    match key.code {
+        KeyCode::Char(c)
+            if !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+        {
+            Some(c)
+        }
+        _ => None,
+    }
+}
+
+/// What a [`RenderLoop`](struct.RenderLoop.html) wants the application to do next.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum RenderLoopStep {
+    /// An event was read and should be processed.
+    Event(Event),
+    /// The next frame should be rendered.
+    Render,
+}
+
+/// Interleaves reading events with rendering frames at a limited frame rate.
+///
+/// [`next_step`](struct.RenderLoop.html#method.next_step) returns events as they arrive, and
+/// asks to render a frame once the frame duration has passed since the previous frame. While
+/// waiting, it blocks in [`poll`](fn.poll.html) for the remaining time of the current frame,
+/// which is recomputed after every event.
+///
+/// # Timing
+///
+/// * A frame is due once `frame_duration` has passed since [`RenderLoopStep::Render`] was
+///   returned last. Time spent rendering counts towards the next frame.
+/// * Frames aren't scheduled on a fixed grid, a late frame delays the following frames as well.
+///   If rendering takes longer than `frame_duration`, the next frame is due right away.
+/// * Available events are returned before a due frame, a flood of events can delay a frame until
+///   no event is available anymore.
+/// * The first frame is due right away, it's returned by the first call to `next_step` unless
+///   an event is already available.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::{io, time::Duration};
+///
+/// use crossterm::event::{Event, KeyCode, RenderLoop, RenderLoopStep};
+///
+/// fn main() -> io::Result<()> {
+///     // Render at most 30 frames per second.
+///     let mut render_loop = RenderLoop::new(Duration::from_secs(1) / 30);
+///
+///     loop {
+///         match render_loop.next_step()? {
+///             RenderLoopStep::Event(Event::Key(key)) if key.code == KeyCode::Esc => break,
+///             RenderLoopStep::Event(event) => println!("{:?}\r", event),
+///             RenderLoopStep::Render => { /* draw the frame */ }
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RenderLoop {
+    frame_duration: Duration,
+    last_render: Option<Instant>,
+}
+
+impl RenderLoop {
+    /// Creates a render loop rendering at most one frame every `frame_duration`.
+    pub fn new(frame_duration: Duration) -> RenderLoop {
+        RenderLoop {
+            frame_duration,
+            last_render: None,
+        }
+    }
+
+    /// Returns the duration between two frames.
+    pub fn frame_duration(&self) -> Duration {
+        self.frame_duration
+    }
+
+    /// Blocks until an event is available or the next frame is due.
+    pub fn next_step(&mut self) -> std::io::Result<RenderLoopStep> {
+        loop {
+            if poll(self.remaining(Instant::now()))? {
+                return Ok(RenderLoopStep::Event(read()?));
+            }
+
+            // `poll` can return early, e.g. when it's interrupted by a signal.
+            let now = Instant::now();
+            if self.remaining(now).is_zero() {
+                self.last_render = Some(now);
+                return Ok(RenderLoopStep::Render);
+            }
+        }
+    }
+
+    /// Returns the time left at `now` until the next frame is due.
+    fn remaining(&self, now: Instant) -> Duration {
+        match self.last_render {
+            Some(last_render) => self
+                .frame_duration
+                .saturating_sub(now.saturating_duration_since(last_render)),
+            None => Duration::ZERO,
+        }
+    }
+}
+
+/// Makes [`poll`](fn.poll.html) and [`read`](fn.read.html) read input from the given file descriptor.
+///
+/// By default, events are read from stdin if it is a terminal, and from `/dev/tty` otherwise.
+/// Use this function when the input comes from somewhere else, for example a pseudo terminal
+/// master or a terminal opened by the application itself.
+///
+/// The file descriptor is not closed by crossterm and must stay open as long as events are read.
+/// Call this function before creating an [`EventStream`](struct.EventStream.html), an existing
+/// stream keeps waking the previous source.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::{fs::File, io, os::unix::io::AsRawFd};
+///
+/// use crossterm::event::{read, set_input_fd};
+///
+/// fn main() -> io::Result<()> {
+///     let tty = File::open("/dev/tty")?;
+///     set_input_fd(tty.as_raw_fd())?;
+///
+///     println!("{:?}", read()?);
+///     Ok(())
+/// }
+/// ```
+#[cfg(unix)]
+pub fn set_input_fd(fd: RawFd) -> std::io::Result<()> {
+    let source = UnixInternalEventSource::from_file_descriptor(FileDesc::new(fd, false))?;
+    lock_internal_event_reader().set_source(Box::new(source));
+    Ok(())
+}
+
+/// Coalesces key presses arriving within `window` of each other into an
+/// [`Event::Paste`](enum.Event.html#variant.Paste).
+///
+/// Without [bracketed paste](struct.EnableBracketedPaste.html), pasted text arrives as a key
+/// press per character. This enables a heuristic which treats key presses following each other
+/// faster than a human types as pasted text. A `window` of a few milliseconds is usually enough
+/// to detect pastes without catching fast typing. Pass [`Duration::ZERO`] to disable it again,
+/// it's disabled by default.
+///
+/// Only key presses of characters, `Enter` and `Tab` without modifiers other than `Shift` are
+/// coalesced. `Enter` and `Tab` become `'\n'` and `'\t'` in the pasted text.
+///
+/// # Notes
+///
+/// This is a heuristic and it changes the events an application receives:
+///
+/// * Keys typed very quickly, or keys sent by tools which automate typing, are reported as a
+///   paste as well.
+/// * Slow connections can split pasted text into several paste and key events.
+/// * Every [`read`](fn.read.html) of a key press waits up to `window` for the next key press,
+///   even if [`poll`](fn.poll.html) returned `Ok(true)` before.
+///
+/// Prefer [`EnableBracketedPaste`](struct.EnableBracketedPaste.html) if the terminal supports it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::{io, time::Duration};
+///
+/// use crossterm::event::{coalesce_fast_input, read, Event};
+///
+/// fn main() -> io::Result<()> {
+///     coalesce_fast_input(Duration::from_millis(5));
+///
+///     if let Event::Paste(text) = read()? {
+///         println!("Pasted {:?}", text);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "bracketed-paste")]
+pub fn coalesce_fast_input(window: Duration) {
+    *PASTE_WINDOW.lock() = Some(window).filter(|window| !window.is_zero());
+}
+
+/// Sets how long to wait for the rest of an escape sequence after a lone `ESC` byte.
+///
+/// The Escape key and the start of the escape sequences sent for other keys are the same byte.
+/// Terminals usually send a sequence at once, but over slow connections, like SSH, a sequence
+/// can arrive in parts. If the input read so far ends with `ESC`, the reader waits up to this
+/// timeout for more input before reporting the Escape key. A longer timeout handles slower
+/// connections, but delays the Escape key by the same amount. The default is 10ms, a timeout
+/// of zero reports the Escape key right away.
+///
+/// # Notes
+///
+/// * The escape timeout is independent of the timeout given to [`poll`](fn.poll.html). A `poll`
+///   with a shorter timeout can return `Ok(false)` while the Escape key is still pending, it's
+///   returned by a later `poll` or [`read`](fn.read.html).
+/// * Only has an effect on unix systems, Windows reports keys without escape sequences.
+#[cfg(unix)]
+pub fn set_escape_timeout(timeout: Duration) {
+    *ESCAPE_TIMEOUT.lock() = timeout;
+}
+
+/// Returns the timeout set with `set_escape_timeout`.
+#[cfg(unix)]
+pub(crate) fn escape_timeout() -> Duration {
+    *ESCAPE_TIMEOUT.lock()
+}
+
+/// Parses the bytes of a single event read from the terminal.
+///
+/// This is the parser [`read`](fn.read.html) uses on unix systems, for programs reading the
+/// terminal input themselves. Feed it the bytes read so far, one byte more on every call,
+/// until it returns an event or an error:
+///
+/// * `Ok(None)`: the bytes are the start of an event, more bytes are needed.
+/// * `Ok(Some(event))`: the bytes form a complete event. Clear the buffer before adding the next
+///   byte.
+/// * `Err(_)`: the bytes can't be parsed. Discard them.
+///
+/// Replies to the queries crossterm sends itself, like the cursor position report, are parsed as
+/// well but aren't events. An error of kind `InvalidData` is returned for them.
+///
+/// `input_available` tells whether more input is available right now. It resolves the ambiguity
+/// between a lone `ESC` byte, which is the Escape key, and the start of an escape sequence: if
+/// more input is available, `ESC` is assumed to start a sequence and `Ok(None)` is returned,
+/// otherwise it's parsed as [`KeyCode::Esc`](enum.KeyCode.html#variant.Esc). Terminals write an
+/// escape sequence at once, so pass whether the last read returned more bytes after this one.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+///
+/// use crossterm::event::parse_event;
+///
+/// fn parse(input: &[u8]) -> io::Result<()> {
+///     let mut buffer = Vec::new();
+///
+///     for (i, &byte) in input.iter().enumerate() {
+///         buffer.push(byte);
+///
+///         match parse_event(&buffer, i + 1 < input.len()) {
+///             Ok(Some(event)) => {
+///                 println!("{:?}", event);
+///                 buffer.clear();
+///             }
+///             Ok(None) => {}
+///             Err(_) => buffer.clear(),
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(unix)]
+pub fn parse_event(buffer: &[u8], input_available: bool) -> std::io::Result<Option<Event>> {
+    match sys::unix::parse::parse_event(buffer, input_available)? {
+        Some(InternalEvent::Event(event)) => Ok(Some(event)),
+        Some(_) => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "The input is a reply to a terminal query, not an event.",
+        )),
+        None => Ok(None),
+    }
+}
+
 /// Polls to check if there are any `InternalEvent`s that can be read within the given duration.
 pub(crate) fn poll_internal<F>(timeout: Option<Duration>, filter: &F) -> std::io::Result<bool>
 where
-    F: Filter,
+    F: Filter + ?Sized,
 {
     let (mut reader, timeout) = if let Some(timeout) = timeout {
         let poll_timeout = PollTimeout::new(Some(timeout));
@@ -238,7 +751,15 @@ where
     F: Filter,
 {
     let mut reader = lock_internal_event_reader();
-    reader.read(filter)
+    let event = reader.read(filter)?;
+    let event = reader.coalesce_resize(event, filter)?;
+
+    #[cfg(feature = "bracketed-paste")]
+    if let Some(window) = *PASTE_WINDOW.lock() {
+        return reader.coalesce_fast_input(event, window, filter);
+    }
+
+    Ok(event)
 }
 
 bitflags! {
@@ -246,7 +767,7 @@ bitflags! {
     ///
     /// See <https://sw.kovidgoyal.net/kitty/keyboard-protocol/#progressive-enhancement> for more information.
     ///
-    /// Alternate keys and Unicode codepoints are not yet supported by crossterm.
+    /// Alternate keys are not yet supported by crossterm.
     #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
     #[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
     pub struct KeyboardEnhancementFlags: u8 {
@@ -263,10 +784,12 @@ bitflags! {
         /// Represent all keyboard events as CSI-u sequences. This is required to get repeat/release
         /// events for plain-text keys.
         const REPORT_ALL_KEYS_AS_ESCAPE_CODES = 0b0000_1000;
-        // Send the Unicode codepoint as well as the keycode.
-        //
-        // *Note*: this is not yet supported by crossterm.
-        // const REPORT_ASSOCIATED_TEXT = 0b0001_0000;
+        /// Send the text generated by a key as well as the keycode. Keys which produce text are
+        /// then reported as [`Event::KeyWithText`].
+        ///
+        /// Requires [`REPORT_ALL_KEYS_AS_ESCAPE_CODES`](Self::REPORT_ALL_KEYS_AS_ESCAPE_CODES),
+        /// otherwise text keys are sent as plain characters without any extra information.
+        const REPORT_ASSOCIATED_TEXT = 0b0001_0000;
     }
 }
 
@@ -275,97 +798,474 @@ bitflags! {
 /// Mouse events can be captured with [read](./fn.read.html)/[poll](./fn.poll.html).
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct EnableMouseCapture;
+pub struct EnableMouseCapture;
+
+#[cfg(feature = "events")]
+impl Command for EnableMouseCapture {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(concat!(
+            // Normal tracking: Send mouse X & Y on button press and release
+            csi!("?1000h"),
+            // Button-event tracking: Report button motion events (dragging)
+            csi!("?1002h"),
+            // Any-event tracking: Report all motion events
+            csi!("?1003h"),
+            // RXVT mouse mode: Allows mouse coordinates of >223
+            csi!("?1015h"),
+            // SGR mouse mode: Allows mouse coordinates of >223, preferred over RXVT mode
+            csi!("?1006h"),
+        ))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        sys::windows::enable_mouse_capture()
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        false
+    }
+}
+
+/// A command that disables mouse event capturing.
+///
+/// Mouse events can be captured with [read](./fn.read.html)/[poll](./fn.poll.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisableMouseCapture;
+
+impl Command for DisableMouseCapture {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(concat!(
+            // The inverse commands of EnableMouseCapture, in reverse order.
+            csi!("?1006l"),
+            csi!("?1015l"),
+            csi!("?1003l"),
+            csi!("?1002l"),
+            csi!("?1000l"),
+        ))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        sys::windows::disable_mouse_capture()
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        false
+    }
+}
+
+/// A command that enables mouse event capturing with the urxvt encoding (mode 1015) only.
+///
+/// [`EnableMouseCapture`] already enables the urxvt encoding, along with the SGR encoding (mode
+/// 1006). Terminals that support both use SGR, which is preferred because it can tell which
+/// button was released; urxvt is only used by terminals without SGR support, e.g. older versions
+/// of rxvt-unicode. This command is for the rare case where SGR must stay disabled. If no
+/// extended encoding is supported, the terminal falls back to the X10 encoding, which can't
+/// report coordinates beyond 223. All three encodings are decoded by [read](./fn.read.html).
+///
+/// It should be paired with [`DisableUrxvtMouse`] at the end of execution.
+///
+/// # Notes
+///
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * On the legacy Windows API this is the same as [`EnableMouseCapture`].
+#[cfg(feature = "events")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnableUrxvtMouse;
+
+#[cfg(feature = "events")]
+impl Command for EnableUrxvtMouse {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(concat!(
+            csi!("?1000h"),
+            csi!("?1002h"),
+            csi!("?1003h"),
+            csi!("?1015h"),
+        ))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        sys::windows::enable_mouse_capture()
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        false
+    }
+}
+
+/// A command that disables mouse event capturing with the urxvt encoding.
+///
+/// See [`EnableUrxvtMouse`].
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
+#[cfg(feature = "events")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisableUrxvtMouse;
+
+#[cfg(feature = "events")]
+impl Command for DisableUrxvtMouse {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(concat!(
+            csi!("?1015l"),
+            csi!("?1003l"),
+            csi!("?1002l"),
+            csi!("?1000l"),
+        ))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        sys::windows::disable_mouse_capture()
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        false
+    }
+}
+
+/// Whether [`EnablePixelMouseCapture`] was written more recently than
+/// [`DisablePixelMouseCapture`], i.e. whether SGR mouse reports have pixel coordinates.
+static PIXEL_MOUSE_CAPTURE: AtomicBool = AtomicBool::new(false);
+
+/// Held by the tests which write the pixel mouse capture commands or parse SGR mouse reports.
+#[cfg(test)]
+pub(crate) static PIXEL_MOUSE_CAPTURE_TEST_LOCK: Mutex<()> = parking_lot::const_mutex(());
+
+/// Returns whether SGR mouse reports have pixel coordinates, see [`EnablePixelMouseCapture`].
+#[cfg(unix)]
+pub(crate) fn is_pixel_mouse_capture_enabled() -> bool {
+    PIXEL_MOUSE_CAPTURE.load(Ordering::Relaxed)
+}
+
+/// A command that enables mouse event capturing with pixel coordinates (SGR-Pixels mode 1016).
+///
+/// Mouse events are reported like with [`EnableMouseCapture`], except that the
+/// [`column`](struct.MouseEvent.html#structfield.column) and
+/// [`row`](struct.MouseEvent.html#structfield.row) of every [`MouseEvent`] are the zero-based
+/// horizontal and vertical position in pixels instead of cells. The reports don't say which
+/// unit they use, so writing this command is recorded and SGR reports read afterwards have
+/// [`pixels`](struct.MouseEvent.html#structfield.pixels) set, until
+/// [`DisablePixelMouseCapture`] is written.
+///
+/// Supported by xterm, foot, kitty, WezTerm and Contour among others. Other terminals ignore
+/// mode 1016 but still honour any-event tracking (mode 1003), so they report mouse events in
+/// cells, with the legacy encoding unless SGR mode 1006 is enabled as well, e.g. by
+/// [`EnableMouseCapture`]. In the latter case the reports can't be told apart from pixel
+/// reports, check [`active_mouse_modes`] for `sgr_pixels` to find out whether the terminal
+/// supports the mode.
+///
+/// # Notes
+///
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * Not supported by the legacy Windows API, executing it returns an error.
+#[cfg(feature = "events")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnablePixelMouseCapture;
+
+#[cfg(feature = "events")]
+impl Command for EnablePixelMouseCapture {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        PIXEL_MOUSE_CAPTURE.store(true, Ordering::Relaxed);
+        f.write_str(csi!("?1003;1016h"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        use std::io;
+
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Pixel mouse capture not implemented for the legacy Windows API.",
+        ))
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        false
+    }
+}
+
+/// A command that disables mouse event capturing with pixel coordinates.
+///
+/// See [`EnablePixelMouseCapture`].
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
+#[cfg(feature = "events")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisablePixelMouseCapture;
+
+#[cfg(feature = "events")]
+impl Command for DisablePixelMouseCapture {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        PIXEL_MOUSE_CAPTURE.store(false, Ordering::Relaxed);
+        f.write_str(csi!("?1016;1003l"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        use std::io;
+
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Pixel mouse capture not implemented for the legacy Windows API.",
+        ))
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        false
+    }
+}
+
+/// The mouse modes the terminal reports as active, see [`active_mouse_modes`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct MouseModes {
+    /// Normal tracking (mode 1000): button presses and releases are reported.
+    pub normal_tracking: bool,
+    /// Button-event tracking (mode 1002): motion while a button is pressed is reported.
+    pub button_event_tracking: bool,
+    /// Any-event tracking (mode 1003): all motion is reported.
+    pub any_event_tracking: bool,
+    /// SGR encoding (mode 1006).
+    pub sgr: bool,
+    /// urxvt encoding (mode 1015).
+    pub urxvt: bool,
+    /// SGR-Pixels encoding (mode 1016), coordinates are reported in pixels.
+    pub sgr_pixels: bool,
+}
+
+/// The private modes queried by [`active_mouse_modes`], in the order of the fields of
+/// [`MouseModes`].
+const MOUSE_MODES: [u16; 6] = [1000, 1002, 1003, 1006, 1015, 1016];
+
+impl MouseModes {
+    fn from_responses(responses: &[Option<ModeReport>]) -> Self {
+        let is_set =
+            |index: usize| matches!(responses.get(index), Some(Some(report)) if report.is_set());
+
+        MouseModes {
+            normal_tracking: is_set(0),
+            button_event_tracking: is_set(1),
+            any_event_tracking: is_set(2),
+            sgr: is_set(3),
+            urxvt: is_set(4),
+            sgr_pixels: is_set(5),
+        }
+    }
+}
+
+/// Queries which mouse modes are active.
+///
+/// The state of the mouse tracking modes 1000, 1002 and 1003 and of the encodings 1006, 1015 and
+/// 1016 is queried with DECRQM in a single round trip, see [`run_queries`](crate::run_queries).
+/// This helps to avoid enabling mouse capture twice and to detect a terminal which silently
+/// dropped a mode. A mode the terminal doesn't report, e.g. because it doesn't support DECRQM or
+/// doesn't answer within two seconds, is reported as inactive.
+///
+/// On Windows, all modes are reported as inactive, use the console mode instead.
+///
+/// On unix systems, this function will block and possibly time out while
+/// [`read`](./fn.read.html) or [`poll`](./fn.poll.html) are being called.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::{event::{active_mouse_modes, EnableMouseCapture}, execute};
+///
+/// fn main() -> io::Result<()> {
+///     if !active_mouse_modes()?.normal_tracking {
+///         execute!(io::stdout(), EnableMouseCapture)?;
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn active_mouse_modes() -> std::io::Result<MouseModes> {
+    let queries = MOUSE_MODES.map(Query::private_mode);
+    let responses = crate::run_queries(&mut std::io::stdout(), &queries)?;
+    Ok(MouseModes::from_responses(&responses))
+}
+
+/// A command that enables focus event emission.
+///
+/// It should be paired with [`DisableFocusChange`] at the end of execution.
+///
+/// Focus events can be captured with [read](./fn.read.html)/[poll](./fn.poll.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnableFocusChange;
+
+impl Command for EnableFocusChange {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("?1004h"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        // Focus events are always enabled on Windows
+        Ok(())
+    }
+}
+
+/// A command that disables focus event emission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisableFocusChange;
 
-#[cfg(feature = "events")]
-impl Command for EnableMouseCapture {
+impl Command for DisableFocusChange {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        f.write_str(concat!(
-            // Normal tracking: Send mouse X & Y on button press and release
-            csi!("?1000h"),
-            // Button-event tracking: Report button motion events (dragging)
-            csi!("?1002h"),
-            // Any-event tracking: Report all motion events
-            csi!("?1003h"),
-            // RXVT mouse mode: Allows mouse coordinates of >223
-            csi!("?1015h"),
-            // SGR mouse mode: Allows mouse coordinates of >223, preferred over RXVT mode
-            csi!("?1006h"),
-        ))
+        f.write_str(csi!("?1004l"))
     }
 
     #[cfg(windows)]
     fn execute_winapi(&self) -> std::io::Result<()> {
-        sys::windows::enable_mouse_capture()
+        // Focus events can't be disabled on Windows
+        Ok(())
     }
+}
 
-    #[cfg(windows)]
-    fn is_ansi_code_supported(&self) -> bool {
-        false
+/// Enables focus reporting until it's dropped.
+///
+/// Executes [`EnableFocusChange`] on stdout when created and [`DisableFocusChange`] when
+/// dropped, so focus reporting is disabled again even if the application returns early or
+/// panics.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+///
+/// use crossterm::event::{read, Event, FocusReportingGuard};
+///
+/// fn main() -> io::Result<()> {
+///     let _guard = FocusReportingGuard::new()?;
+///
+///     loop {
+///         if let Event::FocusLost = read()? {
+///             break;
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct FocusReportingGuard {
+    _private: (),
+}
+
+impl FocusReportingGuard {
+    /// Enables focus reporting.
+    pub fn new() -> std::io::Result<FocusReportingGuard> {
+        std::io::stdout().execute(EnableFocusChange)?;
+        Ok(FocusReportingGuard { _private: () })
     }
 }
 
-/// A command that disables mouse event capturing.
-///
-/// Mouse events can be captured with [read](./fn.read.html)/[poll](./fn.poll.html).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct DisableMouseCapture;
+impl Drop for FocusReportingGuard {
+    fn drop(&mut self) {
+        let _ = std::io::stdout().execute(DisableFocusChange);
+    }
+}
 
-impl Command for DisableMouseCapture {
-    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        f.write_str(concat!(
-            // The inverse commands of EnableMouseCapture, in reverse order.
-            csi!("?1006l"),
-            csi!("?1015l"),
-            csi!("?1003l"),
-            csi!("?1002l"),
-            csi!("?1000l"),
-        ))
+/// Queries whether the terminal supports focus reporting.
+///
+/// The terminal is asked for the state of the focus reporting mode with DECRQM. Returns
+/// `Ok(false)` if the terminal doesn't know the mode, can't change it, doesn't answer the query,
+/// or doesn't answer within two seconds.
+///
+/// Windows always reports focus changes, so this always returns `Ok(true)` there.
+///
+/// On unix systems, this function will block and possibly time out while
+/// [`read`](fn.read.html) or [`poll`](fn.poll.html) are being called.
+#[cfg(feature = "events")]
+pub fn supports_focus_change() -> std::io::Result<bool> {
+    #[cfg(unix)]
+    {
+        Ok(crate::terminal::query_private_mode(1004)?.is_supported())
     }
 
     #[cfg(windows)]
-    fn execute_winapi(&self) -> std::io::Result<()> {
-        sys::windows::disable_mouse_capture()
+    {
+        Ok(true)
+    }
+}
+
+/// Queries whether the terminal supports bracketed paste.
+///
+/// The terminal is asked for the state of the bracketed paste mode with DECRQM
+/// (`ESC [ ? 2004 $ p`), followed by a primary device attributes query which every terminal
+/// answers. Returns `Ok(true)` if the terminal reports the mode as set or reset, i.e. it can be
+/// enabled with [`EnableBracketedPaste`], whether or not it's enabled right now. Returns
+/// `Ok(false)` if the terminal doesn't recognize the mode, doesn't support DECRQM, or doesn't
+/// answer within two seconds, in which case pasted text arrives as regular key events (see
+/// [`coalesce_fast_input`]).
+///
+/// This always returns `Ok(false)` on Windows.
+///
+/// On unix systems, this function will block and possibly time out while
+/// [`read`](fn.read.html) or [`poll`](fn.poll.html) are being called.
+#[cfg(feature = "bracketed-paste")]
+pub fn supports_bracketed_paste() -> std::io::Result<bool> {
+    #[cfg(unix)]
+    {
+        Ok(crate::terminal::query_private_mode(2004)?.is_supported())
     }
 
     #[cfg(windows)]
-    fn is_ansi_code_supported(&self) -> bool {
-        false
+    {
+        Ok(false)
     }
 }
 
-/// A command that enables focus event emission.
+/// A command that switches the keypad to application mode.
 ///
-/// It should be paired with [`DisableFocusChange`] at the end of execution.
+/// In application mode the numeric keypad sends its own escape sequences instead of the
+/// characters printed on its keys. These are reported as key events with the
+/// [`KeyEventState::KEYPAD`] state, which makes them distinguishable from the regular keys.
 ///
-/// Focus events can be captured with [read](./fn.read.html)/[poll](./fn.poll.html).
+/// It should be paired with [`DisableApplicationKeypad`] at the end of execution.
+///
+/// # Notes
+///
+/// * Most terminals default to the normal keypad mode.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct EnableFocusChange;
+pub struct EnableApplicationKeypad;
 
-impl Command for EnableFocusChange {
+impl Command for EnableApplicationKeypad {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        f.write_str(csi!("?1004h"))
+        f.write_str("\x1B=")
     }
 
     #[cfg(windows)]
     fn execute_winapi(&self) -> std::io::Result<()> {
-        // Focus events are always enabled on Windows
+        // The keypad mode doesn't affect the input records read with WinAPI
         Ok(())
     }
 }
 
-/// A command that disables focus event emission.
+/// A command that switches the keypad back to normal mode.
+///
+/// See [`EnableApplicationKeypad`] for more information.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct DisableFocusChange;
+pub struct DisableApplicationKeypad;
 
-impl Command for DisableFocusChange {
+impl Command for DisableApplicationKeypad {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        f.write_str(csi!("?1004l"))
+        f.write_str("\x1B>")
     }
 
     #[cfg(windows)]
     fn execute_winapi(&self) -> std::io::Result<()> {
-        // Focus events can't be disabled on Windows
+        // The keypad mode doesn't affect the input records read with WinAPI
         Ok(())
     }
 }
@@ -501,6 +1401,81 @@ impl Command for PopKeyboardEnhancementFlags {
     }
 }
 
+/// A command that enables xterm's `modifyOtherKeys` mode at the given level.
+///
+/// With `modifyOtherKeys` the terminal reports key combinations which are otherwise
+/// indistinguishable, like `Ctrl+Enter` or `Ctrl+Shift+A`, as `CSI 27 ; modifiers ; code ~` or
+/// `CSI code ; modifiers u` sequences. Both forms are decoded into [`KeyEvent`]s with the
+/// correct modifiers.
+///
+/// The level is interpreted as follows:
+///
+/// * `0` - Disables `modifyOtherKeys`, modified keys are reported as usual.
+/// * `1` - Reports modified keys, except for keys with a well-known behavior like `Ctrl+C`
+///   or `Ctrl+A`.
+/// * `2` - Reports all modified keys, including the ones with a well-known behavior.
+///
+/// This is supported by xterm and a few other terminals which do not implement the
+/// [kitty keyboard protocol](PushKeyboardEnhancementFlags).
+///
+/// # Notes
+///
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnableModifyOtherKeys(pub u8);
+
+impl Command for EnableModifyOtherKeys {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, csi!(">4;{}m"), self.0)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        use std::io;
+
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "modifyOtherKeys not implemented for the legacy Windows API.",
+        ))
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        false
+    }
+}
+
+/// A command that resets xterm's `modifyOtherKeys` mode to the terminal's default.
+///
+/// See [`EnableModifyOtherKeys`] for more information.
+///
+/// # Notes
+///
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisableModifyOtherKeys;
+
+impl Command for DisableModifyOtherKeys {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!(">4m"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        use std::io;
+
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "modifyOtherKeys not implemented for the legacy Windows API.",
+        ))
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        false
+    }
+}
+
 /// Represents an event.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(not(feature = "bracketed-paste"), derive(Copy))]
@@ -512,6 +1487,10 @@ pub enum Event {
     FocusLost,
     /// A single key event with additional pressed modifiers.
     Key(KeyEvent),
+    /// A key event together with the text it produced. Only emitted if
+    /// [`KeyboardEnhancementFlags::REPORT_ASSOCIATED_TEXT`] has been enabled and the terminal
+    /// reported text for the key.
+    KeyWithText(KeyEvent, KeyText),
     /// A single mouse event with additional pressed modifiers.
     Mouse(MouseEvent),
     /// A string that was pasted into the terminal. Only emitted if bracketed paste has been
@@ -519,10 +1498,30 @@ pub enum Event {
     #[cfg(feature = "bracketed-paste")]
     Paste(String),
     /// An resize event with new dimensions after resize (columns, rows).
-    /// **Note** that resize events can occur in batches.
+    ///
+    /// Resize events which are pending at once are coalesced into a single one. On unix systems
+    /// its dimensions are queried when the event is read, so they match the current window even
+    /// while it's being resized rapidly.
     Resize(u16, u16),
 }
 
+/// Formats the event in a compact notation, e.g. `Key(Ctrl+Shift+F5)` or
+/// `Mouse(Down Left @ 3,4)`.
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::FocusGained => f.write_str("FocusGained"),
+            Event::FocusLost => f.write_str("FocusLost"),
+            Event::Key(event) => write!(f, "Key({})", event),
+            Event::KeyWithText(event, text) => write!(f, "Key({} {:?})", event, text.as_str()),
+            Event::Mouse(event) => write!(f, "Mouse({})", event),
+            #[cfg(feature = "bracketed-paste")]
+            Event::Paste(data) => write!(f, "Paste({:?})", data),
+            Event::Resize(columns, rows) => write!(f, "Resize({}x{})", columns, rows),
+        }
+    }
+}
+
 /// Represents a mouse event.
 ///
 /// # Platform-specific Notes
@@ -538,6 +1537,12 @@ pub enum Event {
 /// Some platforms/terminals does not report all key modifiers
 /// combinations for all mouse event types. For example - macOS reports
 /// `Ctrl` + left mouse button click as a right mouse button click.
+///
+/// On unix systems, the modifiers are decoded from the button byte of the mouse report (`4` is
+/// `Shift`, `8` is `Alt` and `16` is `Ctrl`). xterm, kitty, WezTerm, foot, Alacritty and VTE based
+/// terminals set these bits, but many terminals handle some combinations themselves and don't
+/// report them at all, e.g. a click with `Shift` held selects text even if mouse capture is
+/// enabled.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct MouseEvent {
@@ -549,6 +1554,32 @@ pub struct MouseEvent {
     pub row: u16,
     /// The key modifiers active when the event occurred.
     pub modifiers: KeyModifiers,
+    /// Whether `column` and `row` are in pixels rather than cells.
+    ///
+    /// The reports don't say which unit they use, so this is set on the SGR reports read after
+    /// [`EnablePixelMouseCapture`] was written, until [`DisablePixelMouseCapture`] is written. It's
+    /// always `false` on Windows.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pixels: bool,
+}
+
+/// Formats the mouse event like `Ctrl+Down Left @ 3,4`, where `3,4` is the column and row, or
+/// like `Moved @ 120,48px` if the position is in pixels.
+impl fmt::Display for MouseEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_modifiers(f, self.modifiers)?;
+        match self.kind {
+            MouseEventKind::Down(button) => write!(f, "Down {:?}", button)?,
+            MouseEventKind::Up(button) => write!(f, "Up {:?}", button)?,
+            MouseEventKind::Drag(button) => write!(f, "Drag {:?}", button)?,
+            kind => write!(f, "{:?}", kind)?,
+        }
+        write!(f, " @ {},{}", self.column, self.row)?;
+        if self.pixels {
+            f.write_str("px")?;
+        }
+        Ok(())
+    }
 }
 
 /// A mouse event kind.
@@ -575,6 +1606,10 @@ pub enum MouseEventKind {
     ScrollDown,
     /// Scrolled mouse wheel upwards (away from the user).
     ScrollUp,
+    /// Scrolled mouse wheel left (mostly on a laptop touchpad).
+    ScrollLeft,
+    /// Scrolled mouse wheel right (mostly on a laptop touchpad).
+    ScrollRight,
 }
 
 /// Represents a mouse button.
@@ -608,6 +1643,65 @@ bitflags! {
     }
 }
 
+impl KeyModifiers {
+    /// Creates the modifiers from whether each of Control, Shift, Alt and Meta is pressed.
+    pub const fn from_flags(ctrl: bool, shift: bool, alt: bool, meta: bool) -> KeyModifiers {
+        let mut bits = 0;
+        if ctrl {
+            bits |= KeyModifiers::CONTROL.bits();
+        }
+        if shift {
+            bits |= KeyModifiers::SHIFT.bits();
+        }
+        if alt {
+            bits |= KeyModifiers::ALT.bits();
+        }
+        if meta {
+            bits |= KeyModifiers::META.bits();
+        }
+        KeyModifiers::from_bits_truncate(bits)
+    }
+
+    /// Returns whether Control is pressed.
+    pub const fn ctrl(self) -> bool {
+        self.contains(KeyModifiers::CONTROL)
+    }
+
+    /// Returns whether Shift is pressed.
+    pub const fn shift(self) -> bool {
+        self.contains(KeyModifiers::SHIFT)
+    }
+
+    /// Returns whether Alt is pressed.
+    pub const fn alt(self) -> bool {
+        self.contains(KeyModifiers::ALT)
+    }
+
+    /// Returns whether Meta is pressed.
+    pub const fn meta(self) -> bool {
+        self.contains(KeyModifiers::META)
+    }
+}
+
+/// Writes the modifiers as a `+` separated prefix, e.g. `Ctrl+Alt+`.
+fn write_modifiers(f: &mut fmt::Formatter<'_>, modifiers: KeyModifiers) -> fmt::Result {
+    const NAMES: [(KeyModifiers, &str); 6] = [
+        (KeyModifiers::CONTROL, "Ctrl"),
+        (KeyModifiers::ALT, "Alt"),
+        (KeyModifiers::SHIFT, "Shift"),
+        (KeyModifiers::SUPER, "Super"),
+        (KeyModifiers::HYPER, "Hyper"),
+        (KeyModifiers::META, "Meta"),
+    ];
+
+    for (modifier, name) in NAMES {
+        if modifiers.contains(modifier) {
+            write!(f, "{}+", name)?;
+        }
+    }
+    Ok(())
+}
+
 /// Represents a keyboard event kind.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
@@ -641,6 +1735,13 @@ bitflags! {
 }
 
 /// Represents a key event.
+///
+/// # Control characters
+///
+/// On Unix, the control characters `0x01` to `0x1A` are reported as `Ctrl` plus the matching
+/// lowercase letter. This includes ENQ (`0x05`), which is reported as `Ctrl+E`: a terminal sends
+/// the very same byte when `Ctrl+E` is pressed, so the two can't be told apart. Code reading from
+/// a serial line that may send ENQ should treat `Ctrl+E` accordingly.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialOrd, Clone, Copy)]
 pub struct KeyEvent {
@@ -750,6 +1851,21 @@ impl PartialEq for KeyEvent {
 
 impl Eq for KeyEvent {}
 
+/// Formats the key event like `Ctrl+Shift+F5`.
+///
+/// Repeat and release events are suffixed with ` (Repeat)` and ` (Release)`.
+impl fmt::Display for KeyEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let event = self.normalize_case();
+        write_modifiers(f, event.modifiers)?;
+        write!(f, "{}", event.code)?;
+        match event.kind {
+            KeyEventKind::Press => Ok(()),
+            kind => write!(f, " ({:?})", kind),
+        }
+    }
+}
+
 impl Hash for KeyEvent {
     fn hash<H: Hasher>(&self, hash_state: &mut H) {
         let KeyEvent {
@@ -765,6 +1881,78 @@ impl Hash for KeyEvent {
     }
 }
 
+/// The text generated by a key, as reported in [`Event::KeyWithText`].
+///
+/// The text is stored inline so that events stay `Copy`. It holds up to
+/// [`KeyText::CAPACITY`] bytes of UTF-8, which is enough for a character followed by a few
+/// combining characters.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KeyText {
+    bytes: [u8; KeyText::CAPACITY],
+    len: u8,
+}
+
+impl KeyText {
+    /// The maximum length of the text in bytes.
+    pub const CAPACITY: usize = 16;
+
+    /// Creates a new `KeyText`, or returns `None` if `text` is longer than
+    /// [`KeyText::CAPACITY`] bytes.
+    pub fn new(text: &str) -> Option<KeyText> {
+        if text.len() > KeyText::CAPACITY {
+            return None;
+        }
+
+        let mut bytes = [0; KeyText::CAPACITY];
+        bytes[..text.len()].copy_from_slice(text.as_bytes());
+        Some(KeyText {
+            bytes,
+            len: text.len() as u8,
+        })
+    }
+
+    /// Returns the text as a string slice.
+    pub fn as_str(&self) -> &str {
+        // `bytes` is only ever filled from a `&str` in `KeyText::new`.
+        std::str::from_utf8(&self.bytes[..self.len as usize]).unwrap()
+    }
+}
+
+impl fmt::Debug for KeyText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for KeyText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl AsRef<str> for KeyText {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for KeyText {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for KeyText {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        KeyText::new(&text).ok_or_else(|| {
+            serde::de::Error::invalid_length(text.len(), &"at most 16 bytes of text")
+        })
+    }
+}
+
 /// Represents a media key (as part of [`KeyCode::Media`]).
 #[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -932,6 +2120,22 @@ pub enum KeyCode {
     Modifier(ModifierKeyCode),
 }
 
+/// Formats the key like `F5`, `PageUp` or `a`.
+///
+/// The space character is written as `Space` to keep it visible.
+impl fmt::Display for KeyCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyCode::F(n) => write!(f, "F{}", n),
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Media(media) => write!(f, "{:?}", media),
+            KeyCode::Modifier(modifier) => write!(f, "{:?}", modifier),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
 /// An internal event.
 ///
 /// Encapsulates publicly available `Event` with additional internal
@@ -946,9 +2150,23 @@ pub(crate) enum InternalEvent {
     /// The progressive keyboard enhancement flags enabled by the terminal.
     #[cfg(unix)]
     KeyboardEnhancementFlags(KeyboardEnhancementFlags),
-    /// Attributes and architectural class of the terminal.
+    /// Architectural class of the terminal, followed by its attributes.
+    #[cfg(unix)]
+    PrimaryDeviceAttributes(Vec<u16>),
+    /// The name and version of the terminal, as reported by XTVERSION.
+    #[cfg(unix)]
+    TerminalVersion(String),
+    /// The state of a private mode (`mode`, `value`), as reported by DECRPM.
     #[cfg(unix)]
-    PrimaryDeviceAttributes,
+    PrivateModeReport(u16, u8),
+    /// The value of a control function setting as reported by DECRPSS, `None` if the request
+    /// wasn't valid.
+    #[cfg(unix)]
+    SettingReport(Option<String>),
+    /// The reply to an operating system command, e.g. `4;1;rgb:cdcd/0000/0000` for a palette
+    /// color, without the introducer and the terminator.
+    #[cfg(unix)]
+    OscReply(String),
 }
 
 #[cfg(test)]
@@ -956,7 +2174,220 @@ mod tests {
     use std::collections::hash_map::DefaultHasher;
     use std::hash::{Hash, Hasher};
 
-    use super::{KeyCode, KeyEvent, KeyModifiers};
+    use super::{
+        key_char, read_until_error, DisableApplicationKeypad, DisableModifyOtherKeys,
+        DisablePixelMouseCapture, DisableUrxvtMouse, EnableApplicationKeypad,
+        EnableModifyOtherKeys, EnablePixelMouseCapture, EnableUrxvtMouse, Event, KeyCode, KeyEvent,
+        KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind, MouseModes,
+        RenderLoop,
+    };
+    use crate::terminal::ModeReport;
+    use crate::Command;
+
+    #[test]
+    fn test_mouse_modes_from_responses() {
+        let responses = [
+            Some(ModeReport::Set),
+            Some(ModeReport::Reset),
+            Some(ModeReport::PermanentlySet),
+            None,
+            Some(ModeReport::NotRecognized),
+            Some(ModeReport::Set),
+        ];
+
+        assert_eq!(
+            MouseModes::from_responses(&responses),
+            MouseModes {
+                normal_tracking: true,
+                button_event_tracking: false,
+                any_event_tracking: true,
+                sgr: false,
+                urxvt: false,
+                sgr_pixels: true,
+            }
+        );
+        assert_eq!(MouseModes::from_responses(&[]), MouseModes::default());
+    }
+
+    #[test]
+    fn test_read_until_error_ends_after_fatal_error() {
+        use std::io;
+
+        let mut results = vec![
+            Ok(Event::FocusGained),
+            Err(io::Error::from(io::ErrorKind::Interrupted)),
+            Ok(Event::FocusLost),
+            Err(io::Error::from(io::ErrorKind::BrokenPipe)),
+            Ok(Event::FocusGained),
+        ]
+        .into_iter();
+        let kinds: Vec<_> = read_until_error(|| results.next().unwrap())
+            .map(|result| result.map_err(|e| e.kind()))
+            .collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                Ok(Event::FocusGained),
+                Err(io::ErrorKind::Interrupted),
+                Ok(Event::FocusLost),
+                Err(io::ErrorKind::BrokenPipe),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_loop_remaining() {
+        use std::time::{Duration, Instant};
+
+        let frame = Duration::from_millis(20);
+        let mut render_loop = RenderLoop::new(frame);
+        let start = Instant::now();
+
+        // The first frame is due right away.
+        assert_eq!(render_loop.remaining(start), Duration::ZERO);
+
+        render_loop.last_render = Some(start);
+        assert_eq!(render_loop.remaining(start), frame);
+        assert_eq!(
+            render_loop.remaining(start + Duration::from_millis(15)),
+            Duration::from_millis(5)
+        );
+        assert_eq!(render_loop.remaining(start + frame), Duration::ZERO);
+
+        // A late frame is due right away, the next one is timed from when it was rendered.
+        let late = start + Duration::from_millis(50);
+        assert_eq!(render_loop.remaining(late), Duration::ZERO);
+        render_loop.last_render = Some(late);
+        assert_eq!(
+            render_loop.remaining(start + Duration::from_millis(60)),
+            Duration::from_millis(10)
+        );
+    }
+
+    #[test]
+    fn test_key_char() {
+        let key = |code, modifiers| key_char(&KeyEvent::new(code, modifiers));
+
+        assert_eq!(key(KeyCode::Char('y'), KeyModifiers::NONE), Some('y'));
+        assert_eq!(key(KeyCode::Char('Y'), KeyModifiers::SHIFT), Some('Y'));
+        assert_eq!(key(KeyCode::Char('c'), KeyModifiers::CONTROL), None);
+        assert_eq!(key(KeyCode::Char('x'), KeyModifiers::ALT), None);
+        assert_eq!(key(KeyCode::Enter, KeyModifiers::NONE), None);
+        assert_eq!(key(KeyCode::Up, KeyModifiers::NONE), None);
+    }
+
+    #[test]
+    fn test_key_modifiers_accessors() {
+        let modifiers = KeyModifiers::CONTROL | KeyModifiers::ALT;
+        assert!(modifiers.ctrl());
+        assert!(!modifiers.shift());
+        assert!(modifiers.alt());
+        assert!(!modifiers.meta());
+
+        assert!(KeyModifiers::SHIFT.shift());
+        assert!(KeyModifiers::META.meta());
+        assert!(!KeyModifiers::NONE.ctrl());
+    }
+
+    #[test]
+    fn test_key_modifiers_from_flags() {
+        assert_eq!(
+            KeyModifiers::from_flags(false, false, false, false),
+            KeyModifiers::NONE
+        );
+        assert_eq!(
+            KeyModifiers::from_flags(true, true, false, false),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT
+        );
+        assert_eq!(
+            KeyModifiers::from_flags(false, false, true, true),
+            KeyModifiers::ALT | KeyModifiers::META
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_parse_event() {
+        use super::parse_event;
+
+        assert_eq!(parse_event(b"\x1B", true).unwrap(), None);
+        assert_eq!(
+            parse_event(b"\x1B", false).unwrap(),
+            Some(Event::Key(KeyCode::Esc.into()))
+        );
+        assert_eq!(parse_event(b"\x1B[", false).unwrap(), None);
+        assert_eq!(
+            parse_event(b"\x1B[D", false).unwrap(),
+            Some(Event::Key(KeyCode::Left.into()))
+        );
+        // cursor position report
+        assert_eq!(
+            parse_event(b"\x1B[20;10R", false).unwrap_err().kind(),
+            std::io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let key = |code, modifiers| Event::Key(KeyEvent::new(code, modifiers));
+
+        assert_eq!(
+            key(KeyCode::Char('c'), KeyModifiers::CONTROL).to_string(),
+            "Key(Ctrl+c)"
+        );
+        assert_eq!(
+            key(KeyCode::F(5), KeyModifiers::SHIFT | KeyModifiers::CONTROL).to_string(),
+            "Key(Ctrl+Shift+F5)"
+        );
+        assert_eq!(
+            key(KeyCode::Char('a'), KeyModifiers::SHIFT).to_string(),
+            "Key(Shift+A)"
+        );
+        assert_eq!(
+            key(KeyCode::Char(' '), KeyModifiers::ALT).to_string(),
+            "Key(Alt+Space)"
+        );
+        assert_eq!(
+            KeyEvent::new_with_kind(KeyCode::PageUp, KeyModifiers::NONE, KeyEventKind::Release)
+                .to_string(),
+            "PageUp (Release)"
+        );
+        assert_eq!(
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column: 3,
+                row: 4,
+                modifiers: KeyModifiers::NONE,
+                pixels: false,
+            })
+            .to_string(),
+            "Mouse(Down Left @ 3,4)"
+        );
+        assert_eq!(
+            MouseEvent {
+                kind: MouseEventKind::ScrollUp,
+                column: 0,
+                row: 1,
+                modifiers: KeyModifiers::ALT,
+                pixels: false,
+            }
+            .to_string(),
+            "Alt+ScrollUp @ 0,1"
+        );
+        assert_eq!(
+            MouseEvent {
+                kind: MouseEventKind::Moved,
+                column: 120,
+                row: 48,
+                modifiers: KeyModifiers::NONE,
+                pixels: true,
+            }
+            .to_string(),
+            "Moved @ 120,48px"
+        );
+        assert_eq!(Event::Resize(80, 24).to_string(), "Resize(80x24)");
+    }
 
     #[test]
     fn test_equality() {
@@ -987,4 +2418,50 @@ mod tests {
         assert_eq!(lowercase_d_with_shift_hash, uppercase_d_with_shift_hash);
         assert_eq!(uppercase_d_hash, uppercase_d_with_shift_hash);
     }
+
+    #[test]
+    fn test_modify_other_keys_ansi() {
+        let mut ansi = String::new();
+        EnableModifyOtherKeys(2).write_ansi(&mut ansi).unwrap();
+        assert_eq!(ansi, "\x1B[>4;2m");
+
+        let mut ansi = String::new();
+        DisableModifyOtherKeys.write_ansi(&mut ansi).unwrap();
+        assert_eq!(ansi, "\x1B[>4m");
+    }
+
+    #[test]
+    fn test_pixel_mouse_capture_ansi() {
+        let _lock = super::PIXEL_MOUSE_CAPTURE_TEST_LOCK.lock();
+
+        let mut ansi = String::new();
+        EnablePixelMouseCapture.write_ansi(&mut ansi).unwrap();
+        assert_eq!(ansi, "\x1B[?1003;1016h");
+
+        let mut ansi = String::new();
+        DisablePixelMouseCapture.write_ansi(&mut ansi).unwrap();
+        assert_eq!(ansi, "\x1B[?1016;1003l");
+    }
+
+    #[test]
+    fn test_urxvt_mouse_ansi() {
+        let mut ansi = String::new();
+        EnableUrxvtMouse.write_ansi(&mut ansi).unwrap();
+        assert_eq!(ansi, "\x1B[?1000h\x1B[?1002h\x1B[?1003h\x1B[?1015h");
+
+        let mut ansi = String::new();
+        DisableUrxvtMouse.write_ansi(&mut ansi).unwrap();
+        assert_eq!(ansi, "\x1B[?1015l\x1B[?1003l\x1B[?1002l\x1B[?1000l");
+    }
+
+    #[test]
+    fn test_application_keypad_ansi() {
+        let mut ansi = String::new();
+        EnableApplicationKeypad.write_ansi(&mut ansi).unwrap();
+        assert_eq!(ansi, "\x1B=");
+
+        let mut ansi = String::new();
+        DisableApplicationKeypad.write_ansi(&mut ansi).unwrap();
+        assert_eq!(ansi, "\x1B>");
+    }
 }
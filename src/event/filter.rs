@@ -1,4 +1,4 @@
-use crate::event::InternalEvent;
+use crate::event::{Event, InternalEvent};
 
 /// Interface for filtering an `InternalEvent`.
 pub(crate) trait Filter: Send + Sync + 'static {
@@ -30,7 +30,7 @@ impl Filter for KeyboardEnhancementFlagsFilter {
         // progressive keyboard enhancement.
         matches!(
             *event,
-            InternalEvent::KeyboardEnhancementFlags(_) | InternalEvent::PrimaryDeviceAttributes
+            InternalEvent::KeyboardEnhancementFlags(_) | InternalEvent::PrimaryDeviceAttributes(_)
         )
     }
 }
@@ -42,7 +42,81 @@ pub(crate) struct PrimaryDeviceAttributesFilter;
 #[cfg(unix)]
 impl Filter for PrimaryDeviceAttributesFilter {
     fn eval(&self, event: &InternalEvent) -> bool {
-        matches!(*event, InternalEvent::PrimaryDeviceAttributes)
+        matches!(*event, InternalEvent::PrimaryDeviceAttributes(_))
+    }
+}
+
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub(crate) struct TerminalVersionFilter;
+
+#[cfg(unix)]
+impl Filter for TerminalVersionFilter {
+    fn eval(&self, event: &InternalEvent) -> bool {
+        // Like `KeyboardEnhancementFlagsFilter`, a PrimaryDeviceAttributes response without a
+        // preceding TerminalVersion response means the terminal doesn't support the query.
+        matches!(
+            *event,
+            InternalEvent::TerminalVersion(_) | InternalEvent::PrimaryDeviceAttributes(_)
+        )
+    }
+}
+
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub(crate) struct PrivateModeReportFilter;
+
+#[cfg(unix)]
+impl Filter for PrivateModeReportFilter {
+    fn eval(&self, event: &InternalEvent) -> bool {
+        // A PrimaryDeviceAttributes response without a preceding PrivateModeReport response
+        // means the terminal doesn't support DECRQM.
+        matches!(
+            *event,
+            InternalEvent::PrivateModeReport(_, _) | InternalEvent::PrimaryDeviceAttributes(_)
+        )
+    }
+}
+
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub(crate) struct SettingReportFilter;
+
+#[cfg(unix)]
+impl Filter for SettingReportFilter {
+    fn eval(&self, event: &InternalEvent) -> bool {
+        // The PrimaryDeviceAttributes response marks the end of a batch of DECRQSS requests.
+        matches!(
+            *event,
+            InternalEvent::SettingReport(_) | InternalEvent::PrimaryDeviceAttributes(_)
+        )
+    }
+}
+
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub(crate) struct OscReplyFilter;
+
+#[cfg(unix)]
+impl Filter for OscReplyFilter {
+    fn eval(&self, event: &InternalEvent) -> bool {
+        // The PrimaryDeviceAttributes response marks the end of a batch of OSC queries.
+        matches!(
+            *event,
+            InternalEvent::OscReply(_) | InternalEvent::PrimaryDeviceAttributes(_)
+        )
+    }
+}
+
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub(crate) struct QueryResponseFilter;
+
+#[cfg(unix)]
+impl Filter for QueryResponseFilter {
+    fn eval(&self, event: &InternalEvent) -> bool {
+        // Every response to a query, the PrimaryDeviceAttributes response is the last one.
+        !matches!(*event, InternalEvent::Event(_))
     }
 }
 
@@ -61,6 +135,19 @@ impl Filter for EventFilter {
     }
 }
 
+/// Matches key events, used by `wait_for_key` to pick the key events out of the read events.
+#[derive(Debug, Clone)]
+pub(crate) struct KeyEventFilter;
+
+impl Filter for KeyEventFilter {
+    fn eval(&self, event: &InternalEvent) -> bool {
+        matches!(
+            *event,
+            InternalEvent::Event(Event::Key(_) | Event::KeyWithText(..))
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct InternalEventFilter;
 
@@ -74,8 +161,11 @@ impl Filter for InternalEventFilter {
 #[cfg(unix)]
 mod tests {
     use super::{
-        super::Event, CursorPositionFilter, EventFilter, Filter, InternalEvent,
-        InternalEventFilter, KeyboardEnhancementFlagsFilter, PrimaryDeviceAttributesFilter,
+        super::{Event, KeyCode, KeyEvent, KeyModifiers, KeyText},
+        CursorPositionFilter, EventFilter, Filter, InternalEvent, InternalEventFilter,
+        KeyEventFilter, KeyboardEnhancementFlagsFilter, OscReplyFilter,
+        PrimaryDeviceAttributesFilter, PrivateModeReportFilter, QueryResponseFilter,
+        SettingReportFilter, TerminalVersionFilter,
     };
 
     #[test]
@@ -92,13 +182,55 @@ mod tests {
                 crate::event::KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
             ))
         );
-        assert!(KeyboardEnhancementFlagsFilter.eval(&InternalEvent::PrimaryDeviceAttributes));
+        assert!(
+            KeyboardEnhancementFlagsFilter.eval(&InternalEvent::PrimaryDeviceAttributes(vec![]))
+        );
     }
 
     #[test]
     fn test_primary_device_attributes_filter_filters_primary_device_attributes() {
         assert!(!PrimaryDeviceAttributesFilter.eval(&InternalEvent::Event(Event::Resize(10, 10))));
-        assert!(PrimaryDeviceAttributesFilter.eval(&InternalEvent::PrimaryDeviceAttributes));
+        assert!(PrimaryDeviceAttributesFilter.eval(&InternalEvent::PrimaryDeviceAttributes(vec![])));
+    }
+
+    #[test]
+    fn test_terminal_version_filter_filters_terminal_version() {
+        assert!(!TerminalVersionFilter.eval(&InternalEvent::Event(Event::Resize(10, 10))));
+        assert!(TerminalVersionFilter.eval(&InternalEvent::TerminalVersion("XTerm(380)".into())));
+        assert!(TerminalVersionFilter.eval(&InternalEvent::PrimaryDeviceAttributes(vec![])));
+    }
+
+    #[test]
+    fn test_private_mode_report_filter_filters_private_mode_report() {
+        assert!(!PrivateModeReportFilter.eval(&InternalEvent::Event(Event::Resize(10, 10))));
+        assert!(PrivateModeReportFilter.eval(&InternalEvent::PrivateModeReport(1004, 2)));
+        assert!(PrivateModeReportFilter.eval(&InternalEvent::PrimaryDeviceAttributes(vec![])));
+    }
+
+    #[test]
+    fn test_setting_report_filter_filters_setting_report() {
+        assert!(!SettingReportFilter.eval(&InternalEvent::Event(Event::Resize(10, 10))));
+        assert!(SettingReportFilter.eval(&InternalEvent::SettingReport(Some("0m".to_string()))));
+        assert!(SettingReportFilter.eval(&InternalEvent::SettingReport(None)));
+        assert!(SettingReportFilter.eval(&InternalEvent::PrimaryDeviceAttributes(vec![])));
+    }
+
+    #[test]
+    fn test_osc_reply_filter_filters_osc_reply() {
+        assert!(!OscReplyFilter.eval(&InternalEvent::Event(Event::Resize(10, 10))));
+        assert!(OscReplyFilter.eval(&InternalEvent::OscReply(
+            "4;1;rgb:cdcd/0000/0000".to_string()
+        )));
+        assert!(OscReplyFilter.eval(&InternalEvent::PrimaryDeviceAttributes(vec![])));
+    }
+
+    #[test]
+    fn test_query_response_filter_filters_query_responses() {
+        assert!(!QueryResponseFilter.eval(&InternalEvent::Event(Event::Resize(10, 10))));
+        assert!(QueryResponseFilter.eval(&InternalEvent::CursorPosition(0, 0)));
+        assert!(QueryResponseFilter.eval(&InternalEvent::TerminalVersion("foo".to_string())));
+        assert!(QueryResponseFilter.eval(&InternalEvent::PrivateModeReport(1004, 2)));
+        assert!(QueryResponseFilter.eval(&InternalEvent::PrimaryDeviceAttributes(vec![])));
     }
 
     #[test]
@@ -107,6 +239,25 @@ mod tests {
         assert!(!EventFilter.eval(&InternalEvent::CursorPosition(0, 0)));
     }
 
+    #[test]
+    fn test_key_event_filter_filters_key_events() {
+        assert!(
+            KeyEventFilter.eval(&InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char('y'),
+                KeyModifiers::NONE
+            ))))
+        );
+        assert!(
+            KeyEventFilter.eval(&InternalEvent::Event(Event::KeyWithText(
+                KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE),
+                KeyText::new("y").unwrap()
+            )))
+        );
+        assert!(!KeyEventFilter.eval(&InternalEvent::Event(Event::Resize(10, 10))));
+        assert!(!KeyEventFilter.eval(&InternalEvent::Event(Event::FocusGained)));
+        assert!(!KeyEventFilter.eval(&InternalEvent::CursorPosition(0, 0)));
+    }
+
     #[test]
     fn test_event_filter_filters_internal_events() {
         assert!(InternalEventFilter.eval(&InternalEvent::Event(Event::Resize(10, 10))));
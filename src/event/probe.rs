@@ -0,0 +1,329 @@
+//! # Probe
+//!
+//! Async versions of the capability probes, e.g.
+//! [`terminal::supports_keyboard_enhancement`](crate::terminal::supports_keyboard_enhancement).
+//!
+//! **This module is not available by default. You have to use the `event-stream` feature flag
+//! to make it available.**
+//!
+//! The synchronous probes block the calling thread for up to two seconds while they wait for the
+//! terminal's reply. The probes in this module wait on an [`EventStream`] instead, so the
+//! executor can run other tasks in the meantime. The timeout is driven by the stream's
+//! background thread rather than by sleeping.
+//!
+//! The queries and reply parsers are the same as those of the synchronous probes. Like them, the
+//! probes enable raw mode while waiting for the reply if it isn't enabled already. On Windows
+//! they fall back to the synchronous probes, which don't query the terminal.
+//!
+//! [`supports_focus_change`](crate::event::supports_focus_change) and
+//! [`supports_bracketed_paste`](crate::event::supports_bracketed_paste) have no async version,
+//! they're [`query_private_mode`] with mode 1004 and 2004 followed by
+//! [`ModeReport::is_supported`]. The functions which query the terminal's state rather than its
+//! capabilities, like [`cursor::position`](crate::cursor::position),
+//! [`terminal::size_via_escape`](crate::terminal::size_via_escape) and
+//! [`run_queries`](crate::run_queries), aren't covered by this module.
+//!
+//! ```no_run
+//! use std::io;
+//! use crossterm::event::{probe, EventStream};
+//!
+//! async fn detect() -> io::Result<()> {
+//!     let mut stream = EventStream::new();
+//!     let version = probe::terminal_version(&mut stream).await?;
+//!     let enhanced_keys = probe::supports_keyboard_enhancement(&mut stream).await?;
+//!     println!("{:?} {}", version, enhanced_keys);
+//!     Ok(())
+//! }
+//! ```
+
+use std::io;
+
+use crate::{event::EventStream, style::Color, terminal::ModeReport};
+
+#[cfg(unix)]
+use std::time::Duration;
+
+#[cfg(unix)]
+use crate::{
+    event::{
+        filter::{
+            KeyboardEnhancementFlagsFilter, OscReplyFilter, PrimaryDeviceAttributesFilter,
+            PrivateModeReportFilter, SettingReportFilter, TerminalVersionFilter,
+        },
+        stream::Reply,
+        InternalEvent,
+    },
+    style::{
+        color_count_from_settings, color_count_query, palette_color_from_replies,
+        palette_from_replies, palette_query, palette_unsupported_error, restore_rendition_query,
+        sixel_from_attributes,
+    },
+    terminal::sys::{
+        disable_raw_mode, enable_raw_mode, is_raw_mode_enabled, private_mode_query, write_query,
+        KEYBOARD_ENHANCEMENT_QUERY, PRIMARY_DEVICE_ATTRIBUTES_QUERY, TERMINAL_VERSION_QUERY,
+    },
+};
+
+/// The time to wait for the reply to a query.
+#[cfg(unix)]
+const REPLY_TIMEOUT: Duration = Duration::from_millis(2000);
+
+/// Queries the terminal's support for progressive keyboard enhancement.
+///
+/// The async version of
+/// [`terminal::supports_keyboard_enhancement`](crate::terminal::supports_keyboard_enhancement).
+pub async fn supports_keyboard_enhancement(stream: &mut EventStream) -> io::Result<bool> {
+    #[cfg(unix)]
+    {
+        let _raw_mode = RawModeGuard::new()?;
+        read_supports_keyboard_enhancement(stream).await
+    }
+
+    #[cfg(windows)]
+    {
+        let _ = stream;
+        crate::terminal::supports_keyboard_enhancement()
+    }
+}
+
+/// Queries the name and version of the terminal with XTVERSION.
+///
+/// The async version of [`terminal::terminal_version`](crate::terminal::terminal_version).
+pub async fn terminal_version(stream: &mut EventStream) -> io::Result<Option<String>> {
+    #[cfg(unix)]
+    {
+        let _raw_mode = RawModeGuard::new()?;
+        read_terminal_version(stream).await
+    }
+
+    #[cfg(windows)]
+    {
+        let _ = stream;
+        crate::terminal::terminal_version()
+    }
+}
+
+/// Queries the state of the DEC private mode with the given number.
+///
+/// The async version of [`terminal::query_private_mode`](crate::terminal::query_private_mode).
+pub async fn query_private_mode(stream: &mut EventStream, mode: u16) -> io::Result<ModeReport> {
+    #[cfg(unix)]
+    {
+        let _raw_mode = RawModeGuard::new()?;
+        let value = read_private_mode(stream, mode).await;
+        Ok(value?.map_or(ModeReport::NotRecognized, ModeReport::from_value))
+    }
+
+    #[cfg(windows)]
+    {
+        let _ = stream;
+        crate::terminal::query_private_mode(mode)
+    }
+}
+
+/// Queries whether the terminal supports [Sixel](crate::style::Sixel) graphics.
+///
+/// The async version of [`style::supports_sixel`](crate::style::supports_sixel).
+pub async fn supports_sixel(stream: &mut EventStream) -> io::Result<bool> {
+    #[cfg(unix)]
+    {
+        let _raw_mode = RawModeGuard::new()?;
+        Ok(matches!(
+            read_primary_device_attributes(stream).await?,
+            Some(attributes) if sixel_from_attributes(&attributes)
+        ))
+    }
+
+    #[cfg(windows)]
+    {
+        let _ = stream;
+        crate::style::supports_sixel()
+    }
+}
+
+/// Probes the number of colors the terminal can display.
+///
+/// The async version of [`style::probe_color_count`](crate::style::probe_color_count).
+pub async fn probe_color_count(stream: &mut EventStream) -> io::Result<u32> {
+    #[cfg(unix)]
+    {
+        let _raw_mode = RawModeGuard::new()?;
+        let settings = read_settings(stream, color_count_query().as_bytes()).await?;
+        write_query(restore_rendition_query(&settings).as_bytes())?;
+        Ok(color_count_from_settings(&settings))
+    }
+
+    #[cfg(windows)]
+    {
+        let _ = stream;
+        crate::style::probe_color_count()
+    }
+}
+
+/// Queries the color the terminal displays for the given palette index.
+///
+/// The async version of [`style::query_palette_color`](crate::style::query_palette_color).
+pub async fn query_palette_color(stream: &mut EventStream, index: u8) -> io::Result<Color> {
+    #[cfg(unix)]
+    {
+        let _raw_mode = RawModeGuard::new()?;
+        let replies = read_osc(stream, palette_query(index).as_bytes()).await?;
+        palette_color_from_replies(&replies, index).ok_or_else(palette_unsupported_error)
+    }
+
+    #[cfg(windows)]
+    {
+        let _ = stream;
+        crate::style::query_palette_color(index)
+    }
+}
+
+/// Queries the colors the terminal displays for the 16 base colors of its palette.
+///
+/// The async version of [`style::query_palette`](crate::style::query_palette).
+pub async fn query_palette(stream: &mut EventStream) -> io::Result<[Color; 16]> {
+    #[cfg(unix)]
+    {
+        let _raw_mode = RawModeGuard::new()?;
+        let query: String = (0..16).map(palette_query).collect();
+        let replies = read_osc(stream, query.as_bytes()).await?;
+        palette_from_replies(&replies)
+    }
+
+    #[cfg(windows)]
+    {
+        let _ = stream;
+        crate::style::query_palette()
+    }
+}
+
+/// Enables raw mode if it isn't enabled yet, and disables it again when dropped.
+///
+/// The guard is dropped when the probe's future is dropped as well, so raw mode is also restored
+/// if the probe is cancelled while it waits for the reply.
+#[cfg(unix)]
+struct RawModeGuard {
+    was_enabled: bool,
+}
+
+#[cfg(unix)]
+impl RawModeGuard {
+    fn new() -> io::Result<RawModeGuard> {
+        let was_enabled = is_raw_mode_enabled();
+        if !was_enabled {
+            enable_raw_mode()?;
+        }
+        Ok(RawModeGuard { was_enabled })
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        if !self.was_enabled {
+            let _ = disable_raw_mode();
+        }
+    }
+}
+
+/// Waits for the primary device attributes which are sent after the reply to a query, so they
+/// don't end up in the event queue.
+#[cfg(unix)]
+async fn flush_primary_device_attributes(stream: &mut EventStream) {
+    Reply::new(stream, PrimaryDeviceAttributesFilter, REPLY_TIMEOUT)
+        .await
+        .ok();
+}
+
+#[cfg(unix)]
+async fn read_supports_keyboard_enhancement(stream: &mut EventStream) -> io::Result<bool> {
+    write_query(KEYBOARD_ENHANCEMENT_QUERY)?;
+
+    match Reply::new(stream, KeyboardEnhancementFlagsFilter, REPLY_TIMEOUT).await? {
+        Some(InternalEvent::KeyboardEnhancementFlags(_current_flags)) => {
+            flush_primary_device_attributes(stream).await;
+            Ok(true)
+        }
+        Some(_) => Ok(false),
+        None => Err(io::Error::new(
+            io::ErrorKind::Other,
+            "The keyboard enhancement status could not be read within a normal duration",
+        )),
+    }
+}
+
+#[cfg(unix)]
+async fn read_terminal_version(stream: &mut EventStream) -> io::Result<Option<String>> {
+    write_query(TERMINAL_VERSION_QUERY)?;
+
+    match Reply::new(stream, TerminalVersionFilter, REPLY_TIMEOUT).await? {
+        Some(InternalEvent::TerminalVersion(version)) => {
+            flush_primary_device_attributes(stream).await;
+            Ok(Some(version))
+        }
+        _ => Ok(None),
+    }
+}
+
+#[cfg(unix)]
+async fn read_private_mode(stream: &mut EventStream, mode: u16) -> io::Result<Option<u8>> {
+    write_query(private_mode_query(mode).as_bytes())?;
+
+    loop {
+        match Reply::new(stream, PrivateModeReportFilter, REPLY_TIMEOUT).await? {
+            Some(InternalEvent::PrivateModeReport(reported_mode, value))
+                if reported_mode == mode =>
+            {
+                flush_primary_device_attributes(stream).await;
+                return Ok(Some(value));
+            }
+            // A report of another mode, e.g. an answer to an earlier query
+            Some(InternalEvent::PrivateModeReport(_, _)) => {}
+            _ => return Ok(None),
+        }
+    }
+}
+
+#[cfg(unix)]
+async fn read_primary_device_attributes(stream: &mut EventStream) -> io::Result<Option<Vec<u16>>> {
+    write_query(PRIMARY_DEVICE_ATTRIBUTES_QUERY)?;
+
+    match Reply::new(stream, PrimaryDeviceAttributesFilter, REPLY_TIMEOUT).await? {
+        Some(InternalEvent::PrimaryDeviceAttributes(attributes)) => Ok(Some(attributes)),
+        _ => Ok(None),
+    }
+}
+
+/// Writes `query`, which contains one or more DECRQSS requests, followed by a primary device
+/// attributes request, and collects the DECRPSS replies until the attributes arrive.
+#[cfg(unix)]
+async fn read_settings(stream: &mut EventStream, query: &[u8]) -> io::Result<Vec<Option<String>>> {
+    let mut query = query.to_vec();
+    query.extend_from_slice(PRIMARY_DEVICE_ATTRIBUTES_QUERY);
+    write_query(&query)?;
+
+    let mut settings = Vec::new();
+    while let Some(InternalEvent::SettingReport(setting)) =
+        Reply::new(stream, SettingReportFilter, REPLY_TIMEOUT).await?
+    {
+        settings.push(setting);
+    }
+    Ok(settings)
+}
+
+/// Writes `query`, which contains one or more operating system commands, followed by a primary
+/// device attributes request, and collects the replies until the attributes arrive.
+#[cfg(unix)]
+async fn read_osc(stream: &mut EventStream, query: &[u8]) -> io::Result<Vec<String>> {
+    let mut query = query.to_vec();
+    query.extend_from_slice(PRIMARY_DEVICE_ATTRIBUTES_QUERY);
+    write_query(&query)?;
+
+    let mut replies = Vec::new();
+    while let Some(InternalEvent::OscReply(reply)) =
+        Reply::new(stream, OscReplyFilter, REPLY_TIMEOUT).await?
+    {
+        replies.push(reply);
+    }
+    Ok(replies)
+}
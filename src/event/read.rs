@@ -6,7 +6,10 @@ use crate::event::source::unix::UnixInternalEventSource;
 use crate::event::source::windows::WindowsEventSource;
 #[cfg(feature = "event-stream")]
 use crate::event::sys::Waker;
+use crate::event::Event;
 use crate::event::{filter::Filter, source::EventSource, timeout::PollTimeout, InternalEvent};
+#[cfg(feature = "bracketed-paste")]
+use crate::event::{KeyCode, KeyEventKind, KeyModifiers};
 
 /// Can be used to read `InternalEvent`s.
 pub(crate) struct InternalEventReader {
@@ -33,6 +36,13 @@ impl Default for InternalEventReader {
 }
 
 impl InternalEventReader {
+    /// Replaces the source events are read from.
+    ///
+    /// Events which were already read from the previous source are kept.
+    pub(crate) fn set_source(&mut self, source: Box<dyn EventSource>) {
+        self.source = Some(source);
+    }
+
     /// Returns a `Waker` allowing to wake/force the `poll` method to return `Ok(false)`.
     #[cfg(feature = "event-stream")]
     pub(crate) fn waker(&self) -> Waker {
@@ -41,7 +51,7 @@ impl InternalEventReader {
 
     pub(crate) fn poll<F>(&mut self, timeout: Option<Duration>, filter: &F) -> io::Result<bool>
     where
-        F: Filter,
+        F: Filter + ?Sized,
     {
         for event in &self.events {
             if filter.eval(event) {
@@ -123,6 +133,124 @@ impl InternalEventReader {
             let _ = self.poll(None, filter)?;
         }
     }
+
+    /// Coalesces resize events following `event` into a single one with the latest size.
+    ///
+    /// Only resize events which are available right away are coalesced, so the order of events
+    /// is kept. If the source can tell the current size of the terminal, the returned event
+    /// carries that size instead, since the queued sizes may already be outdated.
+    pub(crate) fn coalesce_resize<F>(
+        &mut self,
+        event: InternalEvent,
+        filter: &F,
+    ) -> io::Result<InternalEvent>
+    where
+        F: Filter,
+    {
+        let mut size = match event {
+            InternalEvent::Event(Event::Resize(columns, rows)) => (columns, rows),
+            _ => return Ok(event),
+        };
+
+        'coalesce: while self.poll(Some(Duration::ZERO), filter)? {
+            // `poll` only tells that a matching event is queued, not where. Takes the queued
+            // resize events in order, events the filter doesn't match are left in the queue and
+            // the first other matching event ends the run.
+            let mut index = 0;
+            while index < self.events.len() {
+                if !filter.eval(&self.events[index]) {
+                    index += 1;
+                    continue;
+                }
+
+                match self.events[index] {
+                    InternalEvent::Event(Event::Resize(columns, rows)) => {
+                        size = (columns, rows);
+                        self.events.remove(index);
+                    }
+                    _ => break 'coalesce,
+                }
+            }
+        }
+
+        if let Some(current) = self.source.as_ref().and_then(|source| source.size()) {
+            size = current;
+        }
+
+        Ok(InternalEvent::Event(Event::Resize(size.0, size.1)))
+    }
+
+    /// Coalesces key presses following `event` within `window` of each other into a paste event.
+    ///
+    /// Returns `event` unchanged if it isn't a key press of a printable character or if no other
+    /// such key press follows within `window`.
+    #[cfg(feature = "bracketed-paste")]
+    pub(crate) fn coalesce_fast_input<F>(
+        &mut self,
+        event: InternalEvent,
+        window: Duration,
+        filter: &F,
+    ) -> io::Result<InternalEvent>
+    where
+        F: Filter,
+    {
+        let mut pasted = match pasted_char(&event) {
+            Some(c) => String::from(c),
+            None => return Ok(event),
+        };
+        let mut count = 1;
+
+        'coalesce: while self.poll(Some(window), filter)? {
+            // Takes the run of queued key presses in order, events the filter doesn't match are
+            // left in the queue and the first other matching event ends the paste.
+            let mut index = 0;
+            while index < self.events.len() {
+                if !filter.eval(&self.events[index]) {
+                    index += 1;
+                    continue;
+                }
+
+                match pasted_char(&self.events[index]) {
+                    Some(c) => {
+                        pasted.push(c);
+                        count += 1;
+                        self.events.remove(index);
+                    }
+                    None => break 'coalesce,
+                }
+            }
+        }
+
+        if count == 1 {
+            return Ok(event);
+        }
+
+        Ok(InternalEvent::Event(Event::Paste(pasted)))
+    }
+}
+
+/// Returns the character a key press contributes to pasted text.
+#[cfg(feature = "bracketed-paste")]
+fn pasted_char(event: &InternalEvent) -> Option<char> {
+    let key = match event {
+        InternalEvent::Event(Event::Key(key) | Event::KeyWithText(key, _))
+            if key.kind == KeyEventKind::Press =>
+        {
+            key
+        }
+        _ => return None,
+    };
+
+    if !(key.modifiers - KeyModifiers::SHIFT).is_empty() {
+        return None;
+    }
+
+    match key.code {
+        KeyCode::Char(c) => Some(c),
+        KeyCode::Enter => Some('\n'),
+        KeyCode::Tab => Some('\t'),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -307,6 +435,27 @@ mod tests {
             .unwrap());
     }
 
+    #[test]
+    fn test_poll_uses_replaced_source() {
+        let mut reader = InternalEventReader {
+            events: VecDeque::new(),
+            source: None,
+            skipped_events: Vec::with_capacity(32),
+        };
+
+        assert!(reader.poll(None, &InternalEventFilter).is_err());
+
+        reader.set_source(Box::new(FakeSource::with_events(&[InternalEvent::Event(
+            Event::Resize(10, 10),
+        )])));
+
+        assert!(reader.poll(None, &InternalEventFilter).unwrap());
+        assert_eq!(
+            reader.read(&InternalEventFilter).unwrap(),
+            InternalEvent::Event(Event::Resize(10, 10))
+        );
+    }
+
     #[test]
     fn test_poll_propagates_error() {
         let mut reader = InternalEventReader {
@@ -377,10 +526,163 @@ mod tests {
         assert_eq!(reader.read(&InternalEventFilter).unwrap(), EVENT);
     }
 
+    #[test]
+    #[cfg(feature = "bracketed-paste")]
+    fn test_coalesce_fast_input() {
+        use super::super::{KeyCode, KeyEvent, KeyModifiers};
+
+        let key =
+            |code, modifiers| InternalEvent::Event(Event::Key(KeyEvent::new(code, modifiers)));
+        let source = FakeSource::with_events(&[
+            key(KeyCode::Char('i'), KeyModifiers::NONE),
+            key(KeyCode::Tab, KeyModifiers::NONE),
+            key(KeyCode::Char('s'), KeyModifiers::SHIFT),
+            key(KeyCode::Enter, KeyModifiers::NONE),
+            key(KeyCode::Left, KeyModifiers::NONE),
+            key(KeyCode::Char('c'), KeyModifiers::CONTROL),
+        ]);
+
+        let mut reader = InternalEventReader {
+            events: VecDeque::new(),
+            source: Some(Box::new(source)),
+            skipped_events: Vec::with_capacity(32),
+        };
+        let window = Duration::from_millis(10);
+        let mut read = || {
+            let event = reader.read(&InternalEventFilter).unwrap();
+            reader
+                .coalesce_fast_input(event, window, &InternalEventFilter)
+                .unwrap()
+        };
+
+        assert_eq!(
+            read(),
+            InternalEvent::Event(Event::Paste("i\ts\n".to_string()))
+        );
+        assert_eq!(read(), key(KeyCode::Left, KeyModifiers::NONE));
+        assert_eq!(read(), key(KeyCode::Char('c'), KeyModifiers::CONTROL));
+    }
+
+    #[test]
+    #[cfg(all(unix, feature = "bracketed-paste"))]
+    fn test_coalesce_fast_input_with_interleaved_events() {
+        use super::super::{filter::EventFilter, KeyCode, KeyEvent, KeyModifiers};
+
+        const CURSOR_POSITION: InternalEvent = InternalEvent::CursorPosition(0, 0);
+        const RESIZE: InternalEvent = InternalEvent::Event(Event::Resize(10, 10));
+
+        let key = |c| {
+            InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char(c),
+                KeyModifiers::NONE,
+            )))
+        };
+        let mut reader = InternalEventReader {
+            events: VecDeque::from(vec![key('a'), CURSOR_POSITION, key('b'), RESIZE, key('c')]),
+            source: Some(Box::new(FakeSource::with_events(&[key('d')]))),
+            skipped_events: Vec::with_capacity(32),
+        };
+        let window = Duration::from_millis(10);
+        let mut read = || {
+            let event = reader.read(&EventFilter).unwrap();
+            reader
+                .coalesce_fast_input(event, window, &EventFilter)
+                .unwrap()
+        };
+
+        assert_eq!(read(), InternalEvent::Event(Event::Paste("ab".to_string())));
+        assert_eq!(read(), RESIZE);
+        assert_eq!(read(), InternalEvent::Event(Event::Paste("cd".to_string())));
+        assert_eq!(reader.read(&InternalEventFilter).unwrap(), CURSOR_POSITION);
+    }
+
+    #[test]
+    fn test_coalesce_resize() {
+        const FOCUS: InternalEvent = InternalEvent::Event(Event::FocusGained);
+
+        let mut reader = InternalEventReader {
+            events: VecDeque::new(),
+            source: Some(Box::new(FakeSource::with_events(&[
+                InternalEvent::Event(Event::Resize(10, 10)),
+                InternalEvent::Event(Event::Resize(20, 15)),
+                InternalEvent::Event(Event::Resize(30, 20)),
+                FOCUS,
+                InternalEvent::Event(Event::Resize(40, 25)),
+            ]))),
+            skipped_events: Vec::with_capacity(32),
+        };
+
+        let mut read = || {
+            let event = reader.read(&InternalEventFilter).unwrap();
+            reader.coalesce_resize(event, &InternalEventFilter).unwrap()
+        };
+
+        // Back-to-back resizes become one, later events keep their order.
+        assert_eq!(read(), InternalEvent::Event(Event::Resize(30, 20)));
+        assert_eq!(read(), FOCUS);
+        assert_eq!(read(), InternalEvent::Event(Event::Resize(40, 25)));
+    }
+
+    #[test]
+    fn test_coalesce_resize_with_interleaved_events() {
+        use super::super::filter::EventFilter;
+
+        const CURSOR_POSITION: InternalEvent = InternalEvent::CursorPosition(0, 0);
+        const FOCUS: InternalEvent = InternalEvent::Event(Event::FocusGained);
+
+        let mut reader = InternalEventReader {
+            events: VecDeque::from(vec![
+                InternalEvent::Event(Event::Resize(10, 10)),
+                CURSOR_POSITION,
+                InternalEvent::Event(Event::Resize(20, 15)),
+                FOCUS,
+                InternalEvent::Event(Event::Resize(30, 20)),
+            ]),
+            source: Some(Box::new(FakeSource::with_events(&[]))),
+            skipped_events: Vec::with_capacity(32),
+        };
+
+        let mut read = || {
+            let event = reader.read(&EventFilter).unwrap();
+            reader.coalesce_resize(event, &EventFilter).unwrap()
+        };
+
+        // The cursor position reply doesn't end the run, the focus event does.
+        assert_eq!(read(), InternalEvent::Event(Event::Resize(20, 15)));
+        assert_eq!(read(), FOCUS);
+        assert_eq!(read(), InternalEvent::Event(Event::Resize(30, 20)));
+        assert_eq!(reader.read(&InternalEventFilter).unwrap(), CURSOR_POSITION);
+    }
+
+    #[test]
+    fn test_coalesce_resize_reports_current_size() {
+        let mut source = FakeSource::with_events(&[
+            InternalEvent::Event(Event::Resize(10, 10)),
+            InternalEvent::Event(Event::Resize(20, 15)),
+        ]);
+        source.size = Some((80, 24));
+
+        let mut reader = InternalEventReader {
+            events: VecDeque::new(),
+            source: Some(Box::new(source)),
+            skipped_events: Vec::with_capacity(32),
+        };
+
+        let event = reader.read(&InternalEventFilter).unwrap();
+        assert_eq!(
+            reader.coalesce_resize(event, &InternalEventFilter).unwrap(),
+            InternalEvent::Event(Event::Resize(80, 24))
+        );
+        assert!(!reader
+            .poll(Some(Duration::from_secs(0)), &InternalEventFilter)
+            .unwrap());
+    }
+
     #[derive(Default)]
     struct FakeSource {
         events: VecDeque<InternalEvent>,
         error: Option<io::Error>,
+        size: Option<(u16, u16)>,
     }
 
     impl FakeSource {
@@ -388,6 +690,7 @@ mod tests {
             FakeSource {
                 events: events.to_vec().into(),
                 error: Some(io::Error::new(io::ErrorKind::Other, "")),
+                size: None,
             }
         }
 
@@ -395,6 +698,7 @@ mod tests {
             FakeSource {
                 events: events.to_vec().into(),
                 error: None,
+                size: None,
             }
         }
     }
@@ -422,6 +726,10 @@ mod tests {
             Ok(None)
         }
 
+        fn size(&self) -> Option<(u16, u16)> {
+            self.size
+        }
+
         #[cfg(feature = "event-stream")]
         fn waker(&self) -> super::super::sys::Waker {
             unimplemented!();
@@ -21,6 +21,13 @@ pub(crate) trait EventSource: Sync + Send {
     /// Returns `Ok(None)` if there's no event available and timeout expires.
     fn try_read(&mut self, timeout: Option<Duration>) -> io::Result<Option<InternalEvent>>;
 
+    /// Returns the current size of the terminal (columns, rows), if the source can tell.
+    ///
+    /// Used to report up-to-date dimensions for resize events, see `coalesce_resize`.
+    fn size(&self) -> Option<(u16, u16)> {
+        None
+    }
+
     /// Returns a `Waker` allowing to wake/force the `try_read` method to return `Ok(None)`.
     #[cfg(feature = "event-stream")]
     fn waker(&self) -> Waker;
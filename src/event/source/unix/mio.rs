@@ -1,4 +1,8 @@
-use std::{collections::VecDeque, io, time::Duration};
+use std::{
+    collections::VecDeque,
+    io,
+    time::{Duration, Instant},
+};
 
 use mio::{unix::SourceFd, Events, Interest, Poll, Token};
 use signal_hook_mio::v0_8::Signals;
@@ -6,7 +10,8 @@ use signal_hook_mio::v0_8::Signals;
 #[cfg(feature = "event-stream")]
 use crate::event::sys::Waker;
 use crate::event::{
-    source::EventSource, sys::unix::parse::parse_event, timeout::PollTimeout, Event, InternalEvent,
+    escape_timeout, source::EventSource, sys::unix::parse::parse_event, timeout::PollTimeout,
+    Event, InternalEvent,
 };
 use crate::terminal::sys::file_descriptor::{tty_fd, FileDesc};
 
@@ -66,6 +71,7 @@ impl UnixInternalEventSource {
 
 impl EventSource for UnixInternalEventSource {
     fn try_read(&mut self, timeout: Option<Duration>) -> io::Result<Option<InternalEvent>> {
+        self.parser.flush_expired();
         if let Some(event) = self.parser.next() {
             return Ok(Some(event));
         }
@@ -73,7 +79,12 @@ impl EventSource for UnixInternalEventSource {
         let timeout = PollTimeout::new(timeout);
 
         loop {
-            if let Err(e) = self.poll.poll(&mut self.events, timeout.leftover()) {
+            let poll_timeout = match (timeout.leftover(), self.parser.pending_timeout()) {
+                (Some(leftover), Some(pending)) => Some(leftover.min(pending)),
+                (leftover, pending) => leftover.or(pending),
+            };
+
+            if let Err(e) = self.poll.poll(&mut self.events, poll_timeout) {
                 // Mio will throw an interrupted error in case of cursor position retrieval. We need to retry until it succeeds.
                 // Previous versions of Mio (< 0.7) would automatically retry the poll call if it was interrupted (if EINTR was returned).
                 // https://docs.rs/mio/0.7.0/mio/struct.Poll.html#notes
@@ -85,8 +96,15 @@ impl EventSource for UnixInternalEventSource {
             };
 
             if self.events.is_empty() {
-                // No readiness events = timeout
-                return Ok(None);
+                // No readiness events = timeout, either the escape timeout or the given one
+                self.parser.flush_expired();
+                if let Some(event) = self.parser.next() {
+                    return Ok(Some(event));
+                }
+                if timeout.elapsed() {
+                    return Ok(None);
+                }
+                continue;
             }
 
             for token in self.events.iter().map(|x| x.token()) {
@@ -130,7 +148,7 @@ impl EventSource for UnixInternalEventSource {
                                     // not a really long time from the absolute time point of view, but
                                     // it's a really long time from the mio, async-std/tokio executor, ...
                                     // point of view.
-                                    let new_size = crate::terminal::size()?;
+                                    let new_size = crate::terminal::sys::size_without_query()?;
                                     return Ok(Some(InternalEvent::Event(Event::Resize(
                                         new_size.0, new_size.1,
                                     ))));
@@ -157,6 +175,10 @@ impl EventSource for UnixInternalEventSource {
         }
     }
 
+    fn size(&self) -> Option<(u16, u16)> {
+        crate::terminal::sys::size_without_query().ok()
+    }
+
     #[cfg(feature = "event-stream")]
     fn waker(&self) -> Waker {
         self.waker.clone()
@@ -173,6 +195,9 @@ impl EventSource for UnixInternalEventSource {
 struct Parser {
     buffer: Vec<u8>,
     internal_events: VecDeque<InternalEvent>,
+    // When the input read so far ended with an incomplete sequence, which is kept until more
+    // input arrives or the escape timeout elapses.
+    pending_since: Option<Instant>,
 }
 
 impl Default for Parser {
@@ -195,14 +220,19 @@ impl Default for Parser {
             // method implementation, all events are consumed before the next TTY_BUFFER
             // is processed -> events pushed.
             internal_events: VecDeque::with_capacity(128),
+            pending_since: None,
         }
     }
 }
 
 impl Parser {
     fn advance(&mut self, buffer: &[u8], more: bool) {
+        // With an escape timeout, a lone ESC at the end of the input is kept until more input
+        // arrives or the timeout elapses, see `flush_expired`.
+        let wait_for_more = !escape_timeout().is_zero();
+
         for (idx, byte) in buffer.iter().enumerate() {
-            let more = idx + 1 < buffer.len() || more;
+            let more = idx + 1 < buffer.len() || more || wait_for_more;
 
             self.buffer.push(*byte);
 
@@ -222,6 +252,36 @@ impl Parser {
                 }
             }
         }
+
+        self.pending_since = if self.buffer.is_empty() {
+            None
+        } else {
+            Some(Instant::now())
+        };
+    }
+
+    /// Returns how much longer to wait for the rest of an incomplete sequence.
+    fn pending_timeout(&self) -> Option<Duration> {
+        self.pending_since
+            .map(|since| escape_timeout().saturating_sub(since.elapsed()))
+    }
+
+    /// Parses an incomplete sequence as if no more input is available, once the escape timeout
+    /// elapsed. This turns a lone ESC into the Escape key.
+    fn flush_expired(&mut self) {
+        if self.pending_timeout() != Some(Duration::ZERO) {
+            return;
+        }
+        self.pending_since = None;
+
+        match parse_event(&self.buffer, false) {
+            Ok(Some(ie)) => {
+                self.internal_events.push_back(ie);
+                self.buffer.clear();
+            }
+            Ok(None) => {}
+            Err(_) => self.buffer.clear(),
+        }
     }
 }
 
@@ -232,3 +292,40 @@ impl Iterator for Parser {
         self.internal_events.pop_front()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::Parser;
+    use crate::event::{escape_timeout, Event, InternalEvent, KeyCode};
+
+    #[test]
+    fn test_lone_escape_waits_for_escape_timeout() {
+        let mut parser = Parser::default();
+
+        parser.advance(b"\x1B", false);
+        parser.flush_expired();
+        assert_eq!(parser.next(), None);
+
+        thread::sleep(escape_timeout() + Duration::from_millis(1));
+        parser.flush_expired();
+        assert_eq!(
+            parser.next(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Esc.into())))
+        );
+    }
+
+    #[test]
+    fn test_escape_sequence_split_across_reads() {
+        let mut parser = Parser::default();
+
+        parser.advance(b"\x1B", false);
+        parser.advance(b"[A", false);
+        assert_eq!(
+            parser.next(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Up.into())))
+        );
+        assert_eq!(parser.next(), None);
+    }
+}
@@ -1,5 +1,10 @@
 use std::os::unix::prelude::AsRawFd;
-use std::{collections::VecDeque, io, os::unix::net::UnixStream, time::Duration};
+use std::{
+    collections::VecDeque,
+    io,
+    os::unix::net::UnixStream,
+    time::{Duration, Instant},
+};
 
 use signal_hook::low_level::pipe;
 
@@ -9,7 +14,9 @@ use filedescriptor::{poll, pollfd, POLLIN};
 
 #[cfg(feature = "event-stream")]
 use crate::event::sys::Waker;
-use crate::event::{source::EventSource, sys::unix::parse::parse_event, InternalEvent};
+use crate::event::{
+    escape_timeout, source::EventSource, sys::unix::parse::parse_event, InternalEvent,
+};
 use crate::terminal::sys::file_descriptor::{tty_fd, FileDesc};
 
 /// Holds a prototypical Waker and a receiver we can wait on when doing select().
@@ -116,12 +123,22 @@ impl EventSource for UnixInternalEventSource {
             make_pollfd(&self.wake_pipe.receiver),
         ];
 
+        self.parser.flush_expired();
+        if let Some(event) = self.parser.next() {
+            return Ok(Some(event));
+        }
+
         while timeout.leftover().map_or(true, |t| !t.is_zero()) {
             // check if there are buffered events from the last read
+            self.parser.flush_expired();
             if let Some(event) = self.parser.next() {
                 return Ok(Some(event));
             }
-            match poll(&mut fds, timeout.leftover()) {
+            let poll_timeout = match (timeout.leftover(), self.parser.pending_timeout()) {
+                (Some(leftover), Some(pending)) => Some(leftover.min(pending)),
+                (leftover, pending) => leftover.or(pending),
+            };
+            match poll(&mut fds, poll_timeout) {
                 Err(filedescriptor::Error::Poll(e)) | Err(filedescriptor::Error::Io(e)) => {
                     match e.kind() {
                         // retry on EINTR
@@ -167,7 +184,7 @@ impl EventSource for UnixInternalEventSource {
                 // not a really long time from the absolute time point of view, but
                 // it's a really long time from the mio, async-std/tokio executor, ...
                 // point of view.
-                let new_size = crate::terminal::size()?;
+                let new_size = crate::terminal::sys::size_without_query()?;
                 return Ok(Some(InternalEvent::Event(Event::Resize(
                     new_size.0, new_size.1,
                 ))));
@@ -188,6 +205,10 @@ impl EventSource for UnixInternalEventSource {
         Ok(None)
     }
 
+    fn size(&self) -> Option<(u16, u16)> {
+        crate::terminal::sys::size_without_query().ok()
+    }
+
     #[cfg(feature = "event-stream")]
     fn waker(&self) -> Waker {
         self.wake_pipe.waker.clone()
@@ -204,6 +225,9 @@ impl EventSource for UnixInternalEventSource {
 struct Parser {
     buffer: Vec<u8>,
     internal_events: VecDeque<InternalEvent>,
+    // When the input read so far ended with an incomplete sequence, which is kept until more
+    // input arrives or the escape timeout elapses.
+    pending_since: Option<Instant>,
 }
 
 impl Default for Parser {
@@ -226,14 +250,19 @@ impl Default for Parser {
             // method implementation, all events are consumed before the next TTY_BUFFER
             // is processed -> events pushed.
             internal_events: VecDeque::with_capacity(128),
+            pending_since: None,
         }
     }
 }
 
 impl Parser {
     fn advance(&mut self, buffer: &[u8], more: bool) {
+        // With an escape timeout, a lone ESC at the end of the input is kept until more input
+        // arrives or the timeout elapses, see `flush_expired`.
+        let wait_for_more = !escape_timeout().is_zero();
+
         for (idx, byte) in buffer.iter().enumerate() {
-            let more = idx + 1 < buffer.len() || more;
+            let more = idx + 1 < buffer.len() || more || wait_for_more;
 
             self.buffer.push(*byte);
 
@@ -253,6 +282,36 @@ impl Parser {
                 }
             }
         }
+
+        self.pending_since = if self.buffer.is_empty() {
+            None
+        } else {
+            Some(Instant::now())
+        };
+    }
+
+    /// Returns how much longer to wait for the rest of an incomplete sequence.
+    fn pending_timeout(&self) -> Option<Duration> {
+        self.pending_since
+            .map(|since| escape_timeout().saturating_sub(since.elapsed()))
+    }
+
+    /// Parses an incomplete sequence as if no more input is available, once the escape timeout
+    /// elapsed. This turns a lone ESC into the Escape key.
+    fn flush_expired(&mut self) {
+        if self.pending_timeout() != Some(Duration::ZERO) {
+            return;
+        }
+        self.pending_since = None;
+
+        match parse_event(&self.buffer, false) {
+            Ok(Some(ie)) => {
+                self.internal_events.push_back(ie);
+                self.buffer.clear();
+            }
+            Ok(None) => {}
+            Err(_) => self.buffer.clear(),
+        }
     }
 }
 
@@ -1,5 +1,6 @@
 use std::{
-    io,
+    future::Future,
+    io, mem,
     pin::Pin,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -12,9 +13,13 @@ use std::{
 };
 
 use futures_core::stream::Stream;
+use parking_lot::Mutex;
 
 use crate::event::{
-    filter::EventFilter, lock_internal_event_reader, poll_internal, read_internal, sys::Waker,
+    filter::{EventFilter, Filter},
+    lock_internal_event_reader, poll_internal, read_internal,
+    sys::Waker,
+    timeout::PollTimeout,
     Event, InternalEvent,
 };
 
@@ -29,32 +34,62 @@ use crate::event::{
 ///
 /// Check the [examples](https://github.com/crossterm-rs/crossterm/tree/master/examples) folder to see how to use
 /// it (`event-stream-*`).
+///
+/// The stream is available on both UNIX and Windows. While no event is available, a background
+/// thread waits on the terminal input (the console input handle on Windows) and wakes the task
+/// polling the stream as soon as an event arrives. Dropping the stream wakes and stops that
+/// thread.
 #[derive(Debug)]
 pub struct EventStream {
     poll_internal_waker: Waker,
     stream_wake_task_executed: Arc<AtomicBool>,
     stream_wake_task_should_shutdown: Arc<AtomicBool>,
+    stream_wake_task_pending: Arc<Mutex<Option<Task>>>,
     task_sender: SyncSender<Task>,
 }
 
 impl Default for EventStream {
     fn default() -> Self {
         let (task_sender, receiver) = mpsc::sync_channel::<Task>(1);
+        let stream_wake_task_pending = Arc::new(Mutex::new(None::<Task>));
+        let pending = stream_wake_task_pending.clone();
 
         thread::spawn(move || {
-            while let Ok(task) = receiver.recv() {
+            while let Ok(mut task) = receiver.recv() {
                 loop {
-                    if let Ok(true) = poll_internal(None, &EventFilter) {
-                        break;
+                    let poll_timeout = PollTimeout::new(task.timeout);
+                    loop {
+                        if let Ok(true) = poll_internal(poll_timeout.leftover(), &*task.filter) {
+                            break;
+                        }
+
+                        if task.stream_wake_task_should_shutdown.load(Ordering::SeqCst) {
+                            break;
+                        }
+
+                        if poll_timeout.elapsed() {
+                            break;
+                        }
                     }
 
-                    if task.stream_wake_task_should_shutdown.load(Ordering::SeqCst) {
-                        break;
+                    // A task queued while this one was running supersedes it, its waker belongs
+                    // to the most recent poll and is the only one that has to be woken up.
+                    let mut pending = pending.lock();
+                    match pending.take() {
+                        Some(next) => {
+                            next.stream_wake_task_should_shutdown
+                                .store(false, Ordering::SeqCst);
+                            task = next;
+                        }
+                        None => {
+                            task.stream_wake_task_executed
+                                .store(false, Ordering::SeqCst);
+                            drop(pending);
+                            task.stream_waker.wake();
+                            break;
+                        }
                     }
                 }
-                task.stream_wake_task_executed
-                    .store(false, Ordering::SeqCst);
-                task.stream_waker.wake();
             }
         });
 
@@ -62,6 +97,7 @@ impl Default for EventStream {
             poll_internal_waker: lock_internal_event_reader().waker(),
             stream_wake_task_executed: Arc::new(AtomicBool::new(false)),
             stream_wake_task_should_shutdown: Arc::new(AtomicBool::new(false)),
+            stream_wake_task_pending,
             task_sender,
         }
     }
@@ -72,12 +108,207 @@ impl EventStream {
     pub fn new() -> EventStream {
         EventStream::default()
     }
+
+    /// Returns a future which collects all events arriving within the given duration.
+    ///
+    /// The future resolves once the duration has passed, with all the events read in the
+    /// meantime. The vector is empty if no event arrived. This makes it easy to handle all
+    /// input once per frame in a render loop.
+    ///
+    /// No busy waiting is involved, the future is woken up as soon as an event arrives or the
+    /// duration has passed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::{io, time::Duration};
+    ///
+    /// use crossterm::event::EventStream;
+    ///
+    /// async fn render_loop() -> io::Result<()> {
+    ///     let mut stream = EventStream::new();
+    ///
+    ///     loop {
+    ///         let events = stream.next_batch(Duration::from_millis(16)).await?;
+    ///         // handle the events and render a frame ...
+    ///     }
+    /// }
+    /// ```
+    pub fn next_batch(&mut self, duration: Duration) -> NextBatch<'_> {
+        NextBatch {
+            stream: self,
+            timeout: PollTimeout::new(Some(duration)),
+            events: Vec::new(),
+        }
+    }
+
+    /// Makes the background thread wake up the given waker as soon as an event is available,
+    /// or when the optional timeout has elapsed.
+    ///
+    /// If the thread is still busy with a previous task, for example one left behind by a
+    /// dropped future, that task is interrupted and replaced by the new one.
+    fn wake_on_event(&self, stream_waker: &std::task::Waker, timeout: Option<Duration>) {
+        self.wake_on(stream_waker, timeout, Box::new(EventFilter));
+    }
+
+    /// Makes the background thread wake up the given waker as soon as an event matching the
+    /// filter is available, or when the optional timeout has elapsed.
+    ///
+    /// Used to wait for the replies to queries. A previous task of the thread, which may wait
+    /// for other events, is stopped first.
+    #[cfg(unix)]
+    pub(crate) fn wake_on_reply(
+        &self,
+        stream_waker: &std::task::Waker,
+        timeout: Option<Duration>,
+        filter: Box<dyn Filter>,
+    ) {
+        self.wake_on(stream_waker, timeout, filter);
+    }
+
+    /// Hands a task to the background thread.
+    ///
+    /// If the thread is busy with a previous task, the new task is queued and the previous one
+    /// is interrupted. The thread picks up the queued task right away without waking the waker
+    /// of the interrupted one.
+    fn wake_on(
+        &self,
+        stream_waker: &std::task::Waker,
+        timeout: Option<Duration>,
+        filter: Box<dyn Filter>,
+    ) {
+        let task = Task {
+            stream_waker: stream_waker.clone(),
+            stream_wake_task_executed: self.stream_wake_task_executed.clone(),
+            stream_wake_task_should_shutdown: self.stream_wake_task_should_shutdown.clone(),
+            timeout,
+            filter,
+        };
+
+        // The lock is held while checking whether the thread is busy, so that the thread can't
+        // finish its task in between and miss the queued one.
+        let mut pending = self.stream_wake_task_pending.lock();
+
+        if !self
+            .stream_wake_task_executed
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            // https://github.com/rust-lang/rust/issues/80486#issuecomment-752244166
+            .unwrap_or_else(|x| x)
+        {
+            self.stream_wake_task_should_shutdown
+                .store(false, Ordering::SeqCst);
+            let _ = self.task_sender.send(task);
+        } else {
+            *pending = Some(task);
+            self.stream_wake_task_should_shutdown
+                .store(true, Ordering::SeqCst);
+            drop(pending);
+            let _ = self.poll_internal_waker.wake();
+        }
+    }
+}
+
+/// The future returned by [`EventStream::next_batch`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct NextBatch<'a> {
+    stream: &'a mut EventStream,
+    timeout: PollTimeout,
+    events: Vec<Event>,
+}
+
+impl Future for NextBatch<'_> {
+    type Output = io::Result<Vec<Event>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        loop {
+            match poll_internal(Some(Duration::from_secs(0)), &EventFilter) {
+                Ok(true) => match read_internal(&EventFilter) {
+                    Ok(InternalEvent::Event(event)) => this.events.push(event),
+                    Err(e) => return Poll::Ready(Err(e)),
+                    #[cfg(unix)]
+                    _ => unreachable!(),
+                },
+                Ok(false) => break,
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+        }
+
+        if this.timeout.elapsed() {
+            return Poll::Ready(Ok(mem::take(&mut this.events)));
+        }
+
+        this.stream
+            .wake_on_event(cx.waker(), this.timeout.leftover());
+        Poll::Pending
+    }
+}
+
+/// A future resolving to the first internal event matching the filter, or `None` if no such
+/// event arrives within the timeout.
+///
+/// Used by the async probes in [`event::probe`](crate::event::probe) to wait for the replies to
+/// their queries without blocking the executor.
+#[cfg(unix)]
+pub(crate) struct Reply<'a, F> {
+    stream: &'a mut EventStream,
+    filter: F,
+    timeout: PollTimeout,
+}
+
+#[cfg(unix)]
+impl<'a, F: Filter + Clone> Reply<'a, F> {
+    pub(crate) fn new(stream: &'a mut EventStream, filter: F, timeout: Duration) -> Self {
+        Reply {
+            stream,
+            filter,
+            timeout: PollTimeout::new(Some(timeout)),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl<F: Filter + Clone + Unpin> Future for Reply<'_, F> {
+    type Output = io::Result<Option<InternalEvent>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        match poll_internal(Some(Duration::from_secs(0)), &this.filter) {
+            Ok(true) => return Poll::Ready(read_internal(&this.filter).map(Some)),
+            Ok(false) => {}
+            Err(e) => return Poll::Ready(Err(e)),
+        }
+
+        if this.timeout.elapsed() {
+            return Poll::Ready(Ok(None));
+        }
+
+        this.stream.wake_on_reply(
+            cx.waker(),
+            this.timeout.leftover(),
+            Box::new(this.filter.clone()),
+        );
+        Poll::Pending
+    }
 }
 
 struct Task {
     stream_waker: std::task::Waker,
     stream_wake_task_executed: Arc<AtomicBool>,
     stream_wake_task_should_shutdown: Arc<AtomicBool>,
+    timeout: Option<Duration>,
+    filter: Box<dyn Filter>,
+}
+
+impl std::fmt::Debug for Task {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Task")
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
 }
 
 // Note to future me
@@ -110,25 +341,7 @@ impl Stream for EventStream {
                 _ => unreachable!(),
             },
             Ok(false) => {
-                if !self
-                    .stream_wake_task_executed
-                    .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
-                    // https://github.com/rust-lang/rust/issues/80486#issuecomment-752244166
-                    .unwrap_or_else(|x| x)
-                {
-                    let stream_waker = cx.waker().clone();
-                    let stream_wake_task_executed = self.stream_wake_task_executed.clone();
-                    let stream_wake_task_should_shutdown =
-                        self.stream_wake_task_should_shutdown.clone();
-
-                    stream_wake_task_should_shutdown.store(false, Ordering::SeqCst);
-
-                    let _ = self.task_sender.send(Task {
-                        stream_waker,
-                        stream_wake_task_executed,
-                        stream_wake_task_should_shutdown,
-                    });
-                }
+                self.wake_on_event(cx.waker(), None);
                 Poll::Pending
             }
             Err(e) => Poll::Ready(Some(Err(e))),
@@ -139,8 +352,54 @@ impl Stream for EventStream {
 
 impl Drop for EventStream {
     fn drop(&mut self) {
+        let mut pending = self.stream_wake_task_pending.lock();
+        pending.take();
         self.stream_wake_task_should_shutdown
             .store(true, Ordering::SeqCst);
+        drop(pending);
         let _ = self.poll_internal_waker.wake();
     }
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{
+        os::unix::{io::AsRawFd, net::UnixStream},
+        sync::mpsc,
+        task::{Context, Poll},
+        thread,
+        time::Duration,
+    };
+
+    use futures::{executor::block_on, task::noop_waker, StreamExt};
+
+    use super::EventStream;
+    use crate::event::set_input_fd;
+
+    #[test]
+    fn test_next_batch_after_dropped_poll_next_resolves_without_input() {
+        // An input which never produces any event, leaked since the reader keeps using it.
+        let (input, other_end) = UnixStream::pair().unwrap();
+        set_input_fd(input.as_raw_fd()).unwrap();
+        std::mem::forget((input, other_end));
+
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            let mut stream = EventStream::new();
+
+            // Leaves the background thread waiting on a task without a timeout.
+            let waker = noop_waker();
+            let mut cx = Context::from_waker(&waker);
+            assert!(matches!(stream.poll_next_unpin(&mut cx), Poll::Pending));
+
+            let events = block_on(stream.next_batch(Duration::from_millis(50)));
+            let _ = sender.send(events.map(|events| events.len()));
+        });
+
+        let events = receiver
+            .recv_timeout(Duration::from_secs(5))
+            .expect("next_batch did not resolve");
+        assert_eq!(events.unwrap(), 0);
+    }
+}
@@ -1,8 +1,9 @@
 use std::io;
 
 use crate::event::{
-    Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, KeyboardEnhancementFlags,
-    MediaKeyCode, ModifierKeyCode, MouseButton, MouseEvent, MouseEventKind,
+    is_pixel_mouse_capture_enabled, Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState,
+    KeyModifiers, KeyText, KeyboardEnhancementFlags, MediaKeyCode, ModifierKeyCode, MouseButton,
+    MouseEvent, MouseEventKind,
 };
 
 use super::super::super::InternalEvent;
@@ -69,11 +70,27 @@ pub(crate) fn parse_event(
                                 val @ b'P'..=b'S' => Ok(Some(InternalEvent::Event(Event::Key(
                                     KeyCode::F(1 + val - b'P').into(),
                                 )))),
-                                _ => Err(could_not_parse_event_error()),
+                                // Keypad keys in application keypad mode
+                                val => match parse_ss3_keypad_key_code(val) {
+                                    Some(keycode) => Ok(Some(InternalEvent::Event(Event::Key(
+                                        KeyEvent::new_with_kind_and_state(
+                                            keycode,
+                                            KeyModifiers::NONE,
+                                            KeyEventKind::Press,
+                                            KeyEventState::KEYPAD,
+                                        ),
+                                    )))),
+                                    None => Err(could_not_parse_event_error()),
+                                },
                             }
                         }
                     }
                     b'[' => parse_csi(buffer),
+                    b'P' if is_xtversion(buffer, input_available) => parse_dcs_xtversion(buffer),
+                    b'P' if is_setting_report(buffer, input_available) => {
+                        parse_dcs_setting_report(buffer)
+                    }
+                    b']' if is_osc_reply(buffer, input_available) => parse_osc_reply(buffer),
                     b'\x1B' => Ok(Some(InternalEvent::Event(Event::Key(KeyCode::Esc.into())))),
                     _ => parse_event(&buffer[1..], input_available).map(|event_option| {
                         event_option.map(|event| {
@@ -103,6 +120,8 @@ pub(crate) fn parse_event(
         b'\x7F' => Ok(Some(InternalEvent::Event(Event::Key(
             KeyCode::Backspace.into(),
         )))),
+        // The control characters are indistinguishable from Ctrl+letter, this includes ENQ (0x05)
+        // which is reported as Ctrl+E.
         c @ b'\x01'..=b'\x1A' => Ok(Some(InternalEvent::Event(Event::Key(KeyEvent::new(
             KeyCode::Char((c - 0x1 + b'a') as char),
             KeyModifiers::CONTROL,
@@ -134,6 +153,23 @@ fn char_code_to_event(code: KeyCode) -> KeyEvent {
     KeyEvent::new(code, modifiers)
 }
 
+fn parse_ss3_keypad_key_code(byte: u8) -> Option<KeyCode> {
+    // ESC O <byte>, sent by the keypad in application keypad mode
+    let keycode = match byte {
+        b'M' => KeyCode::Enter,
+        b'X' => KeyCode::Char('='),
+        b'j' => KeyCode::Char('*'),
+        b'k' => KeyCode::Char('+'),
+        b'l' => KeyCode::Char(','),
+        b'm' => KeyCode::Char('-'),
+        b'n' => KeyCode::Char('.'),
+        b'o' => KeyCode::Char('/'),
+        b'p'..=b'y' => KeyCode::Char((b'0' + byte - b'p') as char),
+        _ => return None,
+    };
+    Some(keycode)
+}
+
 pub(crate) fn parse_csi(buffer: &[u8]) -> io::Result<Option<InternalEvent>> {
     assert!(buffer.starts_with(&[b'\x1B', b'['])); // ESC [
 
@@ -166,7 +202,7 @@ pub(crate) fn parse_csi(buffer: &[u8]) -> io::Result<Option<InternalEvent>> {
             KeyEventKind::Press,
         ))),
         b'M' => return parse_csi_normal_mouse(buffer),
-        b'<' => return parse_csi_sgr_mouse(buffer),
+        b'<' => return parse_csi_sgr_mouse(buffer, is_pixel_mouse_capture_enabled()),
         b'I' => Some(Event::FocusGained),
         b'O' => Some(Event::FocusLost),
         b';' => return parse_csi_modifier_key_code(buffer),
@@ -180,6 +216,7 @@ pub(crate) fn parse_csi(buffer: &[u8]) -> io::Result<Option<InternalEvent>> {
         b'?' => match buffer[buffer.len() - 1] {
             b'u' => return parse_csi_keyboard_enhancement_flags(buffer),
             b'c' => return parse_csi_primary_device_attributes(buffer),
+            b'y' => return parse_csi_private_mode_report(buffer),
             _ => None,
         },
         b'0'..=b'9' => {
@@ -288,16 +325,132 @@ fn parse_csi_keyboard_enhancement_flags(buffer: &[u8]) -> io::Result<Option<Inte
     Ok(Some(InternalEvent::KeyboardEnhancementFlags(flags)))
 }
 
+fn parse_csi_private_mode_report(buffer: &[u8]) -> io::Result<Option<InternalEvent>> {
+    // ESC [ ? mode ; value $ y
+    assert!(buffer.starts_with(b"\x1B[?"));
+    assert!(buffer.ends_with(b"y"));
+
+    let report = buffer[3..buffer.len() - 1]
+        .strip_suffix(b"$")
+        .ok_or_else(could_not_parse_event_error)?;
+    let s = std::str::from_utf8(report).map_err(|_| could_not_parse_event_error())?;
+    let mut split = s.split(';');
+
+    let mode = next_parsed::<u16>(&mut split)?;
+    let value = next_parsed::<u8>(&mut split)?;
+
+    Ok(Some(InternalEvent::PrivateModeReport(mode, value)))
+}
+
+/// Introducer of the reply to the XTVERSION query.
+const XTVERSION_REPLY: &[u8] = b"\x1BP>|";
+
+/// Returns whether `buffer` is, or might become, a reply to the XTVERSION query.
+///
+/// An incomplete introducer is only treated as a reply while more input is available, otherwise
+/// `ESC P` is an `Alt+P` key press.
+fn is_xtversion(buffer: &[u8], input_available: bool) -> bool {
+    buffer.starts_with(XTVERSION_REPLY) || (input_available && XTVERSION_REPLY.starts_with(buffer))
+}
+
+fn parse_dcs_xtversion(buffer: &[u8]) -> io::Result<Option<InternalEvent>> {
+    // ESC P > | name-and-version ESC \
+    let version = if let Some(version) = buffer.strip_suffix(b"\x1B\\") {
+        version
+    } else if let Some(version) = buffer.strip_suffix(b"\x07") {
+        // Some terminals terminate the string with BEL instead of ST.
+        version
+    } else {
+        return Ok(None);
+    };
+
+    let version = version
+        .strip_prefix(XTVERSION_REPLY)
+        .ok_or_else(could_not_parse_event_error)?;
+
+    Ok(Some(InternalEvent::TerminalVersion(
+        String::from_utf8_lossy(version).into_owned(),
+    )))
+}
+
+/// Introducers of the replies to a DECRQSS request, for a valid and an invalid request.
+const DECRPSS_VALID_REPLY: &[u8] = b"\x1BP1$r";
+const DECRPSS_INVALID_REPLY: &[u8] = b"\x1BP0$r";
+
+/// Returns whether `buffer` is, or might become, a reply to a DECRQSS request.
+///
+/// See [`is_xtversion`] for the handling of an incomplete introducer.
+fn is_setting_report(buffer: &[u8], input_available: bool) -> bool {
+    [DECRPSS_VALID_REPLY, DECRPSS_INVALID_REPLY]
+        .iter()
+        .any(|reply| buffer.starts_with(reply) || (input_available && reply.starts_with(buffer)))
+}
+
+fn parse_dcs_setting_report(buffer: &[u8]) -> io::Result<Option<InternalEvent>> {
+    // ESC P 1 $ r setting ESC \   (valid request)
+    // ESC P 0 $ r ESC \           (invalid request)
+    let report = if let Some(report) = buffer.strip_suffix(b"\x1B\\") {
+        report
+    } else if let Some(report) = buffer.strip_suffix(b"\x07") {
+        report
+    } else {
+        return Ok(None);
+    };
+
+    if let Some(setting) = report.strip_prefix(DECRPSS_VALID_REPLY) {
+        Ok(Some(InternalEvent::SettingReport(Some(
+            String::from_utf8_lossy(setting).into_owned(),
+        ))))
+    } else if report.starts_with(DECRPSS_INVALID_REPLY) {
+        Ok(Some(InternalEvent::SettingReport(None)))
+    } else {
+        Err(could_not_parse_event_error())
+    }
+}
+
+/// Returns whether `buffer` is, or might become, the reply to an operating system command,
+/// `ESC ] number ;` followed by the reply.
+///
+/// See [`is_xtversion`] for the handling of an incomplete introducer.
+fn is_osc_reply(buffer: &[u8], input_available: bool) -> bool {
+    let command = &buffer[2..];
+    let digits = command.iter().take_while(|b| b.is_ascii_digit()).count();
+    match command.get(digits) {
+        Some(b';') => digits > 0,
+        Some(_) => false,
+        None => input_available,
+    }
+}
+
+fn parse_osc_reply(buffer: &[u8]) -> io::Result<Option<InternalEvent>> {
+    // ESC ] number ; reply ESC \
+    let reply = if let Some(reply) = buffer.strip_suffix(b"\x1B\\") {
+        reply
+    } else if let Some(reply) = buffer.strip_suffix(b"\x07") {
+        reply
+    } else {
+        return Ok(None);
+    };
+
+    Ok(Some(InternalEvent::OscReply(
+        String::from_utf8_lossy(&reply[2..]).into_owned(),
+    )))
+}
+
 fn parse_csi_primary_device_attributes(buffer: &[u8]) -> io::Result<Option<InternalEvent>> {
     // ESC [ 64 ; attr1 ; attr2 ; ... ; attrn ; c
     assert!(buffer.starts_with(&[b'\x1B', b'[', b'?']));
     assert!(buffer.ends_with(&[b'c']));
 
-    // This is a stub for parsing the primary device attributes. This response is not
-    // exposed in the crossterm API so we don't need to parse the individual attributes yet.
     // See <https://vt100.net/docs/vt510-rm/DA1.html>
+    let s = std::str::from_utf8(&buffer[3..buffer.len() - 1])
+        .map_err(|_| could_not_parse_event_error())?;
+    let attributes = s
+        .split(';')
+        .filter_map(|attribute| attribute.parse::<u16>().ok())
+        .collect();
 
-    Ok(Some(InternalEvent::PrimaryDeviceAttributes))
+    Ok(Some(InternalEvent::PrimaryDeviceAttributes(attributes)))
 }
 
 fn parse_modifiers(mask: u8) -> KeyModifiers {
@@ -606,14 +759,28 @@ pub(crate) fn parse_csi_u_encoded_key_code(buffer: &[u8]) -> io::Result<Option<I
         }
     }
 
-    let input_event = Event::Key(KeyEvent::new_with_kind_and_state(
+    // When the "report associated text" flag is enabled in the Kitty Keyboard Protocol, the
+    // third field contains the produced text as colon separated codepoints.
+    let text = split.next().and_then(|text| {
+        text.split(':')
+            .map(|codepoint| codepoint.parse::<u32>().ok().and_then(char::from_u32))
+            .collect::<Option<String>>()
+    });
+
+    let key_event = KeyEvent::new_with_kind_and_state(
         keycode,
         modifiers,
         kind,
         state_from_keycode | state_from_modifiers,
-    ));
+    );
 
-    Ok(Some(InternalEvent::Event(input_event)))
+    // Text which doesn't fit into a `KeyText` is dropped rather than truncated.
+    let event = match text.as_deref().and_then(KeyText::new) {
+        Some(text) => Event::KeyWithText(key_event, text),
+        None => Event::Key(key_event),
+    };
+
+    Ok(Some(InternalEvent::Event(event)))
 }
 
 pub(crate) fn parse_csi_special_key_code(buffer: &[u8]) -> io::Result<Option<InternalEvent>> {
@@ -627,6 +794,10 @@ pub(crate) fn parse_csi_special_key_code(buffer: &[u8]) -> io::Result<Option<Int
     // This CSI sequence can be a list of semicolon-separated numbers.
     let first = next_parsed::<u8>(&mut split)?;
 
+    if first == 27 {
+        return parse_csi_modify_other_keys(&mut split);
+    }
+
     let (modifiers, kind, state) =
         if let Ok((modifier_mask, kind_code)) = modifier_and_kind_parsed(&mut split) {
             (
@@ -660,6 +831,33 @@ pub(crate) fn parse_csi_special_key_code(buffer: &[u8]) -> io::Result<Option<Int
     Ok(Some(InternalEvent::Event(input_event)))
 }
 
+fn parse_csi_modify_other_keys(
+    split: &mut std::str::Split<'_, char>,
+) -> io::Result<Option<InternalEvent>> {
+    // xterm's modifyOtherKeys encoding:
+    // ESC [ 27 ; modifiers ; codepoint ~
+    let modifiers = parse_modifiers(next_parsed::<u8>(split)?);
+    let codepoint = next_parsed::<u32>(split)?;
+
+    let keycode = match char::from_u32(codepoint).ok_or_else(could_not_parse_event_error)? {
+        '\x1B' => KeyCode::Esc,
+        '\r' => KeyCode::Enter,
+        '\t' => {
+            if modifiers.contains(KeyModifiers::SHIFT) {
+                KeyCode::BackTab
+            } else {
+                KeyCode::Tab
+            }
+        }
+        '\x7F' | '\x08' => KeyCode::Backspace,
+        c => KeyCode::Char(c),
+    };
+
+    Ok(Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+        keycode, modifiers,
+    )))))
+}
+
 pub(crate) fn parse_csi_rxvt_mouse(buffer: &[u8]) -> io::Result<Option<InternalEvent>> {
     // rxvt mouse encoding:
     // ESC [ Cb ; Cx ; Cy ; M
@@ -676,14 +874,19 @@ pub(crate) fn parse_csi_rxvt_mouse(buffer: &[u8]) -> io::Result<Option<InternalE
         .ok_or_else(could_not_parse_event_error)?;
     let (kind, modifiers) = parse_cb(cb)?;
 
-    let cx = next_parsed::<u16>(&mut split)? - 1;
-    let cy = next_parsed::<u16>(&mut split)? - 1;
+    let cx = next_parsed::<u16>(&mut split)?
+        .checked_sub(1)
+        .ok_or_else(could_not_parse_event_error)?;
+    let cy = next_parsed::<u16>(&mut split)?
+        .checked_sub(1)
+        .ok_or_else(could_not_parse_event_error)?;
 
     Ok(Some(InternalEvent::Event(Event::Mouse(MouseEvent {
         kind,
         column: cx,
         row: cy,
         modifiers,
+        pixels: false,
     }))))
 }
 
@@ -712,10 +915,15 @@ pub(crate) fn parse_csi_normal_mouse(buffer: &[u8]) -> io::Result<Option<Interna
         column: cx,
         row: cy,
         modifiers,
+        pixels: false,
     }))))
 }
 
-pub(crate) fn parse_csi_sgr_mouse(buffer: &[u8]) -> io::Result<Option<InternalEvent>> {
+/// Parses an SGR mouse report, `pixels` tells whether its position is in pixels (mode 1016).
+pub(crate) fn parse_csi_sgr_mouse(
+    buffer: &[u8],
+    pixels: bool,
+) -> io::Result<Option<InternalEvent>> {
     // ESC [ < Cb ; Cx ; Cy (;) (M or m)
 
     assert!(buffer.starts_with(&[b'\x1B', b'[', b'<'])); // ESC [ <
@@ -757,6 +965,7 @@ pub(crate) fn parse_csi_sgr_mouse(buffer: &[u8]) -> io::Result<Option<InternalEv
         column: cx,
         row: cy,
         modifiers,
+        pixels,
     }))))
 }
 
@@ -788,6 +997,8 @@ fn parse_cb(cb: u8) -> io::Result<(MouseEventKind, KeyModifiers)> {
         (3, true) | (4, true) | (5, true) => MouseEventKind::Moved,
         (4, false) => MouseEventKind::ScrollUp,
         (5, false) => MouseEventKind::ScrollDown,
+        (6, false) => MouseEventKind::ScrollLeft,
+        (7, false) => MouseEventKind::ScrollRight,
         // We do not support other buttons.
         _ => return Err(could_not_parse_event_error()),
     };
@@ -861,7 +1072,11 @@ pub(crate) fn parse_utf8_char(buffer: &[u8]) -> io::Result<Option<char>> {
 
 #[cfg(test)]
 mod tests {
-    use crate::event::{KeyEventState, KeyModifiers, MouseButton, MouseEvent};
+    use crate::event::{
+        DisablePixelMouseCapture, EnablePixelMouseCapture, KeyEventState, KeyModifiers,
+        MouseButton, MouseEvent, PIXEL_MOUSE_CAPTURE_TEST_LOCK,
+    };
+    use crate::Command;
 
     use super::*;
 
@@ -913,6 +1128,8 @@ mod tests {
 
     #[test]
     fn test_parse_event_subsequent_calls() {
+        let _lock = PIXEL_MOUSE_CAPTURE_TEST_LOCK.lock();
+
         // The main purpose of this test is to check if we're passing
         // correct slice to other parse_ functions.
 
@@ -960,6 +1177,7 @@ mod tests {
                 column: 29,
                 row: 39,
                 modifiers: KeyModifiers::empty(),
+                pixels: false,
             })))
         );
 
@@ -971,6 +1189,7 @@ mod tests {
                 column: 63,
                 row: 79,
                 modifiers: KeyModifiers::CONTROL,
+                pixels: false,
             })))
         );
 
@@ -982,6 +1201,7 @@ mod tests {
                 column: 19,
                 row: 9,
                 modifiers: KeyModifiers::empty(),
+                pixels: false,
             })))
         );
 
@@ -1003,6 +1223,88 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_ss3() {
+        assert_eq!(parse_event(b"\x1BO", true).unwrap(), None);
+
+        for (sequence, code) in [
+            (b"\x1BOA", KeyCode::Up),
+            (b"\x1BOB", KeyCode::Down),
+            (b"\x1BOC", KeyCode::Right),
+            (b"\x1BOD", KeyCode::Left),
+            (b"\x1BOH", KeyCode::Home),
+            (b"\x1BOF", KeyCode::End),
+            (b"\x1BOP", KeyCode::F(1)),
+            (b"\x1BOQ", KeyCode::F(2)),
+            (b"\x1BOR", KeyCode::F(3)),
+            (b"\x1BOS", KeyCode::F(4)),
+        ] {
+            assert_eq!(
+                parse_event(sequence, false).unwrap(),
+                Some(InternalEvent::Event(Event::Key(code.into()))),
+            );
+        }
+
+        assert!(parse_event(b"\x1BOZ", false).is_err());
+    }
+
+    #[test]
+    fn test_parse_ss3_application_keypad() {
+        for (sequence, code) in [
+            (b"\x1BOp", KeyCode::Char('0')),
+            (b"\x1BOy", KeyCode::Char('9')),
+            (b"\x1BOM", KeyCode::Enter),
+            (b"\x1BOk", KeyCode::Char('+')),
+            (b"\x1BOo", KeyCode::Char('/')),
+        ] {
+            assert_eq!(
+                parse_event(sequence, false).unwrap(),
+                Some(InternalEvent::Event(Event::Key(
+                    KeyEvent::new_with_kind_and_state(
+                        code,
+                        KeyModifiers::NONE,
+                        KeyEventKind::Press,
+                        KeyEventState::KEYPAD,
+                    )
+                ))),
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_csi_modify_other_keys() {
+        assert_eq!(
+            parse_event(b"\x1B[27;5;13~", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Enter,
+                KeyModifiers::CONTROL
+            )))),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[27;6;65~", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char('A'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            )))),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[27;2;9~", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::BackTab,
+                KeyModifiers::SHIFT
+            )))),
+        );
+        // `formatOtherKeys` set to 1 makes xterm use the `CSI u` form instead.
+        assert_eq!(
+            parse_event(b"\x1B[13;5u", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Enter,
+                KeyModifiers::CONTROL
+            )))),
+        );
+        assert!(parse_event(b"\x1B[27;5~", false).is_err());
+    }
+
     #[test]
     fn test_parse_csi_cursor_position() {
         assert_eq!(
@@ -1086,10 +1388,42 @@ mod tests {
                 column: 29,
                 row: 39,
                 modifiers: KeyModifiers::empty(),
+                pixels: false,
+            })))
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_rxvt_mouse_large_coordinates() {
+        // Beyond 223 the X10 encoding overflows a byte, urxvt uses decimal numbers.
+        assert_eq!(
+            parse_event(b"\x1B[67;300;250M", false).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Moved,
+                column: 299,
+                row: 249,
+                modifiers: KeyModifiers::empty(),
+                pixels: false,
+            })))
+        );
+        assert_eq!(
+            parse_event(b"\x1B[36;1000;1000M", false).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column: 999,
+                row: 999,
+                modifiers: KeyModifiers::SHIFT,
+                pixels: false,
             })))
         );
     }
 
+    #[test]
+    fn test_parse_csi_rxvt_mouse_invalid_coordinates() {
+        assert!(parse_csi_rxvt_mouse(b"\x1B[32;0;1M").is_err());
+        assert!(parse_csi_rxvt_mouse(b"\x1B[31;1;1M").is_err());
+    }
+
     #[test]
     fn test_parse_csi_normal_mouse() {
         assert_eq!(
@@ -1099,46 +1433,272 @@ mod tests {
                 column: 63,
                 row: 79,
                 modifiers: KeyModifiers::CONTROL,
+                pixels: false,
             })))
         );
     }
 
+    #[test]
+    fn test_parse_csi_private_mode_report() {
+        assert_eq!(parse_event(b"\x1B[?1004;2$", true).unwrap(), None);
+        assert_eq!(
+            parse_event(b"\x1B[?1004;2$y", false).unwrap(),
+            Some(InternalEvent::PrivateModeReport(1004, 2))
+        );
+        assert_eq!(
+            parse_event(b"\x1B[?2026;0$y", false).unwrap(),
+            Some(InternalEvent::PrivateModeReport(2026, 0))
+        );
+        assert!(parse_event(b"\x1B[?1004;2y", false).is_err());
+    }
+
+    #[test]
+    fn test_parse_csi_primary_device_attributes() {
+        assert_eq!(parse_event(b"\x1B[?62;4", true).unwrap(), None);
+        assert_eq!(
+            parse_event(b"\x1B[?62;4;22c", false).unwrap(),
+            Some(InternalEvent::PrimaryDeviceAttributes(vec![62, 4, 22]))
+        );
+        assert_eq!(
+            parse_event(b"\x1B[?1;2c", false).unwrap(),
+            Some(InternalEvent::PrimaryDeviceAttributes(vec![1, 2]))
+        );
+    }
+
+    #[test]
+    fn test_parse_dcs_xtversion() {
+        assert_eq!(parse_event(b"\x1BP>", true).unwrap(), None);
+        assert_eq!(parse_event(b"\x1BP>|XTerm(380", true).unwrap(), None);
+        assert_eq!(
+            parse_event(b"\x1BP>|XTerm(380)\x1B\\", false).unwrap(),
+            Some(InternalEvent::TerminalVersion("XTerm(380)".to_string()))
+        );
+        assert_eq!(
+            parse_event(b"\x1BP>|kitty(0.26.5)\x07", false).unwrap(),
+            Some(InternalEvent::TerminalVersion("kitty(0.26.5)".to_string()))
+        );
+
+        // Without more input, ESC P is still Alt+P
+        assert_eq!(
+            parse_event(b"\x1BP", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char('P'),
+                KeyModifiers::ALT | KeyModifiers::SHIFT
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_parse_osc_reply() {
+        assert_eq!(parse_event(b"\x1B]4", true).unwrap(), None);
+        assert_eq!(parse_event(b"\x1B]4;1;rgb:cdcd/00", true).unwrap(), None);
+        assert_eq!(
+            parse_event(b"\x1B]4;1;rgb:cdcd/0000/0000\x1B\\", false).unwrap(),
+            Some(InternalEvent::OscReply(
+                "4;1;rgb:cdcd/0000/0000".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_event(b"\x1B]11;rgb:0000/0000/0000\x07", false).unwrap(),
+            Some(InternalEvent::OscReply("11;rgb:0000/0000/0000".to_string()))
+        );
+
+        // Without more input, ESC ] is still Alt+]
+        assert_eq!(
+            parse_event(b"\x1B]", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char(']'),
+                KeyModifiers::ALT
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_parse_dcs_setting_report() {
+        assert_eq!(parse_event(b"\x1BP1", true).unwrap(), None);
+        assert_eq!(parse_event(b"\x1BP1$r0;38;2;1;2;3", true).unwrap(), None);
+        assert_eq!(
+            parse_event(b"\x1BP1$r0;38;2;1;2;3m\x1B\\", false).unwrap(),
+            Some(InternalEvent::SettingReport(Some(
+                "0;38;2;1;2;3m".to_string()
+            )))
+        );
+        assert_eq!(
+            parse_event(b"\x1BP1$r0m\x07", false).unwrap(),
+            Some(InternalEvent::SettingReport(Some("0m".to_string())))
+        );
+        assert_eq!(
+            parse_event(b"\x1BP0$r\x1B\\", false).unwrap(),
+            Some(InternalEvent::SettingReport(None))
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_sgr_mouse_modifiers() {
+        let _lock = PIXEL_MOUSE_CAPTURE_TEST_LOCK.lock();
+
+        let mouse = |kind, modifiers| {
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent {
+                kind,
+                column: 4,
+                row: 2,
+                modifiers,
+                pixels: false,
+            })))
+        };
+
+        // ctrl-click
+        assert_eq!(
+            parse_event(b"\x1B[<16;5;3M", false).unwrap(),
+            mouse(
+                MouseEventKind::Down(MouseButton::Left),
+                KeyModifiers::CONTROL
+            )
+        );
+        // shift-drag
+        assert_eq!(
+            parse_event(b"\x1B[<36;5;3M", false).unwrap(),
+            mouse(MouseEventKind::Drag(MouseButton::Left), KeyModifiers::SHIFT)
+        );
+        // alt-right-click release
+        assert_eq!(
+            parse_event(b"\x1B[<10;5;3m", false).unwrap(),
+            mouse(MouseEventKind::Up(MouseButton::Right), KeyModifiers::ALT)
+        );
+        // ctrl-alt-shift-middle-click
+        assert_eq!(
+            parse_event(b"\x1B[<29;5;3M", false).unwrap(),
+            mouse(
+                MouseEventKind::Down(MouseButton::Middle),
+                KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT
+            )
+        );
+        // ctrl-scroll
+        assert_eq!(
+            parse_event(b"\x1B[<80;5;3M", false).unwrap(),
+            mouse(MouseEventKind::ScrollUp, KeyModifiers::CONTROL)
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_sgr_horizontal_scroll() {
+        let _lock = PIXEL_MOUSE_CAPTURE_TEST_LOCK.lock();
+
+        assert_eq!(
+            parse_event(b"\x1B[<66;20;10M", false).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent {
+                kind: MouseEventKind::ScrollLeft,
+                column: 19,
+                row: 9,
+                modifiers: KeyModifiers::empty(),
+                pixels: false,
+            })))
+        );
+        assert_eq!(
+            parse_event(b"\x1B[<67;20;10M", false).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent {
+                kind: MouseEventKind::ScrollRight,
+                column: 19,
+                row: 9,
+                modifiers: KeyModifiers::empty(),
+                pixels: false,
+            })))
+        );
+        // with shift held
+        assert_eq!(
+            parse_event(b"\x1B[<71;1;1M", false).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent {
+                kind: MouseEventKind::ScrollRight,
+                column: 0,
+                row: 0,
+                modifiers: KeyModifiers::SHIFT,
+                pixels: false,
+            })))
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_sgr_pixel_mouse() {
+        // SGR-Pixels (1016) reports use the SGR format with pixel coordinates
+        assert_eq!(
+            parse_csi_sgr_mouse(b"\x1B[<35;1234;567M", true).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Moved,
+                column: 1233,
+                row: 566,
+                modifiers: KeyModifiers::empty(),
+                pixels: true,
+            })))
+        );
+    }
+
+    #[test]
+    fn test_parse_sgr_mouse_after_pixel_mouse_capture() {
+        let _lock = PIXEL_MOUSE_CAPTURE_TEST_LOCK.lock();
+        let moved = |pixels| {
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Moved,
+                column: 1233,
+                row: 566,
+                modifiers: KeyModifiers::empty(),
+                pixels,
+            })))
+        };
+
+        let mut ansi = String::new();
+        EnablePixelMouseCapture.write_ansi(&mut ansi).unwrap();
+        assert_eq!(
+            parse_event(b"\x1B[<35;1234;567M", false).unwrap(),
+            moved(true)
+        );
+
+        DisablePixelMouseCapture.write_ansi(&mut ansi).unwrap();
+        assert_eq!(
+            parse_event(b"\x1B[<35;1234;567M", false).unwrap(),
+            moved(false)
+        );
+    }
+
     #[test]
     fn test_parse_csi_sgr_mouse() {
         assert_eq!(
-            parse_csi_sgr_mouse(b"\x1B[<0;20;10;M").unwrap(),
+            parse_csi_sgr_mouse(b"\x1B[<0;20;10;M", false).unwrap(),
             Some(InternalEvent::Event(Event::Mouse(MouseEvent {
                 kind: MouseEventKind::Down(MouseButton::Left),
                 column: 19,
                 row: 9,
                 modifiers: KeyModifiers::empty(),
+                pixels: false,
             })))
         );
         assert_eq!(
-            parse_csi_sgr_mouse(b"\x1B[<0;20;10M").unwrap(),
+            parse_csi_sgr_mouse(b"\x1B[<0;20;10M", false).unwrap(),
             Some(InternalEvent::Event(Event::Mouse(MouseEvent {
                 kind: MouseEventKind::Down(MouseButton::Left),
                 column: 19,
                 row: 9,
                 modifiers: KeyModifiers::empty(),
+                pixels: false,
             })))
         );
         assert_eq!(
-            parse_csi_sgr_mouse(b"\x1B[<0;20;10;m").unwrap(),
+            parse_csi_sgr_mouse(b"\x1B[<0;20;10;m", false).unwrap(),
             Some(InternalEvent::Event(Event::Mouse(MouseEvent {
                 kind: MouseEventKind::Up(MouseButton::Left),
                 column: 19,
                 row: 9,
                 modifiers: KeyModifiers::empty(),
+                pixels: false,
             })))
         );
         assert_eq!(
-            parse_csi_sgr_mouse(b"\x1B[<0;20;10m").unwrap(),
+            parse_csi_sgr_mouse(b"\x1B[<0;20;10m", false).unwrap(),
             Some(InternalEvent::Event(Event::Mouse(MouseEvent {
                 kind: MouseEventKind::Up(MouseButton::Left),
                 column: 19,
                 row: 9,
                 modifiers: KeyModifiers::empty(),
+                pixels: false,
             })))
         );
     }
@@ -1198,6 +1758,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_enq_as_ctrl_e() {
+        assert_eq!(
+            parse_event(b"\x05", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char('e'),
+                KeyModifiers::CONTROL
+            )))),
+        );
+    }
+
     #[test]
     fn test_parse_char_event_uppercase() {
         assert_eq!(
@@ -1442,6 +2013,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_csi_u_encoded_key_code_with_text() {
+        let event = |buffer: &[u8]| match parse_csi_u_encoded_key_code(buffer).unwrap() {
+            Some(InternalEvent::Event(event)) => event,
+            event => panic!("expected an event, got {:?}", event),
+        };
+
+        // Shift+a producing "A"
+        assert_eq!(
+            event(b"\x1B[97;2;65u"),
+            Event::KeyWithText(
+                KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT),
+                KeyText::new("A").unwrap()
+            )
+        );
+
+        // Text composed of several codepoints, with the event type
+        assert_eq!(
+            event(b"\x1B[101;1:1;101:769u"),
+            Event::KeyWithText(
+                KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE),
+                KeyText::new("e\u{301}").unwrap()
+            )
+        );
+
+        // Without modifiers
+        assert_eq!(
+            event(b"\x1B[228;;228u"),
+            Event::KeyWithText(
+                KeyEvent::new(KeyCode::Char('\u{e4}'), KeyModifiers::NONE),
+                KeyText::new("\u{e4}").unwrap()
+            )
+        );
+
+        // Without text, with invalid text, or with text that doesn't fit
+        let plain_a = Event::Key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE));
+        assert_eq!(event(b"\x1B[97;1u"), plain_a);
+        assert_eq!(event(b"\x1B[97u"), plain_a);
+        assert_eq!(event(b"\x1B[97;1;1114112u"), plain_a);
+        assert_eq!(
+            event(b"\x1B[97;1;97:97:97:97:97:97:97:97:97:97:97:97:97:97:97:97:97u"),
+            plain_a
+        );
+    }
+
     #[test]
     fn test_parse_csi_u_with_shifted_keycode() {
         assert_eq!(
@@ -358,7 +358,16 @@ fn parse_mouse_event_record(
             }
         }
         EventFlags::DoubleClick => None, // double click not supported by unix terminals
-        EventFlags::MouseHwheeled => None, // horizontal scroll not supported by unix terminals
+        EventFlags::MouseHwheeled => {
+            // Horizontal scroll, a negative `button_state` means the wheel was rotated left.
+            if button_state.scroll_left() {
+                Some(MouseEventKind::ScrollLeft)
+            } else if button_state.scroll_right() {
+                Some(MouseEventKind::ScrollRight)
+            } else {
+                None
+            }
+        }
         _ => None,
     };
 
@@ -367,5 +376,6 @@ fn parse_mouse_event_record(
         column: xpos,
         row: ypos,
         modifiers,
+        pixels: false,
     }))
 }
@@ -230,13 +230,23 @@
 //! [stderr]: https://doc.rust-lang.org/std/io/fn.stderr.html
 //! [flush]: https://doc.rust-lang.org/std/io/trait.Write.html#tymethod.flush
 
-pub use crate::command::{Command, ExecutableCommand, QueueableCommand, SynchronizedUpdate};
+pub use crate::command::{
+    ansi_string, batch, AutoFlushWriter, Command, ExecutableCommand, QueueableCommand, Raw,
+    Recorder, SplitWriter, SynchronizedUpdate, Tee,
+};
+#[cfg(feature = "trace")]
+pub use crate::command::{remove_trace_hook, set_trace_hook};
+#[cfg(feature = "events")]
+pub use crate::command::{run_queries, Query, QueryReply};
 
 /// A module to work with the terminal cursor
 pub mod cursor;
 /// A module to read events.
 #[cfg(feature = "events")]
 pub mod event;
+/// A module to render frames with an off-screen buffer.
+#[cfg(feature = "render")]
+pub mod render;
 /// A module to apply attributes and colors on your text.
 pub mod style;
 /// A module to work with the terminal.
@@ -246,7 +256,8 @@ pub mod terminal;
 pub mod tty;
 
 #[cfg(windows)]
-/// A module that exposes one function to check if the current terminal supports ANSI sequences.
+/// A module that exposes functions to check and override if the current terminal supports ANSI
+/// sequences.
 pub mod ansi_support;
 mod command;
 pub(crate) mod macros;
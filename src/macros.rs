@@ -33,7 +33,7 @@ macro_rules! csi {
 /// let mut stdout = stdout();
 ///
 /// // `Print` will executed executed when `flush` is called.
-/// queue!(stdout, Print("foo".to_string()));
+/// queue!(stdout, Print("foo"));
 ///
 /// // some other code (no execution happening here) ...
 ///
@@ -85,10 +85,10 @@ macro_rules! queue {
 /// use crossterm::{execute, style::Print};
 ///
 /// // will be executed directly
-/// execute!(stdout(), Print("sum:\n".to_string()));
+/// execute!(stdout(), Print("sum:\n"));
 ///
 /// // will be executed directly
-/// execute!(stdout(), Print("1 + 1= ".to_string()), Print((1+1).to_string()));
+/// execute!(stdout(), Print("1 + 1= "), Print(1 + 1));
 ///
 /// // ==== Output ====
 /// // sum:
@@ -118,6 +118,48 @@ macro_rules! execute {
     }}
 }
 
+/// Queues formatted text in the given style.
+///
+/// Takes a writer, a [ContentStyle](style/struct.ContentStyle.html) and format arguments like
+/// [format!](std::format). The style, the formatted text and a reset of the style are queued as a
+/// [PrintStyledContent](style/struct.PrintStyledContent.html) command, so the writer still has to
+/// be flushed afterwards.
+///
+/// Unlike `Print(format!(...))`, this doesn't allocate: the arguments are captured with
+/// [format_args!](std::format_args) and formatted straight into the writer, like every other
+/// command.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{self, Write};
+/// use crossterm::{styled_print, style::{Color, ContentStyle}};
+///
+/// fn main() -> io::Result<()> {
+///     let mut stdout = io::stdout();
+///     let red = ContentStyle {
+///         foreground_color: Some(Color::Red),
+///         ..ContentStyle::default()
+///     };
+///
+///     let count = 42;
+///     styled_print!(stdout, red, "count = {}", count)?;
+///     stdout.flush()
+/// }
+/// ```
+#[macro_export]
+macro_rules! styled_print {
+    ($writer:expr, $style:expr, $($arg:tt)+) => {
+        $crate::queue!(
+            $writer,
+            $crate::style::PrintStyledContent($crate::style::StyledContent::new(
+                $style,
+                ::std::format_args!($($arg)+),
+            ))
+        )
+    };
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! impl_display {
@@ -192,6 +234,14 @@ mod tests {
             assert!(!result.flushed);
         }
 
+        #[test]
+        fn test_queue_auto_flush() {
+            let mut result = crate::AutoFlushWriter::new(FakeWrite::default());
+            queue!(result, FakeCommand).unwrap();
+            assert_eq!(&result.writer().buffer, "cmd");
+            assert!(result.writer().flushed);
+        }
+
         #[test]
         fn test_queue_many() {
             let mut result = FakeWrite::default();
@@ -224,6 +274,71 @@ mod tests {
             assert!(result.flushed);
         }
 
+        #[test]
+        fn test_styled_print() {
+            use crate::style::{Attribute, ContentStyle, Stylize};
+
+            let mut result = FakeWrite::default();
+            let bold = ContentStyle::new().attribute(Attribute::Bold);
+            styled_print!(&mut result, bold, "{} = {}", "count", 42).unwrap();
+            assert_eq!(&result.buffer, "\x1B[1mcount = 42\x1B[0m");
+            assert!(!result.flushed);
+        }
+
+        #[test]
+        fn test_batch() {
+            let mut result = FakeWrite::default();
+            crate::batch(&mut result, [FakeCommand, FakeCommand]).unwrap();
+            assert_eq!(&result.buffer, "cmdcmd");
+            assert!(result.flushed);
+        }
+
+        #[test]
+        fn test_tee() {
+            let mut tee = crate::Tee::new(FakeWrite::default(), FakeWrite::default());
+            execute!(tee, FakeCommand, FakeCommand).unwrap();
+
+            let (result, log) = tee.into_inner();
+            assert_eq!(&result.buffer, "cmdcmd");
+            assert!(result.flushed);
+            assert_eq!(&log.buffer, "cmdcmd");
+            assert!(log.flushed);
+        }
+
+        #[test]
+        fn test_recorder() {
+            let mut recorder = crate::Recorder::new(FakeWrite::default(), FakeWrite::default());
+            execute!(recorder, FakeCommand, FakeCommand).unwrap();
+
+            assert_eq!(&recorder.writer().buffer, "cmdcmd");
+            assert!(recorder.writer().flushed);
+            assert!(recorder.log().flushed);
+
+            let records: Vec<&str> = recorder.log().buffer.lines().collect();
+            assert_eq!(records.len(), 2);
+            for record in records {
+                let (elapsed, bytes) = record.split_once("] ").unwrap();
+                assert!(elapsed.starts_with('[') && elapsed[1..].parse::<f64>().is_ok());
+                assert_eq!(bytes, "cmd");
+            }
+        }
+
+        #[test]
+        fn test_split_writer() {
+            let mut writer = crate::SplitWriter::new(FakeWrite::default());
+            execute!(writer, FakeCommand).unwrap();
+            assert_eq!(writer.captured(), b"cmd");
+
+            writer.clear_captured();
+            execute!(writer, FakeCommand, FakeCommand).unwrap();
+            assert_eq!(writer.take_captured(), b"cmdcmd");
+            assert!(writer.captured().is_empty());
+
+            let (result, _) = writer.into_inner();
+            assert_eq!(&result.buffer, "cmdcmdcmd");
+            assert!(result.flushed);
+        }
+
         #[test]
         fn test_execute_trailing_comma() {
             let mut result = FakeWrite::default();
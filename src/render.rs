@@ -0,0 +1,298 @@
+//! # Render
+//!
+//! The `render` module provides an off-screen [`Buffer`] of styled cells. Draw a frame into a
+//! buffer, then use [`Buffer::flush_diff`] to write only the cells which changed since the
+//! previous frame, which is a lot less output than redrawing the whole screen.
+//!
+//! This module is only available with the `render` feature.
+//!
+//! ```no_run
+//! use std::io;
+//! use crossterm::render::Buffer;
+//! use crossterm::style::{ContentStyle, Stylize};
+//!
+//! fn main() -> io::Result<()> {
+//!     let mut previous = Buffer::new(80, 24);
+//!     let mut current = previous.clone();
+//!
+//!     for frame in 0..10 {
+//!         current.clear();
+//!         current.set_str(0, 0, &format!("frame {}", frame), ContentStyle::new().bold());
+//!         current.flush_diff(&previous, &mut io::stdout())?;
+//!         std::mem::swap(&mut previous, &mut current);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::cursor::{MoveRight, MoveTo};
+use crate::style::{width::char_width, ContentStyle};
+use crate::{Command, QueueableCommand};
+
+/// A single cell of a [`Buffer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cell {
+    /// The character shown in the cell.
+    pub symbol: char,
+    /// The style of the cell.
+    pub style: ContentStyle,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell {
+            symbol: ' ',
+            style: ContentStyle::default(),
+        }
+    }
+}
+
+/// An off-screen grid of styled cells.
+///
+/// Cells are addressed by `(column, row)`, the top left cell is `(0, 0)`. A wide character, like
+/// a CJK ideograph, occupies its own cell and the cell right of it. The cell right of it isn't
+/// drawn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Buffer {
+    width: u16,
+    height: u16,
+    cells: Vec<Cell>,
+}
+
+impl Buffer {
+    /// Creates a buffer of `width` columns and `height` rows of blank cells.
+    pub fn new(width: u16, height: u16) -> Buffer {
+        Buffer {
+            width,
+            height,
+            cells: vec![Cell::default(); usize::from(width) * usize::from(height)],
+        }
+    }
+
+    /// Returns the number of columns.
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Returns the cell at `(column, row)`, or `None` if it's outside of the buffer.
+    pub fn get(&self, column: u16, row: u16) -> Option<&Cell> {
+        self.index(column, row).map(|index| &self.cells[index])
+    }
+
+    /// Returns the cell at `(column, row)` for modification, or `None` if it's outside of the
+    /// buffer.
+    pub fn get_mut(&mut self, column: u16, row: u16) -> Option<&mut Cell> {
+        self.index(column, row)
+            .map(move |index| &mut self.cells[index])
+    }
+
+    /// Writes the text into the row, starting at the given column.
+    ///
+    /// Characters which don't fit into the row are cut off. Returns the column after the last
+    /// written character.
+    pub fn set_str(&mut self, column: u16, row: u16, text: &str, style: ContentStyle) -> u16 {
+        let mut column = column;
+
+        for symbol in text.chars() {
+            let width = char_width(symbol) as u16;
+            if width == 0 {
+                // Combining and control characters don't occupy a cell of their own.
+                continue;
+            }
+            if column.saturating_add(width) > self.width || row >= self.height {
+                break;
+            }
+
+            if let Some(cell) = self.get_mut(column, row) {
+                *cell = Cell { symbol, style };
+            }
+            if width == 2 {
+                if let Some(cell) = self.get_mut(column + 1, row) {
+                    *cell = Cell { symbol: ' ', style };
+                }
+            }
+            column += width;
+        }
+
+        column
+    }
+
+    /// Resets all cells to blank cells.
+    pub fn clear(&mut self) {
+        self.cells.fill(Cell::default());
+    }
+
+    /// Writes the changes from `previous` to this buffer to `out` and flushes it.
+    ///
+    /// Only the cells which differ from `previous` are written. The cursor is moved only when
+    /// the next changed cell isn't right after the last written one, and only the attributes and
+    /// colors which differ from the previous written cell are changed. If both buffers don't have
+    /// the same size, all cells are written. The style is reset after the last cell and the
+    /// cursor is left after it.
+    ///
+    /// The buffer is expected to cover the whole screen, the cell `(0, 0)` is written at the top
+    /// left of the screen.
+    ///
+    /// # Notes
+    ///
+    /// The changes are written as ANSI escape codes. On Windows consoles without ANSI support,
+    /// which only support the legacy Windows API, an `Unsupported` error is returned.
+    pub fn flush_diff(&self, previous: &Buffer, out: &mut impl Write) -> io::Result<()> {
+        out.queue(Diff {
+            current: self,
+            previous,
+        })?;
+        out.flush()
+    }
+
+    fn index(&self, column: u16, row: u16) -> Option<usize> {
+        if column < self.width && row < self.height {
+            Some(usize::from(row) * usize::from(self.width) + usize::from(column))
+        } else {
+            None
+        }
+    }
+}
+
+/// The command which writes the difference between two buffers.
+struct Diff<'a> {
+    current: &'a Buffer,
+    previous: &'a Buffer,
+}
+
+impl Command for Diff<'_> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let redraw = self.current.width != self.previous.width
+            || self.current.height != self.previous.height;
+
+        let mut cursor = None;
+        let mut style = ContentStyle::default();
+
+        for row in 0..self.current.height {
+            let mut column = 0;
+            while column < self.current.width {
+                let index =
+                    usize::from(row) * usize::from(self.current.width) + usize::from(column);
+                let cell = &self.current.cells[index];
+                let width = char_width(cell.symbol).max(1) as u16;
+
+                if redraw || self.previous.cells[index] != *cell {
+                    match cursor {
+                        Some((cursor_column, cursor_row))
+                            if cursor_row == row && cursor_column <= column =>
+                        {
+                            if cursor_column < column {
+                                MoveRight(column - cursor_column).write_ansi(f)?;
+                            }
+                        }
+                        _ => MoveTo(column, row).write_ansi(f)?,
+                    }
+
+                    style.write_ansi_transition(f, &cell.style)?;
+                    style = cell.style;
+
+                    f.write_char(cell.symbol)?;
+                    cursor = Some((column + width, row));
+                }
+
+                column += width;
+            }
+        }
+
+        style.write_ansi_transition(f, &ContentStyle::default())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Buffer::flush_diff is not implemented for the legacy Windows API.",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Buffer, Cell};
+    use crate::style::{Color, ContentStyle, Stylize};
+
+    fn diff(current: &Buffer, previous: &Buffer) -> String {
+        let mut out = Vec::new();
+        current.flush_diff(previous, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_set_str() {
+        let mut buffer = Buffer::new(5, 2);
+
+        assert_eq!(buffer.set_str(1, 0, "abcdef", ContentStyle::new()), 5);
+        assert_eq!(buffer.get(4, 0).unwrap().symbol, 'd');
+        assert_eq!(buffer.get(0, 0), Some(&Cell::default()));
+
+        // wide characters occupy two cells and aren't split
+        assert_eq!(buffer.set_str(0, 1, "日本語", ContentStyle::new()), 4);
+        assert_eq!(buffer.get(2, 1).unwrap().symbol, '本');
+        assert_eq!(buffer.get(4, 1).unwrap().symbol, ' ');
+
+        assert_eq!(buffer.get(5, 0), None);
+        assert_eq!(buffer.get(0, 2), None);
+    }
+
+    #[test]
+    fn test_flush_diff_without_changes() {
+        let buffer = Buffer::new(10, 3);
+        assert_eq!(diff(&buffer, &buffer.clone()), "");
+    }
+
+    #[test]
+    fn test_flush_diff_writes_changed_cells() {
+        let previous = Buffer::new(10, 3);
+        let mut current = previous.clone();
+        current.set_str(2, 1, "ab", ContentStyle::new());
+        current.set_str(6, 1, "c", ContentStyle::new());
+        current.set_str(0, 2, "d", ContentStyle::new());
+
+        // adjacent cells don't need a move, gaps in the same row are skipped with MoveRight
+        assert_eq!(diff(&current, &previous), "\x1B[2;3Hab\x1B[2Cc\x1B[3;1Hd");
+    }
+
+    #[test]
+    fn test_flush_diff_changes_styles() {
+        let previous = Buffer::new(10, 1);
+        let mut current = previous.clone();
+        let red = ContentStyle::new().with(Color::Red);
+        current.set_str(0, 0, "ab", red);
+        current.set_str(2, 0, "c", red.bold());
+
+        assert_eq!(
+            diff(&current, &previous),
+            "\x1B[1;1H\x1B[38;5;9mab\x1B[1mc\x1B[22m\x1B[39m"
+        );
+    }
+
+    #[test]
+    fn test_flush_diff_wide_characters() {
+        let previous = Buffer::new(6, 1);
+        let mut current = previous.clone();
+        current.set_str(0, 0, "日x", ContentStyle::new());
+
+        assert_eq!(diff(&current, &previous), "\x1B[1;1H日x");
+    }
+
+    #[test]
+    fn test_flush_diff_redraws_after_resize() {
+        let previous = Buffer::new(2, 1);
+        let current = Buffer::new(3, 1);
+
+        assert_eq!(diff(&current, &previous), "\x1B[1;1H   ");
+    }
+}
@@ -40,7 +40,7 @@
 //!         // Red background
 //!         SetBackgroundColor(Color::Red),
 //!         // Print text
-//!         Print("Blue text on Red.".to_string()),
+//!         Print("Blue text on Red."),
 //!         // Reset to default colors
 //!         ResetColor
 //!     )
@@ -77,7 +77,7 @@
 //!         io::stdout(),
 //!         // Set to bold
 //!         SetAttribute(Attribute::Bold),
-//!         Print("Bold text here.".to_string()),
+//!         Print("Bold text here."),
 //!         // Reset all attributes
 //!         SetAttribute(Attribute::Reset)
 //!     )
@@ -114,14 +114,16 @@
 use std::{
     env,
     fmt::{self, Display},
+    io,
 };
 
 use crate::command::execute_fmt;
-use crate::{csi, impl_display, Command};
+use crate::{csi, cursor::MoveToColumn, impl_display, terminal, Command, QueueableCommand};
 
 pub use self::{
     attributes::Attributes,
     content_style::ContentStyle,
+    sixel::Sixel,
     styled_content::StyledContent,
     stylize::Stylize,
     types::{Attribute, Color, Colored, Colors},
@@ -129,10 +131,12 @@ pub use self::{
 
 mod attributes;
 mod content_style;
+mod sixel;
 mod styled_content;
 mod stylize;
 mod sys;
 mod types;
+pub(crate) mod width;
 
 /// Creates a `StyledContent`.
 ///
@@ -155,6 +159,390 @@ pub fn style<D: Display>(val: D) -> StyledContent<D> {
     ContentStyle::new().apply(val)
 }
 
+/// Prints the styled content centered in the terminal width, on the line the cursor is on.
+///
+/// The padding is computed from the display width of the content, so wide characters like CJK
+/// ideographs count as two columns. Content wider than the terminal is printed left-aligned.
+/// The cursor is left right after the content, no newline is printed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{self, Write};
+/// use crossterm::style::{print_centered, Stylize};
+///
+/// fn main() -> io::Result<()> {
+///     let mut stdout = io::stdout();
+///     print_centered(&mut stdout, "Welcome".bold())?;
+///     stdout.flush()
+/// }
+/// ```
+pub fn print_centered(out: &mut impl io::Write, content: StyledContent<&str>) -> io::Result<()> {
+    let (columns, _) = terminal::size()?;
+    let column = centered_column(columns, width::str_width(content.content()));
+    out.queue(MoveToColumn(column))?
+        .queue(PrintStyledContent(content))?;
+    Ok(())
+}
+
+/// Returns the columns `[start, end)` of the given text, e.g. to scroll a line horizontally.
+///
+/// Columns are counted in terminal cells, wide characters like `日` take two columns. A wide
+/// character cut by `start` or `end` is replaced by a space for each of its cells inside the
+/// range, so the result always takes `end - start` columns if the text is long enough.
+///
+/// Escape sequences, e.g. the ones of [`StyledContent`](struct.StyledContent.html), don't take
+/// any columns. All escape sequences up to `end` are kept, including those in front of `start`,
+/// so the slice is styled like the columns were in the full text. If there are any, the
+/// slice ends with a reset (`ESC [ 0 m`) so its styling doesn't leak into the text after it.
+///
+/// # Examples
+///
+/// ```
+/// use crossterm::style::{slice_columns, Stylize};
+///
+/// let line = format!("{}{}", "crossterm".bold(), " 日本語");
+/// assert_eq!(slice_columns(&line, 5, 12), "\x1B[1mterm\x1B[0m 日\x1B[0m");
+/// ```
+pub fn slice_columns(content: &str, start: usize, end: usize) -> String {
+    width::slice_columns(content, start, end)
+}
+
+/// Returns the column at which content of the given width is centered.
+fn centered_column(columns: u16, content_width: usize) -> u16 {
+    let columns = usize::from(columns);
+    if content_width >= columns {
+        0
+    } else {
+        ((columns - content_width) / 2) as u16
+    }
+}
+
+/// Sets the style, runs `f` and resets the style again.
+///
+/// The style is queued on `out` before `f` is called with `out`, and a
+/// [`ResetColor`](struct.ResetColor.html) is queued after `f` returned, so the style can't be
+/// forgotten to be reset. The style is also reset if `f` panics.
+///
+/// Nothing is flushed, unless `f` flushes `out`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{self, Write};
+/// use crossterm::style::{with_style, ContentStyle, Print, Stylize};
+/// use crossterm::QueueableCommand;
+///
+/// fn main() -> io::Result<()> {
+///     let mut stdout = io::stdout();
+///     with_style(&mut stdout, ContentStyle::new().red().bold(), |stdout| {
+///         stdout.queue(Print("error: "))?;
+///         stdout.queue(Print("file not found"))?;
+///         io::Result::Ok(())
+///     })??;
+///     stdout.flush()
+/// }
+/// ```
+pub fn with_style<W: io::Write, R>(
+    out: &mut W,
+    style: ContentStyle,
+    f: impl FnOnce(&mut W) -> R,
+) -> io::Result<R> {
+    struct ResetOnDrop<'a, W: io::Write> {
+        out: &'a mut W,
+        armed: bool,
+    }
+
+    impl<W: io::Write> Drop for ResetOnDrop<'_, W> {
+        fn drop(&mut self) {
+            if self.armed {
+                let _ = self.out.queue(ResetColor);
+            }
+        }
+    }
+
+    out.queue(SetStyle(style))?;
+
+    let mut guard = ResetOnDrop { out, armed: true };
+    let result = f(guard.out);
+    guard.armed = false;
+    guard.out.queue(ResetColor)?;
+
+    Ok(result)
+}
+
+/// Returns the foreground and background color the console currently writes text with.
+///
+/// This is useful to save and restore the colors around output written directly with the
+/// Windows API. The console attributes can only represent the 16 named colors, so both colors
+/// are always one of those.
+///
+/// The current colors can't be queried from a unix terminal, so this always returns an error
+/// with the kind `Unsupported` there.
+pub fn current_attributes() -> io::Result<Colors> {
+    #[cfg(windows)]
+    {
+        sys::windows::current_attributes()
+    }
+
+    #[cfg(unix)]
+    {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the current colors can't be queried from the terminal",
+        ))
+    }
+}
+
+/// Queries whether the terminal supports [Sixel](struct.Sixel.html) graphics.
+///
+/// The terminal is asked for its primary device attributes (DA1), terminals supporting Sixel
+/// graphics list attribute 4. Returns `Ok(false)` if the terminal doesn't answer within two
+/// seconds.
+///
+/// This always returns `Ok(false)` on Windows.
+///
+/// On unix systems, this function will block and possibly time out while
+/// [`event::read`](crate::event::read) or [`event::poll`](crate::event::poll) are being called.
+#[cfg(feature = "events")]
+pub fn supports_sixel() -> io::Result<bool> {
+    #[cfg(unix)]
+    {
+        Ok(matches!(
+            terminal::sys::primary_device_attributes()?,
+            Some(attributes) if sixel_from_attributes(&attributes)
+        ))
+    }
+
+    #[cfg(windows)]
+    {
+        Ok(false)
+    }
+}
+
+/// Returns whether the primary device attributes list Sixel graphics (attribute 4).
+#[cfg(all(unix, feature = "events"))]
+pub(crate) fn sixel_from_attributes(attributes: &[u16]) -> bool {
+    // The first value is the architectural class, the attributes follow.
+    attributes.iter().skip(1).any(|&attribute| attribute == 4)
+}
+
+/// Probes the number of colors the terminal can display.
+///
+/// Unlike [`available_color_count`](fn.available_color_count.html), which only looks at `TERM`,
+/// this asks the terminal: it sets a truecolor and then a 256-color foreground, reads each back
+/// with a DECRQSS request and restores the original graphic rendition afterwards. Returns
+/// `16_777_216` if the truecolor survived the round trip, `256` if the 256-color did, and falls
+/// back to [`available_color_count`](fn.available_color_count.html) otherwise, including when the
+/// terminal doesn't support DECRQSS.
+///
+/// On Windows, this always returns the fallback.
+///
+/// # Notes
+///
+/// The probe costs a single round trip to the terminal, which is cheap locally but noticeable
+/// over a slow connection, so it's best done once at startup. Raw mode is enabled for the
+/// duration of the probe if it isn't already. A terminal that doesn't answer at all is given two
+/// seconds before the fallback is used.
+///
+/// On unix systems, this function will block and possibly time out while
+/// [`event::read`](crate::event::read) or [`event::poll`](crate::event::poll) are being called.
+#[cfg(feature = "events")]
+pub fn probe_color_count() -> io::Result<u32> {
+    #[cfg(unix)]
+    {
+        let settings = terminal::sys::query_settings(color_count_query().as_bytes())?;
+        terminal::sys::write_query(restore_rendition_query(&settings).as_bytes())?;
+
+        Ok(color_count_from_settings(&settings))
+    }
+
+    #[cfg(windows)]
+    {
+        Ok(u32::from(available_color_count()))
+    }
+}
+
+/// Returns the query of [`probe_color_count`](fn.probe_color_count.html), which requests the
+/// graphic rendition with DECRQSS before and after setting each probed color.
+#[cfg(all(unix, feature = "events"))]
+pub(crate) fn color_count_query() -> String {
+    // ESC P $ q m ESC \      Request the graphic rendition (DECRQSS).
+    const REQUEST: &str = "\x1BP$qm\x1B\\";
+
+    format!(
+        "{request}{truecolor}{request}{indexed}{request}",
+        request = REQUEST,
+        truecolor = csi!("38;2;1;2;3m"),
+        indexed = csi!("38;5;255m"),
+    )
+}
+
+/// Returns the sequence restoring the graphic rendition from the first DECRPSS reply of
+/// [`probe_color_count`](fn.probe_color_count.html).
+#[cfg(all(unix, feature = "events"))]
+pub(crate) fn restore_rendition_query(settings: &[Option<String>]) -> String {
+    // The first reply already contains the trailing `m`. Without it, the probed foreground
+    // color is reset at least.
+    let original = match settings.first() {
+        Some(Some(original)) => original.as_str(),
+        _ => "m",
+    };
+    format!("{}0;{}", csi!(""), original)
+}
+
+/// Determines the color count from the DECRPSS replies of
+/// [`probe_color_count`](fn.probe_color_count.html).
+#[cfg(all(unix, feature = "events"))]
+pub(crate) fn color_count_from_settings(settings: &[Option<String>]) -> u32 {
+    let reported = |sequences: &[&str]| {
+        settings
+            .iter()
+            .flatten()
+            .any(|setting| sequences.iter().any(|sequence| setting.contains(sequence)))
+    };
+
+    // Terminals may report colors with colon separated parameters.
+    if reported(&["38;2;1;2;3", "38:2::1:2:3", "38:2:1:2:3"]) {
+        16_777_216
+    } else if reported(&["38;5;255", "38:5:255"]) {
+        256
+    } else {
+        u32::from(available_color_count())
+    }
+}
+
+/// Queries the color the terminal displays for the given palette index.
+///
+/// The terminal is asked with OSC 4 (`ESC ] 4 ; index ; ? ESC \`) and answers with the color
+/// in the X11 `rgb:RRRR/GGGG/BBBB` format, which is returned as [`Color::Rgb`]. Together with
+/// [`query_palette`](fn.query_palette.html), this allows to snapshot the user's palette and
+/// restore it after changing it.
+///
+/// The query is followed by a primary device attributes query, which every terminal answers, so
+/// a terminal which doesn't support OSC 4 is detected without waiting for a timeout. An error
+/// with the kind `Unsupported` is returned then, as well as when the terminal doesn't answer
+/// within two seconds. Raw mode is enabled while waiting for the reply, if it isn't enabled
+/// already.
+///
+/// This isn't supported on Windows and always returns an error there.
+///
+/// On unix systems, this function will block and possibly time out while
+/// [`event::read`](crate::event::read) or [`event::poll`](crate::event::poll) are being called.
+#[cfg(feature = "events")]
+pub fn query_palette_color(index: u8) -> io::Result<Color> {
+    #[cfg(unix)]
+    {
+        let replies = terminal::sys::query_osc(palette_query(index).as_bytes())?;
+        palette_color_from_replies(&replies, index).ok_or_else(palette_unsupported_error)
+    }
+
+    #[cfg(windows)]
+    {
+        let _ = index;
+        Err(palette_unsupported_error())
+    }
+}
+
+/// Queries the colors the terminal displays for the 16 base colors of its palette.
+///
+/// All colors are queried in a single round trip, see
+/// [`query_palette_color`](fn.query_palette_color.html). An error with the kind `Unsupported` is
+/// returned if the terminal doesn't report all of them.
+///
+/// This isn't supported on Windows and always returns an error there.
+///
+/// On unix systems, this function will block and possibly time out while
+/// [`event::read`](crate::event::read) or [`event::poll`](crate::event::poll) are being called.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::style::query_palette;
+///
+/// fn main() -> io::Result<()> {
+///     for (index, color) in query_palette()?.iter().enumerate() {
+///         println!("{}: {:?}", index, color);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "events")]
+pub fn query_palette() -> io::Result<[Color; 16]> {
+    #[cfg(unix)]
+    {
+        let query: String = (0..16).map(palette_query).collect();
+        let replies = terminal::sys::query_osc(query.as_bytes())?;
+        palette_from_replies(&replies)
+    }
+
+    #[cfg(windows)]
+    {
+        Err(palette_unsupported_error())
+    }
+}
+
+#[cfg(feature = "events")]
+pub(crate) fn palette_unsupported_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "the terminal didn't report its palette",
+    )
+}
+
+/// Returns the OSC 4 query for the given palette index.
+#[cfg(all(unix, feature = "events"))]
+pub(crate) fn palette_query(index: u8) -> String {
+    format!("\x1B]4;{};?\x1B\\", index)
+}
+
+/// Finds the color of the given palette index in the OSC replies, `4;index;rgb:RRRR/GGGG/BBBB`.
+#[cfg(all(unix, feature = "events"))]
+pub(crate) fn palette_color_from_replies(replies: &[String], index: u8) -> Option<Color> {
+    replies.iter().find_map(|reply| {
+        let mut parts = reply.splitn(3, ';');
+        if parts.next()? != "4" || parts.next()?.parse::<u8>().ok()? != index {
+            return None;
+        }
+        parse_x11_rgb(parts.next()?)
+    })
+}
+
+/// Finds the 16 base colors of the palette in the OSC replies of
+/// [`query_palette`](fn.query_palette.html).
+#[cfg(all(unix, feature = "events"))]
+pub(crate) fn palette_from_replies(replies: &[String]) -> io::Result<[Color; 16]> {
+    let mut palette = [Color::Reset; 16];
+    for (index, color) in (0..16).zip(palette.iter_mut()) {
+        *color =
+            palette_color_from_replies(replies, index).ok_or_else(palette_unsupported_error)?;
+    }
+    Ok(palette)
+}
+
+/// Parses a color in the X11 `rgb:R/G/B` format, with one to four hex digits per component.
+#[cfg(all(unix, feature = "events"))]
+fn parse_x11_rgb(spec: &str) -> Option<Color> {
+    let mut components = spec.strip_prefix("rgb:")?.split('/').map(|component| {
+        if component.is_empty() || component.len() > 4 {
+            return None;
+        }
+        let value = u32::from_str_radix(component, 16).ok()?;
+        // Scale the component to 8 bits, e.g. `ff` and `ffff` both become 255.
+        let max = (1 << (4 * component.len())) - 1;
+        Some(((value * 255 + max / 2) / max) as u8)
+    });
+
+    let r = components.next()??;
+    let g = components.next()??;
+    let b = components.next()??;
+    if components.next().is_some() {
+        return None;
+    }
+    Some(Color::Rgb { r, g, b })
+}
+
 /// Returns available color count.
 ///
 /// # Notes
@@ -253,7 +641,7 @@ impl Command for SetUnderlineColor {
 /// execute!(
 ///     stdout(),
 ///     SetColors(Colors::new(Green, Black)),
-///     Print("Hello, world!".to_string()),
+///     Print("Hello, world!"),
 /// ).unwrap();
 /// ```
 ///
@@ -322,10 +710,8 @@ pub struct SetAttributes(pub Attributes);
 
 impl Command for SetAttributes {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        for attr in Attribute::iterator() {
-            if self.0.has(attr) {
-                SetAttribute(attr).write_ansi(f)?;
-            }
+        for attr in self.0.iter() {
+            SetAttribute(attr).write_ansi(f)?;
         }
         Ok(())
     }
@@ -436,6 +822,277 @@ impl<D: Display> Command for PrintStyledContent<D> {
     }
 }
 
+/// A command that prints several styled spans after each other.
+///
+/// Unlike queueing a [`PrintStyledContent`](struct.PrintStyledContent.html) for every span,
+/// the style isn't reset after every span. Only the attributes and colors which differ from
+/// the previous span are changed, and the style is reset once after the last span. This
+/// reduces the amount of bytes written, e.g. for a status line consisting of many segments.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::execute;
+/// use crossterm::style::{PrintSpans, Stylize};
+///
+/// fn main() -> io::Result<()> {
+///     execute!(
+///         io::stdout(),
+///         PrintSpans(&[" NORMAL ".black().on_green(), " main.rs ".on_dark_grey()])
+///     )
+/// }
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrintSpans<'a, D: Display>(pub &'a [StyledContent<D>]);
+
+impl<D: Display> Command for PrintSpans<'_, D> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let mut current = ContentStyle::default();
+
+        for span in self.0 {
+            current.write_ansi_transition(f, span.style())?;
+            write!(f, "{}", span.content())?;
+            current = *span.style();
+        }
+
+        current.write_ansi_suffix(f)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that prints rows of text aligned in columns.
+///
+/// Every cell is padded with spaces to the width of its column, measured in terminal cells so
+/// that wide characters like CJK and most emoji are aligned too. Cells which don't fit into
+/// their column are truncated and end with an ellipsis (`…`). Columns are separated by `gap`
+/// spaces, one by default, and rows by a newline. Cells without a column width are ignored.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::execute;
+/// use crossterm::style::PrintColumns;
+///
+/// fn main() -> io::Result<()> {
+///     let rows: &[&[&str]] = &[&["NAME", "SIZE"], &["Cargo.toml", "2 KiB"], &["src", "-"]];
+///     execute!(io::stdout(), PrintColumns::new(rows, &[12, 6]).gap(2))
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * In raw mode a newline doesn't move the cursor to the start of the line, so the rows aren't
+///   aligned there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrintColumns<'a> {
+    rows: &'a [&'a [&'a str]],
+    widths: &'a [usize],
+    gap: usize,
+}
+
+impl<'a> PrintColumns<'a> {
+    /// Creates a command printing the rows with the given column widths.
+    pub fn new(rows: &'a [&'a [&'a str]], widths: &'a [usize]) -> Self {
+        Self {
+            rows,
+            widths,
+            gap: 1,
+        }
+    }
+
+    /// Sets the number of spaces between columns.
+    pub fn gap(mut self, gap: usize) -> Self {
+        self.gap = gap;
+        self
+    }
+}
+
+impl Command for PrintColumns<'_> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        for (row_index, row) in self.rows.iter().enumerate() {
+            if row_index > 0 {
+                f.write_char('\n')?;
+            }
+
+            for (column, (cell, &width)) in row.iter().zip(self.widths).enumerate() {
+                if column > 0 {
+                    write!(f, "{:1$}", "", self.gap)?;
+                }
+
+                let cell_width = width::str_width(cell);
+                if cell_width <= width {
+                    write!(f, "{}{:2$}", cell, "", width - cell_width)?;
+                } else if width > 0 {
+                    let shown = width::truncate(cell, width - 1);
+                    let padding = width - 1 - width::str_width(shown);
+                    write!(f, "{}…{:2$}", shown, "", padding)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        panic!("tried to execute PrintColumns command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// A command that sets the size of the characters on the line the cursor is on (DECDHL/DECDWL).
+///
+/// Every character on a double-width or double-height line occupies two columns, so a line of
+/// `w` columns only fits `w / 2` characters and cursor positions on it address the doubled
+/// cells, e.g. column 3 is the left half of the second character. Double-height text needs two
+/// lines, the top half on one line and the bottom half on the next, each printing the same text.
+///
+/// Terminals that don't support line attributes ignore this command and render the line
+/// normally.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::{execute, cursor::MoveToNextLine, style::{LineSize, Print}};
+///
+/// fn main() -> io::Result<()> {
+///     execute!(
+///         io::stdout(),
+///         LineSize::DoubleHeightTop,
+///         Print("Banner"),
+///         MoveToNextLine(1),
+///         LineSize::DoubleHeightBottom,
+///         Print("Banner"),
+///         MoveToNextLine(1),
+///     )
+/// }
+/// ```
+///
+/// # Notes
+///
+/// - This command does nothing on the legacy Windows API.
+/// - Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineSize {
+    /// The top half of double-height, double-width characters.
+    DoubleHeightTop,
+    /// The bottom half of double-height, double-width characters.
+    DoubleHeightBottom,
+    /// Single-height, double-width characters.
+    DoubleWidth,
+    /// Single-height, single-width characters, the default.
+    SingleWidth,
+}
+
+impl Command for LineSize {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(match self {
+            LineSize::DoubleHeightTop => "\x1B#3",
+            LineSize::DoubleHeightBottom => "\x1B#4",
+            LineSize::SingleWidth => "\x1B#5",
+            LineSize::DoubleWidth => "\x1B#6",
+        })
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that switches the G0 character set to the DEC special graphics set, which draws
+/// lines with ASCII letters.
+///
+/// This is useful for terminals or fonts that render the Unicode box-drawing characters poorly.
+/// While enabled, these characters are replaced:
+///
+/// | Character | Replacement | Character | Replacement |
+/// |-----------|-------------|-----------|-------------|
+/// | `j`       | `┘`         | `q`       | `─`         |
+/// | `k`       | `┐`         | `t`       | `├`         |
+/// | `l`       | `┌`         | `u`       | `┤`         |
+/// | `m`       | `└`         | `v`       | `┴`         |
+/// | `n`       | `┼`         | `w`       | `┬`         |
+/// | `x`       | `│`         | `a`       | `▒`         |
+/// | `` ` ``   | `◆`         | `~`       | `·`         |
+///
+/// Other lowercase letters are replaced by further symbols, so disable line drawing with
+/// [`DisableLineDrawing`](struct.DisableLineDrawing.html) before printing text.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::{
+///     execute,
+///     style::{DisableLineDrawing, EnableLineDrawing, Print},
+/// };
+///
+/// fn main() -> io::Result<()> {
+///     // Prints ┌──┐
+///     execute!(
+///         io::stdout(),
+///         EnableLineDrawing,
+///         Print("lqqk"),
+///         DisableLineDrawing
+///     )
+/// }
+/// ```
+///
+/// # Notes
+///
+/// - This command does nothing on the legacy Windows API.
+/// - Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnableLineDrawing;
+
+impl Command for EnableLineDrawing {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1B(0")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that switches the G0 character set back to ASCII, after
+/// [`EnableLineDrawing`](struct.EnableLineDrawing.html).
+///
+/// # Notes
+///
+/// - This command does nothing on the legacy Windows API.
+/// - Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisableLineDrawing;
+
+impl Command for DisableLineDrawing {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1B(B")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
 /// A command that resets the colors back to default.
 ///
 /// # Notes
@@ -457,6 +1114,24 @@ impl Command for ResetColor {
 
 /// A command that prints the given displayable type.
 ///
+/// Any type implementing [`Display`] can be printed, so there is no need to convert string
+/// literals or numbers into a `String` first. Printing a `&str` doesn't allocate.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{self, Write};
+/// use crossterm::{queue, style::Print};
+///
+/// fn main() -> io::Result<()> {
+///     let mut stdout = io::stdout();
+///     queue!(stdout, Print("The answer is "), Print(42), Print('\n'))?;
+///     stdout.flush()
+/// }
+/// ```
+///
+/// # Notes
+///
 /// Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Print<T: Display>(pub T);
@@ -483,6 +1158,82 @@ impl<T: Display> Display for Print<T> {
     }
 }
 
+/// A command that prints the given displayable type, turning every `\n` into `\r\n`.
+///
+/// In [raw mode](../terminal/index.html#raw-mode) the terminal doesn't translate line feeds
+/// anymore, so a `\n` moves the cursor one row down but keeps it in the same column, and
+/// `"line1\nline2"` prints `line2` to the right of where `line1` ended. `PrintLn` writes a
+/// carriage return before every line feed, so text prints the same in raw mode as it does
+/// otherwise. Line feeds already preceded by a carriage return are left alone.
+///
+/// Unlike `println!`, no line break is appended to the content.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::{execute, style::PrintLn, terminal};
+///
+/// fn main() -> io::Result<()> {
+///     terminal::enable_raw_mode()?;
+///     execute!(io::stdout(), PrintLn("line 1\nline 2\n"))?;
+///     terminal::disable_raw_mode()
+/// }
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrintLn<T: Display>(pub T);
+
+impl<T: Display> Command for PrintLn<T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        /// Inserts a `\r` before every `\n` which doesn't follow a `\r` already.
+        struct Normalizer<'a, W> {
+            inner: &'a mut W,
+            after_cr: bool,
+        }
+
+        impl<W: fmt::Write> fmt::Write for Normalizer<'_, W> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                let mut start = 0;
+                for (index, c) in s.char_indices() {
+                    if c == '\n' && !self.after_cr {
+                        self.inner.write_str(&s[start..index])?;
+                        self.inner.write_char('\r')?;
+                        start = index;
+                    }
+                    self.after_cr = c == '\r';
+                }
+                self.inner.write_str(&s[start..])
+            }
+        }
+
+        let mut normalizer = Normalizer {
+            inner: f,
+            after_cr: false,
+        };
+        fmt::Write::write_fmt(&mut normalizer, format_args!("{}", self.0))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        panic!("tried to execute PrintLn command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+impl<T: Display> Display for PrintLn<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_ansi(f)
+    }
+}
+
 impl_display!(for SetForegroundColor);
 impl_display!(for SetBackgroundColor);
 impl_display!(for SetColors);
@@ -490,6 +1241,223 @@ impl_display!(for SetAttribute);
 impl_display!(for PrintStyledContent<String>);
 impl_display!(for PrintStyledContent<&'static str>);
 impl_display!(for ResetColor);
+impl_display!(for LineSize);
+impl_display!(for EnableLineDrawing);
+impl_display!(for DisableLineDrawing);
+impl_display!(for PrintColumns<'_>);
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        centered_column, Color, Command, ContentStyle, LineSize, PrintColumns, PrintLn, PrintSpans,
+        Stylize,
+    };
+
+    #[test]
+    fn test_with_style() {
+        use std::io::Write;
+
+        let mut out = Vec::new();
+        let result = super::with_style(&mut out, ContentStyle::new().red(), |out| {
+            out.write_all(b"foo").unwrap();
+            42
+        })
+        .unwrap();
+
+        assert_eq!(result, 42);
+        assert_eq!(out, b"\x1B[38;5;9mfoo\x1B[0m");
+    }
+
+    #[test]
+    fn test_with_style_resets_on_panic() {
+        let mut out = Vec::new();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            super::with_style(&mut out, ContentStyle::new().bold(), |_| panic!("foo"))
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(out, b"\x1B[1m\x1B[0m");
+    }
+
+    #[test]
+    fn test_centered_column() {
+        assert_eq!(centered_column(80, 10), 35);
+        assert_eq!(centered_column(80, 11), 34);
+        assert_eq!(centered_column(10, 10), 0);
+        assert_eq!(centered_column(10, 20), 0);
+        assert_eq!(centered_column(10, super::width::str_width("日本")), 3);
+    }
+
+    #[test]
+    fn test_line_size() {
+        assert_eq!(LineSize::DoubleHeightTop.to_string(), "\x1B#3");
+        assert_eq!(LineSize::DoubleHeightBottom.to_string(), "\x1B#4");
+        assert_eq!(LineSize::SingleWidth.to_string(), "\x1B#5");
+        assert_eq!(LineSize::DoubleWidth.to_string(), "\x1B#6");
+    }
+
+    #[test]
+    fn test_print_columns() {
+        let rows: &[&[&str]] = &[&["name", "size"], &["Cargo.toml", "2"], &["src"]];
+        assert_eq!(
+            PrintColumns::new(rows, &[6, 4]).to_string(),
+            "name   size\nCargo… 2   \nsrc   "
+        );
+        assert_eq!(
+            PrintColumns::new(rows, &[4]).gap(3).to_string(),
+            "name\nCar…\nsrc "
+        );
+        assert_eq!(PrintColumns::new(rows, &[]).to_string(), "\n\n");
+    }
+
+    #[test]
+    fn test_print_columns_wide_characters() {
+        let rows: &[&[&str]] = &[&["日本語", "x"], &["日本", "y"]];
+        // a wide character doesn't fit into the cell before the ellipsis, which is padded instead
+        assert_eq!(
+            PrintColumns::new(rows, &[4, 1]).to_string(),
+            "日…  x\n日本 y"
+        );
+        assert_eq!(PrintColumns::new(rows, &[3, 1]).to_string(), "日… x\n日… y");
+        assert_eq!(PrintColumns::new(rows, &[2, 1]).to_string(), "…  x\n…  y");
+    }
+
+    #[test]
+    fn test_print_ln() {
+        assert_eq!(PrintLn("line1\nline2").to_string(), "line1\r\nline2");
+        assert_eq!(PrintLn("a\r\nb\n\n").to_string(), "a\r\nb\r\n\r\n");
+        assert_eq!(PrintLn("\n").to_string(), "\r\n");
+        assert_eq!(PrintLn("no newline").to_string(), "no newline");
+        assert_eq!(PrintLn(42).to_string(), "42");
+
+        // a `\r` written in a separate chunk still counts
+        assert_eq!(
+            PrintLn(format_args!("{}{}", "a\r", "\nb")).to_string(),
+            "a\r\nb"
+        );
+    }
+
+    #[test]
+    fn test_print_spans() {
+        let spans = [
+            "a".red().bold(),
+            "b".red(),
+            "c".blue().on_green(),
+            "d".on_green(),
+        ];
+
+        let mut ansi = String::new();
+        PrintSpans(&spans).write_ansi(&mut ansi).unwrap();
+
+        assert_eq!(
+            ansi,
+            "\x1B[1m\x1B[38;5;9ma\x1B[22mb\x1B[48;5;10m\x1B[38;5;12mc\x1B[39md\x1B[49m"
+        );
+        // no full reset between the spans
+        assert!(!ansi.contains("\x1B[0m"));
+
+        let spans = ["a".with(Color::Red).italic()];
+        let mut ansi = String::new();
+        PrintSpans(&spans).write_ansi(&mut ansi).unwrap();
+        assert_eq!(ansi, "\x1B[3m\x1B[38;5;9ma\x1B[0m");
+    }
+
+    #[test]
+    #[cfg(all(unix, feature = "events"))]
+    fn test_parse_x11_rgb() {
+        use super::parse_x11_rgb;
+
+        assert_eq!(
+            parse_x11_rgb("rgb:cdcd/0000/ffff"),
+            Some(Color::Rgb {
+                r: 205,
+                g: 0,
+                b: 255
+            })
+        );
+        assert_eq!(
+            parse_x11_rgb("rgb:cd/00/ff"),
+            Some(Color::Rgb {
+                r: 205,
+                g: 0,
+                b: 255
+            })
+        );
+        assert_eq!(
+            parse_x11_rgb("rgb:f/8/0"),
+            Some(Color::Rgb {
+                r: 255,
+                g: 136,
+                b: 0
+            })
+        );
+        assert_eq!(parse_x11_rgb("rgb:ff/ff"), None);
+        assert_eq!(parse_x11_rgb("rgb:ff/ff/ff/ff"), None);
+        assert_eq!(parse_x11_rgb("rgb:fffff/0/0"), None);
+        assert_eq!(parse_x11_rgb("rgb:gg/0/0"), None);
+        assert_eq!(parse_x11_rgb("#ffffff"), None);
+    }
+
+    #[test]
+    #[cfg(all(unix, feature = "events"))]
+    fn test_palette_color_from_replies() {
+        use super::{palette_color_from_replies, palette_query};
+
+        assert_eq!(palette_query(3), "\x1B]4;3;?\x1B\\");
+
+        let replies = [
+            "11;rgb:0000/0000/0000".to_string(),
+            "4;1;rgb:cdcd/0000/0000".to_string(),
+            "4;10;rgb:0000/ffff/0000".to_string(),
+        ];
+        assert_eq!(
+            palette_color_from_replies(&replies, 1),
+            Some(Color::Rgb { r: 205, g: 0, b: 0 })
+        );
+        assert_eq!(
+            palette_color_from_replies(&replies, 10),
+            Some(Color::Rgb { r: 0, g: 255, b: 0 })
+        );
+        assert_eq!(palette_color_from_replies(&replies, 0), None);
+    }
+
+    #[test]
+    #[cfg(all(unix, feature = "events"))]
+    fn test_color_count_from_settings() {
+        use super::{available_color_count, color_count_from_settings};
+
+        let setting = |setting: &str| Some(setting.to_string());
+
+        assert_eq!(
+            color_count_from_settings(&[
+                setting("0m"),
+                setting("0;38;2;1;2;3m"),
+                setting("0;38;5;255m")
+            ]),
+            16_777_216
+        );
+        assert_eq!(
+            color_count_from_settings(&[setting("0m"), setting("0;38:2::1:2:3m")]),
+            16_777_216
+        );
+        assert_eq!(
+            color_count_from_settings(&[
+                setting("0m"),
+                setting("0;38;5;16m"),
+                setting("0;38;5;255m")
+            ]),
+            256
+        );
+        assert_eq!(
+            color_count_from_settings(&[None, None, None]),
+            u32::from(available_color_count())
+        );
+        assert_eq!(
+            color_count_from_settings(&[]),
+            u32::from(available_color_count())
+        );
+    }
+}
 
 /// Utility function for ANSI parsing in Color and Colored.
 /// Gets the next element of `iter` and tries to parse it as a `u8`.
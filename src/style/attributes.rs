@@ -3,7 +3,7 @@ use std::ops::{BitAnd, BitOr, BitXor};
 use crate::style::Attribute;
 
 /// a bitset for all possible attributes
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Attributes(u32);
 
 impl From<Attribute> for Attributes {
@@ -100,6 +100,17 @@ impl Attributes {
     pub const fn is_empty(self) -> bool {
         self.0 == 0
     }
+
+    /// Returns an iterator over all the set attributes.
+    ///
+    /// The attributes are returned in the order of their declaration in [`Attribute`], which
+    /// is the order they are applied in by [`SetAttributes`](crate::style::SetAttributes).
+    /// This means `Reset` comes first, so it never undoes the attributes following it. When
+    /// conflicting attributes like `Bold` and `NormalIntensity` are both set, the one declared
+    /// last wins.
+    pub fn iter(self) -> impl Iterator<Item = Attribute> {
+        Attribute::iterator().filter(move |attribute| self.has(*attribute))
+    }
 }
 
 #[cfg(test)]
@@ -117,4 +128,27 @@ mod tests {
         attributes.toggle(Attribute::Bold);
         assert!(attributes.is_empty());
     }
+
+    #[test]
+    fn test_attributes_iter() {
+        assert_eq!(Attributes::default().iter().count(), 0);
+
+        let attributes = Attributes::from(
+            &[
+                Attribute::NormalIntensity,
+                Attribute::Italic,
+                Attribute::Bold,
+                Attribute::Reset,
+            ][..],
+        );
+        assert_eq!(
+            attributes.iter().collect::<Vec<_>>(),
+            vec![
+                Attribute::Reset,
+                Attribute::Bold,
+                Attribute::Italic,
+                Attribute::NormalIntensity
+            ]
+        );
+    }
 }
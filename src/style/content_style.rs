@@ -1,11 +1,45 @@
 //! This module contains the `content style` that can be applied to an `styled content`.
 
-use std::fmt::Display;
+use std::fmt::{self, Display};
 
-use crate::style::{Attributes, Color, StyledContent};
+use crate::style::{
+    Attribute, Attributes, Color, ResetColor, SetAttribute, SetAttributes, SetBackgroundColor,
+    SetForegroundColor, SetUnderlineColor, StyledContent,
+};
+use crate::Command;
 
 /// The style that can be put on content.
-#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+///
+/// A style is usually built from [`ContentStyle::new`] with the builder methods
+/// [`fg`](ContentStyle::fg) and [`bg`](ContentStyle::bg) and the attribute methods of
+/// [`Stylize`], like `bold`, each of which returns the updated style. The fields are public as
+/// well, for code that prefers to construct the style directly.
+///
+/// [`Stylize`]: crate::style::Stylize
+///
+/// # Examples
+///
+/// ```
+/// use crossterm::style::{Attribute, Attributes, Color, ContentStyle, Stylize};
+///
+/// let style = ContentStyle::new()
+///     .fg(Color::Red)
+///     .bg(Color::Black)
+///     .bold()
+///     .underlined()
+///     .build();
+///
+/// assert_eq!(
+///     style,
+///     ContentStyle {
+///         foreground_color: Some(Color::Red),
+///         background_color: Some(Color::Black),
+///         underline_color: None,
+///         attributes: Attributes::from([Attribute::Bold, Attribute::Underlined].as_ref()),
+///     }
+/// );
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
 pub struct ContentStyle {
     /// The foreground color.
     pub foreground_color: Option<Color>,
@@ -29,6 +63,136 @@ impl ContentStyle {
     pub fn new() -> ContentStyle {
         ContentStyle::default()
     }
+
+    /// Sets the foreground color.
+    #[inline]
+    pub fn fg(mut self, color: Color) -> ContentStyle {
+        self.foreground_color = Some(color);
+        self
+    }
+
+    /// Sets the background color.
+    #[inline]
+    pub fn bg(mut self, color: Color) -> ContentStyle {
+        self.background_color = Some(color);
+        self
+    }
+
+    /// Finishes building the style.
+    ///
+    /// The builder methods return the style itself, this only marks the end of a chain.
+    #[inline]
+    pub fn build(self) -> ContentStyle {
+        self
+    }
+
+    /// Returns the ANSI escape codes that apply this style to the content following them.
+    ///
+    /// These are the same escape codes [`PrintStyledContent`](crate::style::PrintStyledContent)
+    /// emits before the content, which makes it possible to precompute styled strings. Use
+    /// [`ansi_suffix`](ContentStyle::ansi_suffix) to get the escape codes that undo this style.
+    pub fn to_ansi_prefix(&self) -> String {
+        let mut prefix = String::new();
+        // Writing to a `String` can't fail.
+        let _ = self.write_ansi_prefix(&mut prefix);
+        prefix
+    }
+
+    /// Returns the ANSI escape codes that reset this style after the content.
+    ///
+    /// These are the same escape codes [`PrintStyledContent`](crate::style::PrintStyledContent)
+    /// emits after the content.
+    pub fn ansi_suffix(&self) -> String {
+        let mut suffix = String::new();
+        // Writing to a `String` can't fail.
+        let _ = self.write_ansi_suffix(&mut suffix);
+        suffix
+    }
+
+    pub(crate) fn write_ansi_prefix(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if let Some(bg) = self.background_color {
+            SetBackgroundColor(bg).write_ansi(f)?;
+        }
+        if let Some(fg) = self.foreground_color {
+            SetForegroundColor(fg).write_ansi(f)?;
+        }
+        if let Some(ul) = self.underline_color {
+            SetUnderlineColor(ul).write_ansi(f)?;
+        }
+        if !self.attributes.is_empty() {
+            SetAttributes(self.attributes).write_ansi(f)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the ANSI escape codes which change the style from `self` to `to`.
+    ///
+    /// Only what differs between both styles is written, a full reset is only written if `to`
+    /// contains the `Reset` attribute.
+    pub(crate) fn write_ansi_transition(
+        &self,
+        f: &mut impl fmt::Write,
+        to: &ContentStyle,
+    ) -> fmt::Result {
+        if to.attributes.has(Attribute::Reset) && !self.attributes.has(Attribute::Reset) {
+            ResetColor.write_ansi(f)?;
+            // The reset is already written, writing it again with the attributes is redundant.
+            let mut to = *to;
+            to.attributes.unset(Attribute::Reset);
+            return to.write_ansi_prefix(f);
+        }
+
+        let mut disabled = Attributes::default();
+        for attribute in self.attributes.iter() {
+            if to.attributes.has(attribute) {
+                continue;
+            }
+            if let Some(disabling) = attribute.disabling_attribute() {
+                if !disabled.has(disabling) {
+                    SetAttribute(disabling).write_ansi(f)?;
+                    disabled.set(disabling);
+                }
+            }
+        }
+
+        for attribute in to.attributes.iter() {
+            // Attributes which are still set might have been turned off together with a removed
+            // one, e.g. `NormalIntensity` turns off both `Bold` and `Dim`.
+            let turned_off = matches!(
+                attribute.disabling_attribute(),
+                Some(disabling) if disabled.has(disabling)
+            );
+            if !self.attributes.has(attribute) || turned_off {
+                SetAttribute(attribute).write_ansi(f)?;
+            }
+        }
+
+        if self.background_color != to.background_color {
+            SetBackgroundColor(to.background_color.unwrap_or(Color::Reset)).write_ansi(f)?;
+        }
+        if self.foreground_color != to.foreground_color {
+            SetForegroundColor(to.foreground_color.unwrap_or(Color::Reset)).write_ansi(f)?;
+        }
+        if self.underline_color != to.underline_color {
+            SetUnderlineColor(to.underline_color.unwrap_or(Color::Reset)).write_ansi(f)?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn write_ansi_suffix(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if !self.attributes.is_empty() {
+            // Like `PrintStyledContent`, this resets the colors as well.
+            return ResetColor.write_ansi(f);
+        }
+        if self.background_color.is_some() {
+            SetBackgroundColor(Color::Reset).write_ansi(f)?;
+        }
+        if self.foreground_color.is_some() || self.underline_color.is_some() {
+            SetForegroundColor(Color::Reset).write_ansi(f)?;
+        }
+        Ok(())
+    }
 }
 
 impl AsRef<ContentStyle> for ContentStyle {
@@ -41,3 +205,108 @@ impl AsMut<ContentStyle> for ContentStyle {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::style::{Attribute, Color, ContentStyle, Stylize};
+
+    #[test]
+    fn test_ansi_prefix_and_suffix() {
+        let style = ContentStyle::new()
+            .with(Color::Red)
+            .on(Color::Blue)
+            .attribute(Attribute::Bold)
+            .attribute(Attribute::Italic);
+
+        assert_eq!(
+            style.to_ansi_prefix(),
+            "\x1B[48;5;12m\x1B[38;5;9m\x1B[1m\x1B[3m"
+        );
+        assert_eq!(style.ansi_suffix(), "\x1B[0m");
+        assert_eq!(
+            style.apply("foo").to_string(),
+            format!("{}foo{}", style.to_ansi_prefix(), style.ansi_suffix())
+        );
+    }
+
+    #[test]
+    fn test_ansi_prefix_and_suffix_colors_only() {
+        let style = ContentStyle::new().with(Color::Red).on(Color::Blue);
+
+        assert_eq!(style.to_ansi_prefix(), "\x1B[48;5;12m\x1B[38;5;9m");
+        assert_eq!(style.ansi_suffix(), "\x1B[49m\x1B[39m");
+    }
+
+    fn transition(from: ContentStyle, to: ContentStyle) -> String {
+        let mut ansi = String::new();
+        from.write_ansi_transition(&mut ansi, &to).unwrap();
+        ansi
+    }
+
+    #[test]
+    fn test_ansi_transition() {
+        let bold_red = ContentStyle::new()
+            .with(Color::Red)
+            .attribute(Attribute::Bold);
+
+        assert_eq!(transition(bold_red, bold_red), "");
+        assert_eq!(
+            transition(ContentStyle::new(), bold_red),
+            "\x1B[1m\x1B[38;5;9m"
+        );
+        assert_eq!(
+            transition(bold_red, bold_red.with(Color::Blue)),
+            "\x1B[38;5;12m"
+        );
+        assert_eq!(
+            transition(bold_red, ContentStyle::new()),
+            "\x1B[22m\x1B[39m"
+        );
+        // `NormalIntensity` turns off `Dim` as well, so it has to be set again
+        assert_eq!(
+            transition(
+                bold_red.attribute(Attribute::Dim),
+                bold_red.on(Color::Green)
+            ),
+            "\x1B[22m\x1B[1m\x1B[48;5;10m"
+        );
+        assert_eq!(
+            transition(bold_red, ContentStyle::new().attribute(Attribute::Reset)),
+            "\x1B[0m"
+        );
+        // Into a span with `Reset`, the reset is written only once.
+        assert_eq!(
+            transition(
+                ContentStyle::new().on(Color::Green),
+                bold_red.attribute(Attribute::Reset)
+            ),
+            "\x1B[0m\x1B[38;5;9m\x1B[1m"
+        );
+    }
+
+    #[test]
+    fn test_hash_set_of_styles() {
+        use std::collections::HashSet;
+
+        let bold_red = ContentStyle::new()
+            .with(Color::Red)
+            .attribute(Attribute::Bold);
+
+        let mut styles = HashSet::new();
+        assert!(styles.insert(bold_red));
+        assert!(styles.insert(ContentStyle::new()));
+        assert!(styles.insert(bold_red.on(Color::Rgb { r: 1, g: 2, b: 3 })));
+        assert!(!styles.insert(
+            ContentStyle::new()
+                .attribute(Attribute::Bold)
+                .with(Color::Red)
+        ));
+        assert_eq!(styles.len(), 3);
+    }
+
+    #[test]
+    fn test_ansi_prefix_and_suffix_empty() {
+        assert_eq!(ContentStyle::new().to_ansi_prefix(), "");
+        assert_eq!(ContentStyle::new().ansi_suffix(), "");
+    }
+}
@@ -0,0 +1,214 @@
+//! Image output with Sixel graphics.
+
+use std::fmt;
+
+use crate::Command;
+
+/// A command that draws an image with [Sixel](https://en.wikipedia.org/wiki/Sixel) graphics at
+/// the cursor position.
+///
+/// The image is given as indexed colors: `pixels` holds the index into `palette` of every pixel,
+/// row by row starting at the top left, and every row is `width` pixels wide. A trailing
+/// incomplete row is ignored. The palette can have at most 256 colors, the number of colors
+/// terminals support varies, 256 is common.
+///
+/// Use [`supports_sixel`](fn.supports_sixel.html) to check whether the terminal supports Sixel
+/// graphics, terminals that don't may print the sequence as garbage.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::{execute, style::Sixel};
+///
+/// fn main() -> io::Result<()> {
+///     // A 2x2 checkerboard, scaled up to 40x40 pixels.
+///     let palette = [(0, 0, 0), (255, 255, 255)];
+///     let pixels: Vec<u8> = (0..40 * 40)
+///         .map(|i| ((i % 40 / 20 + i / 40 / 20) % 2) as u8)
+///         .collect();
+///
+///     execute!(io::stdout(), Sixel { width: 40, palette: &palette, pixels: &pixels })
+/// }
+/// ```
+///
+/// # Performance
+///
+/// Sixel data encodes six rows of pixels at a time, once per color used in them, so encoding
+/// takes time proportional to the number of pixels times the number of colors used in every
+/// band of six rows. Runs of equal columns are compressed, but the encoded sequence of a large,
+/// detailed image can still be several times as large as its pixel data, and terminals need
+/// noticeable time to decode it. Keep images and palettes small, write through a buffered
+/// writer, and avoid redrawing images which didn't change.
+///
+/// # Notes
+///
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * Not supported by the legacy Windows API, executing it returns an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sixel<'a> {
+    /// The width of the image in pixels.
+    pub width: usize,
+    /// The RGB values of the colors used by the image.
+    pub palette: &'a [(u8, u8, u8)],
+    /// The index into `palette` of every pixel, row by row.
+    pub pixels: &'a [u8],
+}
+
+impl Command for Sixel<'_> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let width = self.width;
+        let height = self.pixels.len().checked_div(width).unwrap_or(0);
+
+        // DCS q, followed by the aspect ratio 1:1 and the size of the image (DECGRA).
+        write!(f, "\x1BPq\"1;1;{};{}", width, height)?;
+
+        for (index, &(r, g, b)) in self.palette.iter().take(256).enumerate() {
+            // Sixel colors are specified as percentages.
+            let percent = |c: u8| (u32::from(c) * 100 + 127) / 255;
+            write!(
+                f,
+                "#{};2;{};{};{}",
+                index,
+                percent(r),
+                percent(g),
+                percent(b)
+            )?;
+        }
+
+        for band_top in (0..height).step_by(6) {
+            if band_top > 0 {
+                // Graphics new line, move to the next band.
+                f.write_char('-')?;
+            }
+
+            let band = &self.pixels[band_top * width..(band_top + 6).min(height) * width];
+            let mut used = [false; 256];
+            for &pixel in band {
+                used[pixel as usize] = true;
+            }
+
+            let mut first_color = true;
+            for color in (0..=255).filter(|&color| used[color as usize]) {
+                if !first_color {
+                    // Graphics carriage return, draw the next color over the same band.
+                    f.write_char('$')?;
+                }
+                first_color = false;
+                write!(f, "#{}", color)?;
+                write_band(f, band, width, color)?;
+            }
+        }
+
+        f.write_str("\x1B\\")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "Sixel graphics are not implemented for the legacy Windows API.",
+        ))
+    }
+}
+
+/// Writes the pixels of a band of up to six rows which have the given color.
+fn write_band(f: &mut impl fmt::Write, band: &[u8], width: usize, color: u8) -> fmt::Result {
+    // Every sixel character encodes a column of six pixels, the top pixel being the lowest bit.
+    let sixel = |x: usize| {
+        let bits = band
+            .chunks(width)
+            .enumerate()
+            .filter(|(_, row)| row[x] == color)
+            .fold(0, |bits, (y, _)| bits | (1 << y));
+        char::from(63 + bits)
+    };
+
+    let mut x = 0;
+    while x < width {
+        let c = sixel(x);
+        let mut count = 1;
+        while x + count < width && sixel(x + count) == c {
+            count += 1;
+        }
+        x += count;
+
+        // Empty columns at the end of the band don't need to be drawn.
+        if c == '?' && x == width {
+            break;
+        }
+        if count > 3 {
+            write!(f, "!{}{}", count, c)?;
+        } else {
+            for _ in 0..count {
+                f.write_char(c)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sixel;
+    use crate::Command;
+
+    fn ansi(sixel: Sixel<'_>) -> String {
+        let mut ansi = String::new();
+        sixel.write_ansi(&mut ansi).unwrap();
+        ansi
+    }
+
+    #[test]
+    fn test_sixel_palette() {
+        let sixel = Sixel {
+            width: 1,
+            palette: &[(255, 0, 0), (0, 128, 255)],
+            pixels: &[1],
+        };
+        assert_eq!(
+            ansi(sixel),
+            "\x1BPq\"1;1;1;1#0;2;100;0;0#1;2;0;50;100#1@\x1B\\"
+        );
+    }
+
+    #[test]
+    fn test_sixel_bands_and_colors() {
+        // Two columns, seven rows: the left column is color 0, the right one color 1, except
+        // for the last row which is color 1 in both columns.
+        let mut pixels = [0, 1].repeat(6);
+        pixels.extend([1, 1]);
+        let sixel = Sixel {
+            width: 2,
+            palette: &[(0, 0, 0), (255, 255, 255)],
+            pixels: &pixels,
+        };
+        assert_eq!(
+            ansi(sixel),
+            "\x1BPq\"1;1;2;7#0;2;0;0;0#1;2;100;100;100#0~$#1?~-#1@@\x1B\\"
+        );
+    }
+
+    #[test]
+    fn test_sixel_run_length() {
+        let sixel = Sixel {
+            width: 8,
+            palette: &[(0, 0, 0), (255, 255, 255)],
+            pixels: &[1, 1, 1, 1, 1, 0, 0, 0],
+        };
+        assert_eq!(
+            ansi(sixel),
+            "\x1BPq\"1;1;8;1#0;2;0;0;0#1;2;100;100;100#0!5?@@@$#1!5@\x1B\\"
+        );
+    }
+
+    #[test]
+    fn test_sixel_empty() {
+        let sixel = Sixel {
+            width: 0,
+            palette: &[],
+            pixels: &[],
+        };
+        assert_eq!(ansi(sixel), "\x1BPq\"1;1;0;0\x1B\\");
+    }
+}
@@ -1,10 +1,11 @@
 use std::convert::TryFrom;
 use std::sync::atomic::{AtomicU32, Ordering};
 
-use crossterm_winapi::{Console, Handle, HandleType, ScreenBuffer};
+use crossterm_winapi::{Console, ScreenBuffer};
 use winapi::um::wincon;
 
-use super::super::{Color, Colored};
+use super::super::{Color, Colored, Colors};
+use crate::terminal::sys::{output_handle, output_screen_buffer};
 
 const FG_GREEN: u16 = wincon::FOREGROUND_GREEN;
 const FG_RED: u16 = wincon::FOREGROUND_RED;
@@ -21,7 +22,7 @@ pub(crate) fn set_foreground_color(fg_color: Color) -> std::io::Result<()> {
 
     let color_value: u16 = Colored::ForegroundColor(fg_color).into();
 
-    let screen_buffer = ScreenBuffer::current()?;
+    let screen_buffer = output_screen_buffer()?;
     let csbi = screen_buffer.info()?;
 
     // Notice that the color values are stored in wAttribute.
@@ -45,7 +46,7 @@ pub(crate) fn set_background_color(bg_color: Color) -> std::io::Result<()> {
 
     let color_value: u16 = Colored::BackgroundColor(bg_color).into();
 
-    let screen_buffer = ScreenBuffer::current()?;
+    let screen_buffer = output_screen_buffer()?;
     let csbi = screen_buffer.info()?;
 
     // Notice that the color values are stored in wAttribute.
@@ -64,10 +65,42 @@ pub(crate) fn set_background_color(bg_color: Color) -> std::io::Result<()> {
     Ok(())
 }
 
+pub(crate) fn current_attributes() -> std::io::Result<Colors> {
+    let attrs = output_screen_buffer()?.info()?.attributes();
+
+    Ok(Colors {
+        foreground: Some(color_from_attributes(attrs & 0x000f)),
+        background: Some(color_from_attributes((attrs & 0x00f0) >> 4)),
+    })
+}
+
+/// Decodes the intensity and color bits of a foreground attribute into a `Color`.
+///
+/// Background attributes use the same layout shifted four bits to the left.
+fn color_from_attributes(bits: u16) -> Color {
+    match bits {
+        0 => Color::Black,
+        FG_INTENSITY => Color::DarkGrey,
+        FG_RED => Color::DarkRed,
+        FG_GREEN => Color::DarkGreen,
+        FG_BLUE => Color::DarkBlue,
+        x if x == FG_GREEN | FG_RED => Color::DarkYellow,
+        x if x == FG_RED | FG_BLUE => Color::DarkMagenta,
+        x if x == FG_GREEN | FG_BLUE => Color::DarkCyan,
+        x if x == FG_RED | FG_GREEN | FG_BLUE => Color::Grey,
+        x if x == FG_INTENSITY | FG_RED => Color::Red,
+        x if x == FG_INTENSITY | FG_GREEN => Color::Green,
+        x if x == FG_INTENSITY | FG_BLUE => Color::Blue,
+        x if x == FG_INTENSITY | FG_GREEN | FG_RED => Color::Yellow,
+        x if x == FG_INTENSITY | FG_RED | FG_BLUE => Color::Magenta,
+        x if x == FG_INTENSITY | FG_GREEN | FG_BLUE => Color::Cyan,
+        _ => Color::White,
+    }
+}
+
 pub(crate) fn reset() -> std::io::Result<()> {
     if let Ok(original_color) = u16::try_from(ORIGINAL_CONSOLE_COLOR.load(Ordering::Relaxed)) {
-        Console::from(Handle::new(HandleType::CurrentOutputHandle)?)
-            .set_text_attribute(original_color)?;
+        Console::from(output_handle()?).set_text_attribute(original_color)?;
     }
 
     Ok(())
@@ -177,9 +210,39 @@ mod tests {
     use crate::style::sys::windows::set_foreground_color;
 
     use super::{
-        Color, Colored, BG_INTENSITY, BG_RED, FG_INTENSITY, FG_RED, ORIGINAL_CONSOLE_COLOR,
+        color_from_attributes, Color, Colored, BG_INTENSITY, BG_RED, FG_INTENSITY, FG_RED,
+        ORIGINAL_CONSOLE_COLOR,
     };
 
+    #[test]
+    fn test_color_from_attributes() {
+        let colors = [
+            Color::Black,
+            Color::DarkGrey,
+            Color::Red,
+            Color::DarkRed,
+            Color::Green,
+            Color::DarkGreen,
+            Color::Yellow,
+            Color::DarkYellow,
+            Color::Blue,
+            Color::DarkBlue,
+            Color::Magenta,
+            Color::DarkMagenta,
+            Color::Cyan,
+            Color::DarkCyan,
+            Color::White,
+            Color::Grey,
+        ];
+
+        for color in colors {
+            let fg: u16 = Colored::ForegroundColor(color).into();
+            let bg: u16 = Colored::BackgroundColor(color).into();
+            assert_eq!(color_from_attributes(fg), color);
+            assert_eq!(color_from_attributes(bg >> 4), color);
+        }
+    }
+
     #[test]
     fn test_parse_fg_color() {
         let colored = Colored::ForegroundColor(Color::Red);
@@ -171,6 +171,28 @@ impl Attribute {
     pub const fn bytes(self) -> u32 {
         1 << ((self as u32) + 1)
     }
+    /// Returns the attribute which turns this attribute off again.
+    ///
+    /// Returns `None` for attributes which don't turn anything on, like `Reset` or `NoItalic`.
+    pub(crate) fn disabling_attribute(self) -> Option<Attribute> {
+        use self::Attribute::*;
+
+        match self {
+            Bold | Dim => Some(NormalIntensity),
+            Italic | Fraktur => Some(NoItalic),
+            Underlined | DoubleUnderlined | Undercurled | Underdotted | Underdashed => {
+                Some(NoUnderline)
+            }
+            SlowBlink | RapidBlink => Some(NoBlink),
+            Reverse => Some(NoReverse),
+            Hidden => Some(NoHidden),
+            CrossedOut => Some(NotCrossedOut),
+            Framed | Encircled => Some(NotFramedOrEncircled),
+            OverLined => Some(NotOverLined),
+            _ => None,
+        }
+    }
+
     /// Returns the SGR attribute value.
     ///
     /// See <https://en.wikipedia.org/wiki/ANSI_escape_code#SGR_parameters>
@@ -181,3 +203,43 @@ impl Attribute {
         SGR[self as usize].to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Attribute;
+
+    #[test]
+    fn test_rare_attributes_sgr() {
+        assert_eq!(Attribute::OverLined.sgr(), "53");
+        assert_eq!(Attribute::NotOverLined.sgr(), "55");
+        assert_eq!(Attribute::NoItalic.sgr(), "23");
+        assert_eq!(Attribute::NoUnderline.sgr(), "24");
+        assert_eq!(Attribute::NoBlink.sgr(), "25");
+        assert_eq!(Attribute::NotCrossedOut.sgr(), "29");
+
+        assert_eq!(Attribute::OverLined.to_string(), "\x1B[53m");
+        assert_eq!(Attribute::NotOverLined.to_string(), "\x1B[55m");
+        assert_eq!(Attribute::NoBlink.to_string(), "\x1B[25m");
+    }
+
+    #[test]
+    fn test_disabling_attribute() {
+        assert_eq!(
+            Attribute::OverLined.disabling_attribute(),
+            Some(Attribute::NotOverLined)
+        );
+        assert_eq!(
+            Attribute::RapidBlink.disabling_attribute(),
+            Some(Attribute::NoBlink)
+        );
+        assert_eq!(
+            Attribute::Italic.disabling_attribute(),
+            Some(Attribute::NoItalic)
+        );
+        assert_eq!(
+            Attribute::CrossedOut.disabling_attribute(),
+            Some(Attribute::NotCrossedOut)
+        );
+        assert_eq!(Attribute::NotOverLined.disabling_attribute(), None);
+    }
+}
@@ -163,6 +163,162 @@ impl Color {
         }
         Some(color)
     }
+
+    /// Returns the index (0-15) of the basic color nearest to this color.
+    ///
+    /// The basic colors are mapped to their own index and `AnsiValue`s below 16 to their value.
+    /// Other colors are mapped to the basic color with the smallest RGB distance, using xterm's
+    /// default palette. This is useful to downgrade colors for terminals which only support the
+    /// 16 basic colors. `Reset` is mapped to 7 (`Grey`), the usual default foreground color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crossterm::style::Color;
+    ///
+    /// assert_eq!(Color::DarkRed.to_ansi16(), 1);
+    /// assert_eq!(Color::Rgb { r: 250, g: 10, b: 10 }.to_ansi16(), 9);
+    /// assert_eq!(Color::AnsiValue(21).to_ansi16(), 4);
+    /// ```
+    pub fn to_ansi16(self) -> u8 {
+        match self {
+            Color::AnsiValue(n) if n < 16 => n,
+            color => color.to_rgb().map_or(7, nearest_ansi16),
+        }
+    }
+
+    /// Returns whether the color is perceived as dark.
+    ///
+    /// The perceived luminance is computed with the ITU-R BT.601 weights (`0.299 R + 0.587 G +
+    /// 0.114 B`) and colors below half of the maximum luminance are dark. The 256 colors from
+    /// index 16 on use xterm's default palette.
+    ///
+    /// Returns `None` for colors whose RGB value isn't known: `Reset`, the 16 named colors and
+    /// [`AnsiValue`](Color::AnsiValue)s below 16, which terminal themes commonly change. Use
+    /// [`query_palette`](crate::style::query_palette) to find out their values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crossterm::style::Color;
+    ///
+    /// assert_eq!(Color::AnsiValue(17).is_dark(), Some(true));
+    /// assert_eq!(Color::Rgb { r: 255, g: 255, b: 0 }.is_dark(), Some(false));
+    /// assert_eq!(Color::DarkBlue.is_dark(), None);
+    /// assert_eq!(Color::Reset.is_dark(), None);
+    /// ```
+    pub fn is_dark(self) -> Option<bool> {
+        let known = match self {
+            Color::Rgb { .. } => true,
+            Color::AnsiValue(n) => n >= 16,
+            _ => false,
+        };
+        if !known {
+            return None;
+        }
+
+        self.to_rgb().map(|(r, g, b)| {
+            let luminance = 299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b);
+            luminance < 128 * 1000
+        })
+    }
+
+    /// Returns black or white, whichever is more readable on top of this color.
+    ///
+    /// See [`is_dark`](Color::is_dark) for how the color is classified. `Reset` is returned for
+    /// colors whose RGB value isn't known, i.e. the default foreground color, which the terminal
+    /// theme keeps readable on top of its own colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crossterm::style::Color;
+    ///
+    /// assert_eq!(Color::AnsiValue(17).contrasting(), Color::White);
+    /// assert_eq!(Color::Rgb { r: 255, g: 255, b: 0 }.contrasting(), Color::Black);
+    /// ```
+    pub fn contrasting(self) -> Color {
+        match self.is_dark() {
+            Some(true) => Color::White,
+            Some(false) => Color::Black,
+            None => Color::Reset,
+        }
+    }
+
+    /// Returns the RGB value of the color, using xterm's default palette for the basic and the
+    /// 256 colors. Returns `None` for `Reset`.
+    pub(crate) fn to_rgb(self) -> Option<(u8, u8, u8)> {
+        let index = match self {
+            Color::Reset => return None,
+            Color::Rgb { r, g, b } => return Some((r, g, b)),
+            Color::AnsiValue(n) => n,
+            Color::Black => 0,
+            Color::DarkRed => 1,
+            Color::DarkGreen => 2,
+            Color::DarkYellow => 3,
+            Color::DarkBlue => 4,
+            Color::DarkMagenta => 5,
+            Color::DarkCyan => 6,
+            Color::Grey => 7,
+            Color::DarkGrey => 8,
+            Color::Red => 9,
+            Color::Green => 10,
+            Color::Yellow => 11,
+            Color::Blue => 12,
+            Color::Magenta => 13,
+            Color::Cyan => 14,
+            Color::White => 15,
+        };
+
+        Some(match index {
+            0..=15 => ANSI16_RGB[index as usize],
+            // 6x6x6 color cube
+            16..=231 => {
+                let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+                let n = index - 16;
+                (level(n / 36), level(n / 6 % 6), level(n % 6))
+            }
+            // grayscale ramp
+            232..=255 => {
+                let level = 8 + (index - 232) * 10;
+                (level, level, level)
+            }
+        })
+    }
+}
+
+/// xterm's default RGB values of the 16 basic colors.
+const ANSI16_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Returns the index of the basic color with the smallest RGB distance to the given color.
+fn nearest_ansi16((r, g, b): (u8, u8, u8)) -> u8 {
+    let distance = |&(pr, pg, pb): &(u8, u8, u8)| {
+        let dr = i32::from(r) - i32::from(pr);
+        let dg = i32::from(g) - i32::from(pg);
+        let db = i32::from(b) - i32::from(pb);
+        dr * dr + dg * dg + db * db
+    };
+
+    (0..16u8)
+        .min_by_key(|&index| distance(&ANSI16_RGB[index as usize]))
+        .unwrap_or(7)
 }
 
 impl TryFrom<&str> for Color {
@@ -352,6 +508,102 @@ mod tests {
         assert_eq!("foo".parse(), Ok(Color::White));
     }
 
+    #[test]
+    fn test_to_ansi16() {
+        assert_eq!(Color::Black.to_ansi16(), 0);
+        assert_eq!(Color::Grey.to_ansi16(), 7);
+        assert_eq!(Color::White.to_ansi16(), 15);
+        assert_eq!(Color::Reset.to_ansi16(), 7);
+        assert_eq!(Color::AnsiValue(9).to_ansi16(), 9);
+
+        // RGB colors
+        assert_eq!(Color::Rgb { r: 0, g: 0, b: 0 }.to_ansi16(), 0);
+        assert_eq!(Color::Rgb { r: 128, g: 0, b: 0 }.to_ansi16(), 1);
+        assert_eq!(
+            Color::Rgb {
+                r: 255,
+                g: 140,
+                b: 0
+            }
+            .to_ansi16(),
+            3
+        );
+        assert_eq!(
+            Color::Rgb {
+                r: 120,
+                g: 120,
+                b: 130
+            }
+            .to_ansi16(),
+            8
+        );
+        assert_eq!(
+            Color::Rgb {
+                r: 250,
+                g: 250,
+                b: 250
+            }
+            .to_ansi16(),
+            15
+        );
+
+        // the 6x6x6 color cube and the grayscale ramp
+        assert_eq!(Color::AnsiValue(196).to_ansi16(), 9);
+        assert_eq!(Color::AnsiValue(28).to_ansi16(), 2);
+        assert_eq!(Color::AnsiValue(232).to_ansi16(), 0);
+        assert_eq!(Color::AnsiValue(255).to_ansi16(), 7);
+    }
+
+    #[test]
+    fn test_is_dark() {
+        // colors which terminal themes change
+        assert_eq!(Color::Reset.is_dark(), None);
+        assert_eq!(Color::Black.is_dark(), None);
+        assert_eq!(Color::White.is_dark(), None);
+        assert_eq!(Color::DarkRed.is_dark(), None);
+        assert_eq!(Color::AnsiValue(15).is_dark(), None);
+
+        assert_eq!(Color::AnsiValue(16).is_dark(), Some(true));
+        assert_eq!(Color::AnsiValue(231).is_dark(), Some(false));
+
+        // the threshold is a luminance of 128
+        assert_eq!(
+            Color::Rgb {
+                r: 127,
+                g: 127,
+                b: 127
+            }
+            .is_dark(),
+            Some(true)
+        );
+        assert_eq!(
+            Color::Rgb {
+                r: 128,
+                g: 128,
+                b: 128
+            }
+            .is_dark(),
+            Some(false)
+        );
+
+        // green contributes most and blue least to the luminance
+        assert_eq!(Color::Rgb { r: 0, g: 219, b: 0 }.is_dark(), Some(false));
+        assert_eq!(Color::Rgb { r: 0, g: 0, b: 255 }.is_dark(), Some(true));
+
+        // the grayscale ramp
+        assert_eq!(Color::AnsiValue(243).is_dark(), Some(true));
+        assert_eq!(Color::AnsiValue(244).is_dark(), Some(false));
+    }
+
+    #[test]
+    fn test_contrasting() {
+        assert_eq!(Color::Reset.contrasting(), Color::Reset);
+        assert_eq!(Color::Black.contrasting(), Color::Reset);
+        assert_eq!(Color::Grey.contrasting(), Color::Reset);
+        assert_eq!(Color::AnsiValue(17).contrasting(), Color::White);
+        assert_eq!(Color::AnsiValue(231).contrasting(), Color::Black);
+    }
+
     #[test]
     fn test_know_rgb_color_conversion() {
         assert_eq!(Color::from((0, 0, 0)), Color::Rgb { r: 0, g: 0, b: 0 });
@@ -0,0 +1,255 @@
+//! Display width of text in terminal cells.
+//!
+//! This is an approximation of the East Asian Width property: combining marks and control
+//! characters don't take a cell, wide and fullwidth characters (CJK, Hangul, most emoji) take
+//! two cells and everything else takes one.
+
+use crate::csi;
+
+/// Ranges of characters which don't occupy a cell on their own.
+const ZERO_WIDTH: &[(u32, u32)] = &[
+    (0x0300, 0x036F), // combining diacritical marks
+    (0x0483, 0x0489),
+    (0x0591, 0x05BD),
+    (0x0610, 0x061A),
+    (0x064B, 0x065F),
+    (0x1AB0, 0x1AFF),
+    (0x1DC0, 0x1DFF),
+    (0x200B, 0x200F), // zero width space, joiners and direction marks
+    (0x20D0, 0x20FF), // combining marks for symbols
+    (0xFE00, 0xFE0F), // variation selectors
+    (0xFE20, 0xFE2F),
+    (0xE0100, 0xE01EF),
+];
+
+/// Ranges of characters which occupy two cells.
+const WIDE: &[(u32, u32)] = &[
+    (0x1100, 0x115F), // Hangul Jamo
+    (0x231A, 0x231B),
+    (0x2329, 0x232A),
+    (0x23E9, 0x23EC),
+    (0x25FD, 0x25FE),
+    (0x2614, 0x2615),
+    (0x2E80, 0x303E), // CJK radicals, symbols and punctuation
+    (0x3041, 0x33FF), // Hiragana, Katakana, CJK compatibility
+    (0x3400, 0x4DBF), // CJK unified ideographs extension A
+    (0x4E00, 0x9FFF), // CJK unified ideographs
+    (0xA000, 0xA4CF), // Yi
+    (0xA960, 0xA97F),
+    (0xAC00, 0xD7A3), // Hangul syllables
+    (0xF900, 0xFAFF), // CJK compatibility ideographs
+    (0xFE10, 0xFE19),
+    (0xFE30, 0xFE6F),
+    (0xFF00, 0xFF60), // fullwidth forms
+    (0xFFE0, 0xFFE6),
+    (0x1F004, 0x1F004),
+    (0x1F0CF, 0x1F0CF),
+    (0x1F18E, 0x1F18E),
+    (0x1F191, 0x1F19A),
+    (0x1F200, 0x1F251),
+    (0x1F300, 0x1F64F), // pictographs and emoticons
+    (0x1F680, 0x1F6FF), // transport and map symbols
+    (0x1F7E0, 0x1F7EB),
+    (0x1F900, 0x1F9FF), // supplemental symbols and pictographs
+    (0x1FA70, 0x1FAFF),
+    (0x20000, 0x2FFFD),
+    (0x30000, 0x3FFFD),
+];
+
+fn in_ranges(ranges: &[(u32, u32)], c: u32) -> bool {
+    ranges
+        .binary_search_by(|&(start, end)| {
+            if end < c {
+                std::cmp::Ordering::Less
+            } else if start > c {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Returns the number of cells the character occupies.
+pub(crate) fn char_width(c: char) -> usize {
+    let c = c as u32;
+    if c < 0x20 || (0x7F..0xA0).contains(&c) || in_ranges(ZERO_WIDTH, c) {
+        0
+    } else if in_ranges(WIDE, c) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Returns the number of cells the text occupies.
+pub(crate) fn str_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+/// Returns the longest prefix of the text which occupies at most `width` cells.
+pub(crate) fn truncate(s: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (index, c) in s.char_indices() {
+        used += char_width(c);
+        if used > width {
+            return &s[..index];
+        }
+    }
+    s
+}
+
+/// Returns the columns `[start, end)` of the text, keeping the escape sequences up to the end of
+/// the slice and appending a reset if there are any.
+///
+/// Wide characters cut by `start` or `end` are replaced by spaces for their visible cells.
+pub(crate) fn slice_columns(s: &str, start: usize, end: usize) -> String {
+    let mut sliced = String::new();
+    let mut styled = false;
+    let mut column = 0;
+    // Whether the last character taking a cell is part of the slice, the zero-width characters
+    // following it belong to it.
+    let mut shown = false;
+
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        if c == '\x1B' {
+            let length = escape_sequence_len(rest);
+            if column <= end {
+                sliced.push_str(&rest[..length]);
+                styled = true;
+            }
+            rest = &rest[length..];
+            continue;
+        }
+        rest = &rest[c.len_utf8()..];
+
+        let width = char_width(c);
+        if width == 0 {
+            if shown {
+                sliced.push(c);
+            }
+            continue;
+        }
+
+        let next = column + width;
+        shown = column >= start && next <= end;
+        if shown {
+            sliced.push(c);
+        } else {
+            let visible = next.min(end).saturating_sub(column.max(start));
+            for _ in 0..visible {
+                sliced.push(' ');
+            }
+        }
+        column = next;
+    }
+
+    if styled {
+        sliced.push_str(csi!("0m"));
+    }
+    sliced
+}
+
+/// Returns the length in bytes of the escape sequence at the start of the text.
+fn escape_sequence_len(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    match bytes.get(1) {
+        // CSI, ends with a byte in the range 0x40-0x7E
+        Some(b'[') => bytes[2..]
+            .iter()
+            .position(|b| (0x40..=0x7E).contains(b))
+            .map_or(bytes.len(), |index| index + 3),
+        // OSC, ends with BEL or ST (ESC \)
+        Some(b']') => {
+            for index in 2..bytes.len() {
+                if bytes[index] == 0x07 {
+                    return index + 1;
+                }
+                if bytes[index] == 0x1B && bytes.get(index + 1) == Some(&b'\\') {
+                    return index + 2;
+                }
+            }
+            bytes.len()
+        }
+        // ESC followed by a single character
+        Some(_) => 1 + s[1..].chars().next().map_or(0, char::len_utf8),
+        None => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{char_width, slice_columns, str_width, truncate};
+
+    #[test]
+    fn test_char_width() {
+        assert_eq!(char_width('a'), 1);
+        assert_eq!(char_width('é'), 1);
+        assert_eq!(char_width('\u{301}'), 0);
+        assert_eq!(char_width('\t'), 0);
+        assert_eq!(char_width('中'), 2);
+        assert_eq!(char_width('한'), 2);
+        assert_eq!(char_width('Ａ'), 2);
+        assert_eq!(char_width('🦀'), 2);
+    }
+
+    #[test]
+    fn test_str_width() {
+        assert_eq!(str_width(""), 0);
+        assert_eq!(str_width("crossterm"), 9);
+        assert_eq!(str_width("e\u{301}"), 1);
+        assert_eq!(str_width("日本語"), 6);
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("crossterm", 5), "cross");
+        assert_eq!(truncate("crossterm", 20), "crossterm");
+        assert_eq!(truncate("crossterm", 0), "");
+        // a wide character doesn't fit into the last cell
+        assert_eq!(truncate("日本語", 3), "日");
+        // combining marks stay with their base character
+        assert_eq!(truncate("e\u{301}x", 1), "e\u{301}");
+    }
+
+    #[test]
+    fn test_slice_columns() {
+        assert_eq!(slice_columns("crossterm", 5, 9), "term");
+        assert_eq!(slice_columns("crossterm", 0, 5), "cross");
+        assert_eq!(slice_columns("crossterm", 5, 20), "term");
+        assert_eq!(slice_columns("crossterm", 20, 30), "");
+        assert_eq!(slice_columns("crossterm", 5, 5), "");
+        // combining marks stay with their base character
+        assert_eq!(slice_columns("e\u{301}xe\u{301}", 1, 3), "xe\u{301}");
+    }
+
+    #[test]
+    fn test_slice_columns_wide_characters() {
+        assert_eq!(slice_columns("日本語", 2, 4), "本");
+        // wide characters straddling the start or the end are replaced by spaces
+        assert_eq!(slice_columns("日本語", 1, 5), " 本 ");
+        assert_eq!(slice_columns("a日本", 0, 2), "a ");
+        assert_eq!(slice_columns("a日本", 2, 5), " 本");
+        assert_eq!(slice_columns("日本語", 1, 2), " ");
+    }
+
+    #[test]
+    fn test_slice_columns_keeps_styling() {
+        // the bold and the red before the slice stay active
+        assert_eq!(
+            slice_columns("\x1B[1mab\x1B[38;5;9mcd\x1B[0mef", 1, 3),
+            "\x1B[1mb\x1B[38;5;9mc\x1B[0m"
+        );
+        // sequences directly behind the slice are kept, later ones are dropped
+        assert_eq!(
+            slice_columns("ab\x1B[22mc\x1B[1md", 0, 2),
+            "ab\x1B[22m\x1B[0m"
+        );
+        assert_eq!(slice_columns("abc\x1B[1md", 0, 2), "ab");
+        assert_eq!(
+            slice_columns("\x1B]8;;https://example.com\x07日本\x1B]8;;\x1B\\", 1, 4),
+            "\x1B]8;;https://example.com\x07 本\x1B]8;;\x1B\\\x1B[0m"
+        );
+    }
+}
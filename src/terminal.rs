@@ -83,6 +83,9 @@
 //!
 //! For manual execution control check out [crossterm::queue](../macro.queue.html).
 
+#[cfg(unix)]
+use std::os::unix::io::RawFd;
+use std::path::Path;
 use std::{fmt, io};
 
 #[cfg(windows)]
@@ -90,7 +93,7 @@ use crossterm_winapi::{ConsoleMode, Handle, ScreenBuffer};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 #[cfg(windows)]
-use winapi::um::wincon::ENABLE_WRAP_AT_EOL_OUTPUT;
+use winapi::um::wincon::{ENABLE_VIRTUAL_TERMINAL_PROCESSING, ENABLE_WRAP_AT_EOL_OUTPUT};
 
 #[doc(no_inline)]
 use crate::Command;
@@ -99,7 +102,7 @@ use crate::{csi, impl_display};
 pub(crate) mod sys;
 
 #[cfg(feature = "events")]
-pub use sys::supports_keyboard_enhancement;
+pub use sys::{supports_keyboard_enhancement, terminal_version};
 
 /// Tells whether the raw mode is enabled.
 ///
@@ -123,6 +126,34 @@ pub fn enable_raw_mode() -> io::Result<()> {
     sys::enable_raw_mode()
 }
 
+/// Enables raw mode, but keeps the terminal features selected by the given config.
+///
+/// [enable_raw_mode](terminal::enable_raw_mode) is equal to calling this function with
+/// `RawModeConfig::default()`. Use [disable_raw_mode](terminal::disable_raw_mode) to restore
+/// the previous mode.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::terminal::{disable_raw_mode, enable_raw_mode_with, RawModeConfig};
+///
+/// fn main() -> io::Result<()> {
+///     // Keep `\n` translated into `\r\n`, so `println!` keeps working.
+///     enable_raw_mode_with(RawModeConfig {
+///         output_processing: true,
+///         ..RawModeConfig::default()
+///     })?;
+///
+///     println!("still in raw mode");
+///
+///     disable_raw_mode()
+/// }
+/// ```
+pub fn enable_raw_mode_with(config: RawModeConfig) -> io::Result<()> {
+    sys::enable_raw_mode_with(config)
+}
+
 /// Disables raw mode.
 ///
 /// Please have a look at the [raw mode](./index.html#raw-mode) section.
@@ -130,252 +161,1810 @@ pub fn disable_raw_mode() -> io::Result<()> {
     sys::disable_raw_mode()
 }
 
+/// Enables cbreak mode.
+///
+/// Cbreak mode sits between the default mode and the [raw mode](./index.html#raw-mode):
+/// input is no longer line buffered or echoed, but special keys like `CTRL+C` are still
+/// processed by the terminal driver and raise their signals.
+///
+/// On Windows `ENABLE_PROCESSED_INPUT` is kept set while line input and echo are disabled.
+pub fn enable_cbreak_mode() -> io::Result<()> {
+    sys::enable_cbreak_mode()
+}
+
+/// Disables cbreak mode.
+///
+/// Please have a look at [enable_cbreak_mode](terminal::enable_cbreak_mode).
+pub fn disable_cbreak_mode() -> io::Result<()> {
+    sys::disable_cbreak_mode()
+}
+
+/// The optional features to enable with [enter_tui](terminal::enter_tui).
+///
+/// Every feature is disabled by default.
+#[cfg(feature = "events")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TuiOptions {
+    /// Capture mouse events with [`EnableMouseCapture`](crate::event::EnableMouseCapture).
+    pub mouse: bool,
+    /// Report focus changes with [`EnableFocusChange`](crate::event::EnableFocusChange).
+    pub focus: bool,
+    /// Report pasted text at once with
+    /// [`EnableBracketedPaste`](crate::event::EnableBracketedPaste).
+    #[cfg(feature = "bracketed-paste")]
+    pub paste: bool,
+}
+
+#[cfg(feature = "events")]
+impl TuiOptions {
+    fn queue_enter(&self, out: &mut impl io::Write) -> io::Result<()> {
+        use crate::{cursor::Hide, event, QueueableCommand};
+
+        out.queue(EnterAlternateScreen)?.queue(Hide)?;
+        if self.mouse {
+            out.queue(event::EnableMouseCapture)?;
+        }
+        if self.focus {
+            out.queue(event::EnableFocusChange)?;
+        }
+        #[cfg(feature = "bracketed-paste")]
+        if self.paste {
+            out.queue(event::EnableBracketedPaste)?;
+        }
+        Ok(())
+    }
+
+    fn queue_leave(&self, out: &mut impl io::Write) -> io::Result<()> {
+        use crate::{cursor::Show, event, QueueableCommand};
+
+        #[cfg(feature = "bracketed-paste")]
+        if self.paste {
+            out.queue(event::DisableBracketedPaste)?;
+        }
+        if self.focus {
+            out.queue(event::DisableFocusChange)?;
+        }
+        if self.mouse {
+            out.queue(event::DisableMouseCapture)?;
+        }
+        out.queue(Show)?.queue(LeaveAlternateScreen)?;
+        Ok(())
+    }
+}
+
+/// Sets the terminal up for a full screen application and returns a guard undoing it.
+///
+/// This is the usual startup sequence of a TUI, in this order:
+///
+/// 1. Enable [raw mode](./index.html#raw-mode), unless it's already enabled.
+/// 2. [`EnterAlternateScreen`](struct.EnterAlternateScreen.html).
+/// 3. [`Hide`](crate::cursor::Hide) the cursor.
+/// 4. Depending on `options`, [`EnableMouseCapture`](crate::event::EnableMouseCapture),
+///    [`EnableFocusChange`](crate::event::EnableFocusChange) and
+///    [`EnableBracketedPaste`](crate::event::EnableBracketedPaste).
+///
+/// The commands are written to `out`, which is flushed afterwards. The returned [`TuiGuard`]
+/// undoes these steps in reverse order: it disables the features enabled from `options`, shows
+/// the cursor, leaves the alternate screen and disables raw mode if it was enabled by this
+/// function. If a step fails, the steps before it are undone before the error is returned.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::{
+///     event::read,
+///     terminal::{enter_tui, TuiOptions},
+/// };
+///
+/// fn main() -> io::Result<()> {
+///     let mut stdout = io::stdout();
+///     let guard = enter_tui(
+///         &mut stdout,
+///         TuiOptions {
+///             mouse: true,
+///             ..TuiOptions::default()
+///         },
+///     )?;
+///
+///     read()?;
+///
+///     guard.restore(&mut stdout)
+/// }
+/// ```
+#[cfg(feature = "events")]
+pub fn enter_tui(out: &mut impl io::Write, options: TuiOptions) -> io::Result<TuiGuard> {
+    let raw_mode = !is_raw_mode_enabled()?;
+    if raw_mode {
+        enable_raw_mode()?;
+    }
+
+    let guard = TuiGuard {
+        options,
+        raw_mode,
+        restored: false,
+    };
+
+    if let Err(error) = options.queue_enter(out).and_then(|()| out.flush()) {
+        let _ = guard.restore(out);
+        return Err(error);
+    }
+
+    Ok(guard)
+}
+
+/// Restores the terminal set up by [enter_tui](terminal::enter_tui).
+///
+/// Call [`restore`](TuiGuard::restore) to restore the terminal through the writer given to
+/// `enter_tui` and to see whether it succeeded. Otherwise, the terminal is restored through stdout
+/// when the guard is dropped, which also happens if the application returns early or panics.
+#[cfg(feature = "events")]
+#[derive(Debug)]
+#[must_use = "the terminal is restored as soon as the guard is dropped"]
+pub struct TuiGuard {
+    options: TuiOptions,
+    raw_mode: bool,
+    restored: bool,
+}
+
+#[cfg(feature = "events")]
+impl TuiGuard {
+    /// Restores the terminal, writing the commands to `out`.
+    pub fn restore(mut self, out: &mut impl io::Write) -> io::Result<()> {
+        self.restored = true;
+        self.leave(out)
+    }
+
+    fn leave(&self, out: &mut impl io::Write) -> io::Result<()> {
+        // Disable raw mode even if the commands can't be written.
+        let written = self.options.queue_leave(out).and_then(|()| out.flush());
+        if self.raw_mode {
+            disable_raw_mode()?;
+        }
+        written
+    }
+}
+
+#[cfg(feature = "events")]
+impl Drop for TuiGuard {
+    fn drop(&mut self) {
+        if !self.restored {
+            let _ = self.leave(&mut io::stdout());
+        }
+    }
+}
+
+/// Suspends the application to the shell, like Ctrl+Z does outside of raw mode.
+///
+/// In raw mode, the terminal doesn't turn Ctrl+Z into `SIGTSTP` anymore, the application reads
+/// it as a key event instead. Call this function then to stop the process with job control:
+///
+/// 1. The terminal is restored: the main screen is shown, the cursor is shown and raw mode is
+///    disabled. Whether the alternate screen is active and the cursor is hidden is queried from
+///    the terminal with DECRQM, a terminal which doesn't answer is assumed to show the main screen
+///    and the cursor.
+/// 2. `SIGTSTP` is raised, which stops the process until the shell continues it, e.g. with `fg`.
+/// 3. Once continued, the prior state is applied again: raw mode is enabled if it was enabled,
+///    and the alternate screen is entered and the cursor hidden if they were before.
+///
+/// The function returns after the process was continued. The content of the alternate screen
+/// isn't restored, so the application should redraw everything. If the application installed a
+/// handler for `SIGTSTP`, the handler runs instead of the process being stopped.
+///
+/// On Windows, this does nothing.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::{
+///     event::{read, Event, KeyCode, KeyEvent, KeyModifiers},
+///     terminal::suspend,
+/// };
+///
+/// fn main() -> io::Result<()> {
+///     if let Event::Key(KeyEvent {
+///         code: KeyCode::Char('z'),
+///         modifiers: KeyModifiers::CONTROL,
+///         ..
+///     }) = read()?
+///     {
+///         suspend()?;
+///         // redraw the screen
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "events")]
+pub fn suspend() -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::io::Write;
+
+        use crate::{cursor, QueueableCommand};
+
+        let raw_mode = is_raw_mode_enabled()?;
+        let (alternate_screen, cursor_hidden) = {
+            let responses = crate::run_queries(
+                &mut io::stdout(),
+                &[
+                    crate::Query::private_mode(1049),
+                    crate::Query::private_mode(25),
+                ],
+            )?;
+            let report = |index: usize| responses[index].unwrap_or(ModeReport::NotRecognized);
+            (report(0).is_set(), report(1) == ModeReport::Reset)
+        };
+
+        let mut stdout = io::stdout();
+        if alternate_screen {
+            stdout.queue(LeaveAlternateScreen)?;
+        }
+        if cursor_hidden {
+            stdout.queue(cursor::Show)?;
+        }
+        stdout.flush()?;
+        if raw_mode {
+            disable_raw_mode()?;
+        }
+
+        sys::raise_stop_signal()?;
+
+        if raw_mode {
+            enable_raw_mode()?;
+        }
+        if alternate_screen {
+            stdout.queue(EnterAlternateScreen)?;
+        }
+        if cursor_hidden {
+            stdout.queue(cursor::Hide)?;
+        }
+        stdout.flush()
+    }
+
+    #[cfg(windows)]
+    {
+        Ok(())
+    }
+}
+
+/// A snapshot of all terminal settings, taken with [save_termios](terminal::save_termios).
+///
+/// On unix systems, this is the complete `termios` of the terminal. On Windows, this is empty.
+#[derive(Clone)]
+pub struct TermiosSnapshot {
+    #[cfg(unix)]
+    termios: libc::termios,
+}
+
+impl fmt::Debug for TermiosSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TermiosSnapshot").finish_non_exhaustive()
+    }
+}
+
+/// Takes a snapshot of all terminal settings.
+///
+/// [enable_raw_mode](terminal::enable_raw_mode) and [disable_raw_mode](terminal::disable_raw_mode)
+/// only restore the settings from before raw mode was enabled. Applications which change other
+/// settings, or embed crossterm in a larger application configuring the terminal, can use this
+/// together with [restore_termios](terminal::restore_termios) to get back to exactly the
+/// settings they started with.
+///
+/// On Windows, this does nothing and returns an empty snapshot.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::terminal::{enable_raw_mode, disable_raw_mode, restore_termios, save_termios};
+///
+/// fn main() -> io::Result<()> {
+///     let snapshot = save_termios()?;
+///
+///     enable_raw_mode()?;
+///     // ...
+///     disable_raw_mode()?;
+///
+///     restore_termios(&snapshot)
+/// }
+/// ```
+pub fn save_termios() -> io::Result<TermiosSnapshot> {
+    Ok(TermiosSnapshot {
+        #[cfg(unix)]
+        termios: sys::save_termios()?,
+    })
+}
+
+/// Applies all terminal settings of a snapshot taken with [save_termios](terminal::save_termios).
+///
+/// This doesn't change whether crossterm considers raw mode to be enabled, so disable raw mode
+/// before restoring a snapshot taken while it was disabled.
+///
+/// On Windows, this does nothing.
+pub fn restore_termios(snapshot: &TermiosSnapshot) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        sys::restore_termios(&snapshot.termios)
+    }
+
+    #[cfg(windows)]
+    {
+        let _ = snapshot;
+        Ok(())
+    }
+}
+
 /// Returns the terminal size `(columns, rows)`.
 ///
 /// The top left cell is represented `(1, 1)`.
+///
+/// On unix systems, the terminal is asked for its size with [size_via_escape] if it reports a
+/// size of zero.
+///
+/// See [dimensions](terminal::dimensions) for a variant that returns a [Size](terminal::Size),
+/// which can't be mixed up.
 pub fn size() -> io::Result<(u16, u16)> {
-    sys::size()
+    dimensions().map(Into::into)
+}
+
+/// Returns the terminal size.
+///
+/// This is the same as [size](terminal::size), but the number of columns and rows are named, so
+/// they can't be transposed by accident.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::terminal::{dimensions, Size};
+///
+/// fn main() -> io::Result<()> {
+///     let required = Size::new(80, 24);
+///     if !required.fits_in(dimensions()?) {
+///         println!("the terminal must be at least 80x24");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn dimensions() -> io::Result<Size> {
+    let (columns, rows) = sys::size()?;
+    Ok(Size { columns, rows })
+}
+
+/// The size of the terminal in cells, see [dimensions](terminal::dimensions).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Size {
+    /// The number of columns, i.e. the width.
+    pub columns: u16,
+    /// The number of rows, i.e. the height.
+    pub rows: u16,
+}
+
+impl Size {
+    /// Creates a size of the given number of columns and rows.
+    pub fn new(columns: u16, rows: u16) -> Self {
+        Size { columns, rows }
+    }
+
+    /// Returns whether something of this size fits into `other`, i.e. neither has more columns
+    /// nor more rows.
+    pub fn fits_in(self, other: Size) -> bool {
+        self.columns <= other.columns && self.rows <= other.rows
+    }
+}
+
+/// Converts a `(columns, rows)` tuple, as returned by [size](terminal::size).
+impl From<(u16, u16)> for Size {
+    fn from((columns, rows): (u16, u16)) -> Self {
+        Size { columns, rows }
+    }
+}
+
+/// Converts into a `(columns, rows)` tuple, as returned by [size](terminal::size).
+impl From<Size> for (u16, u16) {
+    fn from(size: Size) -> Self {
+        (size.columns, size.rows)
+    }
+}
+
+/// Returns the terminal size `(columns, rows)` by asking the terminal where the cursor ends up
+/// when it's moved to the bottom right corner.
+///
+/// This is the classic escape sequence based size detection: the cursor position is saved
+/// (`ESC 7`), the cursor is moved to `ESC [ 999 ; 999 H`, which the terminal clamps to the bottom
+/// right corner, its position is queried with `ESC [ 6 n` and the saved position is restored
+/// (`ESC 8`), also if the query fails. The cursor visibly jumps for a moment and the saved
+/// cursor position of [SavePosition](crate::cursor::SavePosition) is overwritten.
+///
+/// [size](terminal::size) falls back to this if the terminal reports a size of zero, which
+/// happens in some sandboxes.
+///
+/// On unix systems, this function will block and possibly time out while
+/// [`event::read`](crate::event::read) or [`event::poll`](crate::event::poll) are being called.
+#[cfg(all(unix, feature = "events"))]
+pub fn size_via_escape() -> io::Result<(u16, u16)> {
+    sys::size_via_escape()
+}
+
+/// The size of the terminal window in cells and in pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WindowSize {
+    /// The number of rows.
+    pub rows: u16,
+    /// The number of columns.
+    pub columns: u16,
+    /// The width of the window in pixels, 0 if the terminal doesn't report it.
+    pub width: u16,
+    /// The height of the window in pixels, 0 if the terminal doesn't report it.
+    pub height: u16,
+}
+
+/// Returns the terminal size in cells and in pixels.
+///
+/// Many terminals don't report their size in pixels, in which case `width` and `height` are 0.
+///
+/// This isn't supported on Windows and always returns an error there.
+pub fn window_size() -> io::Result<WindowSize> {
+    #[cfg(unix)]
+    {
+        sys::window_size()
+    }
+
+    #[cfg(windows)]
+    {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Window pixel size not implemented for the Windows API.",
+        ))
+    }
+}
+
+/// Returns the size of a single cell `(width, height)` in pixels.
+///
+/// This is derived from [window_size](terminal::window_size) by dividing the size of the window
+/// in pixels by the number of columns and rows. It can be used to compensate for non-square
+/// cells, e.g. to draw charts with the correct aspect ratio.
+///
+/// Returns an error with the kind `Unsupported` if the terminal doesn't report its size in
+/// pixels, which is common, and always on Windows.
+pub fn cell_size() -> io::Result<(u16, u16)> {
+    cell_size_of(window_size()?)
+}
+
+fn cell_size_of(size: WindowSize) -> io::Result<(u16, u16)> {
+    if size.width == 0 || size.height == 0 || size.columns == 0 || size.rows == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the terminal doesn't report its size in pixels",
+        ));
+    }
+
+    Ok((size.width / size.columns, size.height / size.rows))
+}
+
+/// Returns the size `(columns, rows)` of the terminal behind the given file descriptor.
+///
+/// Unlike [size](terminal::size), which always queries the controlling terminal, this can be
+/// used to query any terminal, for example the pseudo terminal of a child process.
+#[cfg(unix)]
+pub fn size_of(fd: RawFd) -> io::Result<(u16, u16)> {
+    sys::size_of(fd)
+}
+
+/// Returns the size `(columns, rows)` of the screen buffer behind the given handle.
+///
+/// Unlike [size](terminal::size), which always queries the current output handle, this can be
+/// used to query any screen buffer.
+#[cfg(windows)]
+pub fn size_of(handle: &Handle) -> io::Result<(u16, u16)> {
+    sys::size_of(handle)
+}
+
+/// Resizes the terminal behind the given file descriptor to `(columns, rows)`.
+///
+/// This is useful for resizing the pseudo terminal of a child process whenever the parent
+/// terminal is resized.
+#[cfg(unix)]
+pub fn set_size_of(fd: RawFd, columns: u16, rows: u16) -> io::Result<()> {
+    sys::set_size_of(fd, columns, rows)
+}
+
+/// Resizes the screen buffer behind the given handle to `(columns, rows)`.
+///
+/// This performs the same resize as the [SetSize](terminal::SetSize) command, but on the given
+/// screen buffer instead of the current one.
+#[cfg(windows)]
+pub fn set_size_of(handle: &Handle, columns: u16, rows: u16) -> io::Result<()> {
+    sys::set_size_of(handle, columns, rows)
+}
+
+/// Sets the number of lines of the console screen buffer, i.e. the visible lines plus the
+/// scrollback.
+///
+/// The size of the visible window stays the same. If the window is further down than the new
+/// number of lines, it's moved up. Returns an error with the kind `InvalidInput` if `lines` is
+/// smaller than the height of the window.
+///
+/// This is only supported on Windows. Other terminals manage their scrollback themselves, this
+/// always returns an error with the kind `Unsupported` there.
+pub fn set_scrollback_lines(lines: u16) -> io::Result<()> {
+    #[cfg(windows)]
+    {
+        sys::set_scrollback_lines(lines)
+    }
+
+    #[cfg(unix)]
+    {
+        let _ = lines;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "The scrollback is managed by the terminal.",
+        ))
+    }
+}
+
+/// Makes the WinAPI fallback of commands on this thread target the given screen buffer.
+///
+/// By default, commands executed through the WinAPI change the current console output. With
+/// this, cursor movement, clearing, scrolling and color commands change the given screen buffer
+/// instead, e.g. a second screen buffer used as back buffer which is shown once a frame is
+/// complete. Pass `None` to target the current console output again. Returns the previously set
+/// handle.
+///
+/// This only affects commands executed through the WinAPI. ANSI escape sequences are always
+/// written to the writer the command is executed on.
+#[cfg(windows)]
+pub fn set_winapi_output_handle(handle: Option<Handle>) -> Option<Handle> {
+    sys::set_output_handle(handle)
+}
+
+/// The code page of UTF-8, see [set_code_page](terminal::set_code_page).
+#[cfg(windows)]
+pub const UTF8_CODE_PAGE: u32 = 65001;
+
+/// Returns the code page the console uses to translate output, e.g. 437 or
+/// [UTF8_CODE_PAGE](terminal::UTF8_CODE_PAGE).
+///
+/// This is only available on Windows.
+#[cfg(windows)]
+pub fn code_page() -> io::Result<u32> {
+    sys::code_page()
+}
+
+/// Sets the code page the console uses to translate output.
+///
+/// Rust strings are UTF-8, but older consoles default to a legacy code page and print non-ASCII
+/// characters as mojibake. Setting the code page to [UTF8_CODE_PAGE](terminal::UTF8_CODE_PAGE)
+/// fixes that. The code page belongs to the console and outlives the application, so restore the
+/// previous one, as returned by [code_page](terminal::code_page), before exiting.
+///
+/// This is only available on Windows.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::terminal::{code_page, set_code_page, UTF8_CODE_PAGE};
+///
+/// fn main() -> io::Result<()> {
+///     let original = code_page()?;
+///     set_code_page(UTF8_CODE_PAGE)?;
+///
+///     println!("Grüße, 世界");
+///
+///     set_code_page(original)
+/// }
+/// ```
+#[cfg(windows)]
+pub fn set_code_page(code_page: u32) -> io::Result<()> {
+    sys::set_code_page(code_page)
+}
+
+/// Returns the code page the console uses to translate input.
+///
+/// This is only available on Windows.
+#[cfg(windows)]
+pub fn input_code_page() -> io::Result<u32> {
+    sys::input_code_page()
+}
+
+/// Sets the code page the console uses to translate input, see
+/// [set_code_page](terminal::set_code_page).
+///
+/// This is only available on Windows.
+#[cfg(windows)]
+pub fn set_input_code_page(code_page: u32) -> io::Result<()> {
+    sys::set_input_code_page(code_page)
+}
+
+/// Tells whether the console currently processes ANSI escape sequences written to stdout.
+///
+/// On Windows, `ansi_support::supports_ansi` tries to enable virtual terminal processing and
+/// reports whether that attempt succeeded. This reads the current mode of the console output
+/// instead and checks whether `ENABLE_VIRTUAL_TERMINAL_PROCESSING` is actually set. Returns
+/// `false` if the console mode can't be read, e.g. when the output isn't a console.
+///
+/// This always returns `true` on unix systems.
+pub fn vt_processing_enabled() -> bool {
+    #[cfg(unix)]
+    {
+        true
+    }
+
+    #[cfg(windows)]
+    {
+        Handle::current_out_handle()
+            .and_then(|handle| ConsoleMode::from(handle).mode())
+            .map(|mode| mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0)
+            .unwrap_or(false)
+    }
+}
+
+/// Disables line wrapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisableLineWrap;
+
+impl Command for DisableLineWrap {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("?7l"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        let console_mode = ConsoleMode::from(sys::output_handle()?);
+        let new_mode = console_mode.mode()? & !ENABLE_WRAP_AT_EOL_OUTPUT;
+        console_mode.set_mode(new_mode)?;
+        Ok(())
+    }
+}
+
+/// Enable line wrapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnableLineWrap;
+
+impl Command for EnableLineWrap {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("?7h"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        let console_mode = ConsoleMode::from(sys::output_handle()?);
+        let new_mode = console_mode.mode()? | ENABLE_WRAP_AT_EOL_OUTPUT;
+        console_mode.set_mode(new_mode)?;
+        Ok(())
+    }
+}
+
+/// Enables or disables line wrapping until it's dropped, then restores the previous state.
+///
+/// The previous state is queried when the guard is created, with DECRQM on unix systems and from
+/// the console mode (`ENABLE_WRAP_AT_EOL_OUTPUT`) on Windows. This lets components change line
+/// wrapping without clobbering each other's settings, as long as nested guards are dropped in
+/// reverse order of creation, which Rust scopes do naturally. Terminals which don't answer the
+/// query are assumed to have line wrapping enabled, which is the default.
+///
+/// The commands are executed on stdout.
+///
+/// On unix systems, creating the guard will block and possibly time out while
+/// [`event::read`](crate::event::read) or [`event::poll`](crate::event::poll) are being called.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{self, Write};
+/// use crossterm::terminal::LineWrapGuard;
+///
+/// fn main() -> io::Result<()> {
+///     let _guard = LineWrapGuard::new(false)?;
+///     // Long lines are cut off at the right margin instead of wrapping.
+///     write!(io::stdout(), "{}", "-".repeat(500))
+/// }
+/// ```
+#[cfg(feature = "events")]
+#[derive(Debug)]
+pub struct LineWrapGuard {
+    previous: bool,
+}
+
+#[cfg(feature = "events")]
+impl LineWrapGuard {
+    /// Enables line wrapping if `enabled` is true, disables it otherwise.
+    pub fn new(enabled: bool) -> io::Result<LineWrapGuard> {
+        let previous = is_line_wrap_enabled()?;
+        set_line_wrap(enabled)?;
+        Ok(LineWrapGuard { previous })
+    }
+
+    /// Returns whether line wrapping was enabled before the guard was created.
+    pub fn previous(&self) -> bool {
+        self.previous
+    }
+}
+
+#[cfg(feature = "events")]
+impl Drop for LineWrapGuard {
+    fn drop(&mut self) {
+        let _ = set_line_wrap(self.previous);
+    }
+}
+
+#[cfg(feature = "events")]
+fn is_line_wrap_enabled() -> io::Result<bool> {
+    #[cfg(unix)]
+    {
+        Ok(!matches!(
+            query_private_mode(7)?,
+            ModeReport::Reset | ModeReport::PermanentlyReset
+        ))
+    }
+
+    #[cfg(windows)]
+    {
+        let console_mode = ConsoleMode::from(sys::output_handle()?);
+        Ok(console_mode.mode()? & ENABLE_WRAP_AT_EOL_OUTPUT != 0)
+    }
+}
+
+#[cfg(feature = "events")]
+fn set_line_wrap(enabled: bool) -> io::Result<()> {
+    use crate::ExecutableCommand;
+
+    if enabled {
+        io::stdout().execute(EnableLineWrap)?;
+    } else {
+        io::stdout().execute(DisableLineWrap)?;
+    }
+    Ok(())
+}
+
+/// A command that enables origin mode (DECOM).
+///
+/// In origin mode, cursor positions are relative to the scroll region instead of the screen:
+/// [`MoveTo(0, 0)`](../cursor/struct.MoveTo.html) moves the cursor to the first column of the
+/// top row of the scroll region, and the cursor can't be moved outside of the region. Without a
+/// scroll region, the whole screen is the region and origin mode makes no difference.
+///
+/// Setting the scroll region (`ESC [ top ; bottom r`) moves the cursor to the origin, which is
+/// the top of the region in origin mode.
+///
+/// # Notes
+///
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * Use [DisableOriginMode](./struct.DisableOriginMode.html) to make positions relative to the
+///   screen again.
+/// * The Windows console has no scroll regions, so this does nothing with WinAPI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnableOriginMode;
+
+impl Command for EnableOriginMode {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("?6h"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that disables origin mode (DECOM), making cursor positions relative to the
+/// screen again.
+///
+/// See [EnableOriginMode](./struct.EnableOriginMode.html).
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisableOriginMode;
+
+impl Command for DisableOriginMode {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("?6l"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that enables left and right margins (DECLRMM, mode 69).
+///
+/// While this mode is enabled, [`SetLeftRightMargins`](./struct.SetLeftRightMargins.html) can
+/// restrict scrolling to a range of columns, e.g. to scroll the panes of a vertically split
+/// screen independently.
+///
+/// # Notes
+///
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * Only some terminals support left and right margins, e.g. xterm, iTerm2, WezTerm and
+///   Contour. Others ignore this command.
+/// * While the mode is enabled, `ESC [ s` sets the margins instead of saving the cursor
+///   position. [`SavePosition`](../cursor/struct.SavePosition.html) is unaffected.
+/// * The Windows console has no margins, so this does nothing with WinAPI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnableLeftRightMargins;
+
+impl Command for EnableLeftRightMargins {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("?69h"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that disables left and right margins (DECLRMM), which resets them to the full
+/// width of the screen.
+///
+/// See [EnableLeftRightMargins](./struct.EnableLeftRightMargins.html).
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisableLeftRightMargins;
+
+impl Command for DisableLeftRightMargins {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("?69l"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that sets the left and right margins (DECSLRM).
+///
+/// `left` and `right` are the first and the last column inside the margins, the leftmost column
+/// being 0. Text scrolled with [`ScrollUp`](./struct.ScrollUp.html), inserted or deleted lines
+/// only move the cells between the margins. Setting the margins moves the cursor to the home
+/// position.
+///
+/// # Notes
+///
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * [`EnableLeftRightMargins`](./struct.EnableLeftRightMargins.html) must be executed first,
+///   terminals ignore this command otherwise. Only some terminals support margins at all.
+/// * The Windows console has no margins, so this does nothing with WinAPI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetLeftRightMargins {
+    /// The first column inside the margins.
+    pub left: u16,
+    /// The last column inside the margins.
+    pub right: u16,
+}
+
+impl Command for SetLeftRightMargins {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, csi!("{};{}s"), self.left + 1, self.right + 1)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that sets (enables) the DEC private mode with the given number (`ESC [ ? n h`).
+///
+/// This makes it possible to enable modes crossterm has no command for. Prefer the typed
+/// commands where they exist, e.g. [`EnableLineWrap`](struct.EnableLineWrap.html) instead of
+/// `SetPrivateMode(7)`, they document their behavior and work on the legacy Windows API.
+///
+/// # Notes
+///
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * With the legacy Windows API, only line wrapping (7), cursor visibility (25) and the
+///   alternate screen (1049) are supported, other modes are ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetPrivateMode(pub u16);
+
+impl Command for SetPrivateMode {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, csi!("?{}h"), self.0)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        match self.0 {
+            7 => EnableLineWrap.execute_winapi(),
+            25 => crate::cursor::Show.execute_winapi(),
+            1049 => EnterAlternateScreen.execute_winapi(),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A command that resets (disables) the DEC private mode with the given number (`ESC [ ? n l`).
+///
+/// This makes it possible to disable modes crossterm has no command for. Prefer the typed
+/// commands where they exist, e.g. [`DisableLineWrap`](struct.DisableLineWrap.html) instead of
+/// `ResetPrivateMode(7)`, they document their behavior and work on the legacy Windows API.
+///
+/// # Notes
+///
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * With the legacy Windows API, only line wrapping (7), cursor visibility (25) and the
+///   alternate screen (1049) are supported, other modes are ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResetPrivateMode(pub u16);
+
+impl Command for ResetPrivateMode {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, csi!("?{}l"), self.0)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        match self.0 {
+            7 => DisableLineWrap.execute_winapi(),
+            25 => crate::cursor::Hide.execute_winapi(),
+            1049 => LeaveAlternateScreen.execute_winapi(),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The state of a DEC private mode, as reported by the terminal.
+///
+/// See [`query_private_mode`].
+#[cfg(feature = "events")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModeReport {
+    /// The terminal doesn't know the mode.
+    NotRecognized,
+    /// The mode is set.
+    Set,
+    /// The mode is reset.
+    Reset,
+    /// The mode is set and can't be reset.
+    PermanentlySet,
+    /// The mode is reset and can't be set.
+    PermanentlyReset,
+}
+
+#[cfg(feature = "events")]
+impl ModeReport {
+    /// Decodes the value of a DECRPM reply (`ESC [ ? n ; value $ y`).
+    pub(crate) fn from_value(value: u8) -> Self {
+        match value {
+            1 => ModeReport::Set,
+            2 => ModeReport::Reset,
+            3 => ModeReport::PermanentlySet,
+            4 => ModeReport::PermanentlyReset,
+            _ => ModeReport::NotRecognized,
+        }
+    }
+
+    /// Returns whether the mode is set, permanently or not.
+    pub fn is_set(self) -> bool {
+        matches!(self, ModeReport::Set | ModeReport::PermanentlySet)
+    }
+
+    /// Returns whether the mode is supported, i.e. it is set or can be set.
+    pub fn is_supported(self) -> bool {
+        matches!(
+            self,
+            ModeReport::Set | ModeReport::Reset | ModeReport::PermanentlySet
+        )
+    }
+}
+
+/// Queries the state of the DEC private mode with the given number.
+///
+/// The terminal is asked for the state of the mode with DECRQM (`ESC [ ? n $ p`), followed by a
+/// primary device attributes query which every terminal answers. Returns
+/// [`ModeReport::NotRecognized`] if the terminal doesn't know the mode, doesn't support DECRQM,
+/// or doesn't answer within two seconds. Raw mode is enabled while waiting for the reply, if it
+/// isn't enabled already.
+///
+/// Use [`run_queries`](crate::run_queries) with
+/// [`Query::private_mode`](crate::Query::private_mode) to query several modes in a single round
+/// trip.
+///
+/// This isn't supported on Windows and always returns an error there.
+///
+/// On unix systems, this function will block and possibly time out while
+/// [`event::read`](crate::event::read) or [`event::poll`](crate::event::poll) are being called.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use crossterm::terminal::query_private_mode;
+///
+/// fn main() -> io::Result<()> {
+///     let synchronized_output = query_private_mode(2026)?.is_supported();
+///     println!("synchronized output: {}", synchronized_output);
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "events")]
+pub fn query_private_mode(mode: u16) -> io::Result<ModeReport> {
+    #[cfg(unix)]
+    {
+        Ok(
+            sys::query_private_mode(mode)?
+                .map_or(ModeReport::NotRecognized, ModeReport::from_value),
+        )
+    }
+
+    #[cfg(windows)]
+    {
+        let _ = mode;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Querying private modes is not implemented for the legacy Windows API.",
+        ))
+    }
+}
+
+/// A command that switches to alternate screen.
+///
+/// # Notes
+///
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * Use [LeaveAlternateScreen](./struct.LeaveAlternateScreen.html) command to leave the entered alternate screen.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{self, Write};
+/// use crossterm::{execute, terminal::{EnterAlternateScreen, LeaveAlternateScreen}};
+///
+/// fn main() -> io::Result<()> {
+///     execute!(io::stdout(), EnterAlternateScreen)?;
+///
+///     // Do anything on the alternate screen
+///
+///     execute!(io::stdout(), LeaveAlternateScreen)
+/// }
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnterAlternateScreen;
+
+impl Command for EnterAlternateScreen {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("?1049h"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        let alternate_screen = ScreenBuffer::create()?;
+        alternate_screen.show()?;
+        Ok(())
+    }
+}
+
+/// A command that switches back to the main screen.
+///
+/// # Notes
+///
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * Use [EnterAlternateScreen](./struct.EnterAlternateScreen.html) to enter the alternate screen.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{self, Write};
+/// use crossterm::{execute, terminal::{EnterAlternateScreen, LeaveAlternateScreen}};
+///
+/// fn main() -> io::Result<()> {
+///     execute!(io::stdout(), EnterAlternateScreen)?;
+///
+///     // Do anything on the alternate screen
+///
+///     execute!(io::stdout(), LeaveAlternateScreen)
+/// }
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeaveAlternateScreen;
+
+impl Command for LeaveAlternateScreen {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("?1049l"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        let screen_buffer = ScreenBuffer::from(Handle::current_out_handle()?);
+        screen_buffer.show()?;
+        Ok(())
+    }
+}
+
+/// The terminal features to keep enabled when enabling raw mode with
+/// [enable_raw_mode_with](terminal::enable_raw_mode_with).
+///
+/// Every feature is disabled by default, which is the regular [raw mode](./index.html#raw-mode).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RawModeConfig {
+    /// Keep echoing the input to the screen.
+    ///
+    /// This is `ECHO` on UNIX and `ENABLE_ECHO_INPUT` on Windows. Windows only echoes the input
+    /// when `canonical` is enabled as well.
+    pub echo: bool,
+    /// Keep buffering the input until enter is pressed.
+    ///
+    /// This is `ICANON` on UNIX and `ENABLE_LINE_INPUT` on Windows.
+    pub canonical: bool,
+    /// Keep raising signals for special keys like `CTRL+C`.
+    ///
+    /// This is `ISIG` on UNIX and `ENABLE_PROCESSED_INPUT` on Windows.
+    pub signals: bool,
+    /// Keep processing the output, like translating `\n` into `\r\n`.
+    ///
+    /// This is `OPOST` on UNIX. Raw mode doesn't change the output processing on Windows,
+    /// therefore this has no effect there.
+    pub output_processing: bool,
+}
+
+/// Different ways to clear the terminal buffer.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Hash)]
+pub enum ClearType {
+    /// All cells.
+    All,
+    /// All plus history
+    Purge,
+    /// All cells from the cursor position downwards.
+    FromCursorDown,
+    /// All cells from the cursor position upwards.
+    FromCursorUp,
+    /// All cells at the cursor row, the cursor stays where it is.
+    CurrentLine,
+    /// All cells from the cursor position until the new line.
+    UntilNewLine,
+}
+
+/// A command that scrolls the terminal screen a given number of rows up.
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollUp(pub u16);
+
+impl Command for ScrollUp {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if self.0 != 0 {
+            write!(f, csi!("{}S"), self.0)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        sys::scroll_up(self.0)
+    }
+}
+
+/// A command that scrolls the terminal screen a given number of rows down.
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollDown(pub u16);
+
+impl Command for ScrollDown {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if self.0 != 0 {
+            write!(f, csi!("{}T"), self.0)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        sys::scroll_down(self.0)
+    }
+}
+
+/// A command that inserts the given number of blank lines at the cursor position (IL).
+///
+/// The line the cursor is on and all lines below it are moved down, lines moved past the bottom
+/// of the screen (or scrolling region) are lost. This makes it possible to insert lines without
+/// redrawing everything below them.
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InsertLines(pub u16);
+
+impl Command for InsertLines {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if self.0 != 0 {
+            write!(f, csi!("{}L"), self.0)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        sys::insert_lines(self.0)
+    }
+}
+
+/// A command that deletes the given number of lines, starting at the line the cursor is on (DL).
+///
+/// The lines below the deleted ones are moved up and blank lines are inserted at the bottom of
+/// the screen (or scrolling region).
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeleteLines(pub u16);
+
+impl Command for DeleteLines {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if self.0 != 0 {
+            write!(f, csi!("{}M"), self.0)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        sys::delete_lines(self.0)
+    }
+}
+
+/// A command that inserts the given number of blank characters at the cursor position (ICH).
+///
+/// The characters from the cursor position until the end of the line are moved right,
+/// characters moved past the end of the line are lost.
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InsertChars(pub u16);
+
+impl Command for InsertChars {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if self.0 != 0 {
+            write!(f, csi!("{}@"), self.0)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        sys::insert_chars(self.0)
+    }
+}
+
+/// A command that deletes the given number of characters, starting at the cursor position (DCH).
+///
+/// The characters after the deleted ones are moved left and blanks are inserted at the end of
+/// the line.
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeleteChars(pub u16);
+
+impl Command for DeleteChars {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if self.0 != 0 {
+            write!(f, csi!("{}P"), self.0)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        sys::delete_chars(self.0)
+    }
+}
+
+/// A command that erases the given number of characters, starting at the cursor position (ECH).
+///
+/// The erased cells become blanks and the cursor doesn't move, which is cheaper than printing
+/// spaces and moving the cursor back. Characters after the erased ones keep their position,
+/// unlike with [`DeleteChars`](struct.DeleteChars.html). Erasing stops at the end of the line.
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EraseChars(pub u16);
+
+impl Command for EraseChars {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if self.0 != 0 {
+            write!(f, csi!("{}X"), self.0)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        sys::erase_chars(self.0)
+    }
+}
+
+/// A command that clears the terminal screen buffer.
+///
+/// See the [`ClearType`](enum.ClearType.html) enum.
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Clear(pub ClearType);
+
+impl Command for Clear {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(match self.0 {
+            ClearType::All => csi!("2J"),
+            ClearType::Purge => csi!("3J"),
+            ClearType::FromCursorDown => csi!("J"),
+            ClearType::FromCursorUp => csi!("1J"),
+            ClearType::CurrentLine => csi!("2K"),
+            ClearType::UntilNewLine => csi!("K"),
+        })
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        sys::clear(self.0)
+    }
+}
+
+/// A command that clears the screen, moves the cursor to the top left cell and resets the
+/// scroll region, like the `clear` command of shells does.
+///
+/// Unlike [`Clear(ClearType::All)`](struct.Clear.html), which only erases the cells, this
+/// leaves the terminal ready to draw from the top left again.
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClearAll;
+
+impl Command for ClearAll {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(concat!(csi!("2J"), csi!("H"), csi!("r")))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        // Clearing the whole buffer moves the cursor to the top left cell as well, and the
+        // console has no scroll region to reset.
+        sys::clear(ClearType::All)
+    }
+}
+
+/// A command that resets the modes and attributes of the terminal (DECSTR, `ESC [ ! p`).
+///
+/// Unlike [`HardReset`](struct.HardReset.html), this leaves the screen, the scrollback and the
+/// cursor position alone. It shows the cursor, resets the colors and attributes, the scroll
+/// region, origin mode, insert mode and the character sets, which makes it the recommended way
+/// to restore a sane terminal state when exiting. [`Clear(ClearType::All)`](struct.Clear.html)
+/// on the other hand only clears the screen and resets nothing else.
+///
+/// # Notes
+///
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * Modes which aren't part of the VT standard, like mouse capture, bracketed paste or the
+///   alternate screen, aren't affected by most terminals. Disable them with their `Disable*`
+///   commands.
+/// * With WinAPI, the output mode of the console, the colors and the cursor visibility are
+///   reset, the content of the screen buffer is kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SoftReset;
+
+impl Command for SoftReset {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("!p"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        sys::soft_reset()
+    }
+}
+
+/// A command that resets the terminal to its initial state (RIS, `ESC c`).
+///
+/// This is the heaviest reset there is: the terminal reinitializes itself as if it was just
+/// started. The screen and the scrollback are cleared, the cursor is moved to the top left cell,
+/// and all modes, colors, attributes, tab stops, scroll regions, character sets and custom
+/// palette colors are reset to their defaults. Mouse capture, bracketed paste, keyboard
+/// enhancements and the alternate screen are disabled as well.
+///
+/// It is meant as a last resort to recover a terminal left in a badly corrupted state. Don't
+/// use it to merely clear the screen or undo a few modes: it throws away the scrollback of the
+/// user and any settings other programs made, use [`SoftReset`](struct.SoftReset.html),
+/// [`ClearAll`](struct.ClearAll.html) and the `Disable*` commands instead.
+///
+/// # Notes
+///
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * Raw mode isn't a terminal setting but a setting of the tty, so it isn't affected.
+/// * With WinAPI, the output mode of the console, the colors, the cursor visibility and shape
+///   are reset and the whole screen buffer is cleared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HardReset;
+
+impl Command for HardReset {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1Bc")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        sys::hard_reset()
+    }
 }
 
-/// Disables line wrapping.
+/// A command that sets the terminal buffer size `(columns, rows)`.
+///
+/// This asks the terminal emulator to resize its window with XTWINOPS
+/// (`ESC [ 8 ; rows ; columns t`), which then resizes the pseudo terminal. Unlike [set_size_of],
+/// which changes the size of a pseudo terminal with an ioctl, this works over SSH too, where the
+/// program can't resize the pseudo terminal of the client.
+///
+/// Support varies: xterm and many other emulators honor the request, some ignore it or only allow
+/// it after being configured to, and tiling window managers may refuse to change the window
+/// size. Real ttys, like the Linux console, don't support it at all. Check the size with [size]
+/// or wait for the [resize event](crate::event::Event::Resize) to find out whether the window was
+/// resized.
+///
+/// # Notes
+///
+/// - On Windows, the console screen buffer and window are resized with the WinAPI.
+/// - Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct DisableLineWrap;
+pub struct SetSize(pub u16, pub u16);
 
-impl Command for DisableLineWrap {
+impl Command for SetSize {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        f.write_str(csi!("?7l"))
+        write!(f, csi!("8;{};{}t"), self.1, self.0)
     }
 
     #[cfg(windows)]
     fn execute_winapi(&self) -> io::Result<()> {
-        let screen_buffer = ScreenBuffer::current()?;
-        let console_mode = ConsoleMode::from(screen_buffer.handle().clone());
-        let new_mode = console_mode.mode()? & !ENABLE_WRAP_AT_EOL_OUTPUT;
-        console_mode.set_mode(new_mode)?;
-        Ok(())
+        sys::set_size(self.0, self.1)
     }
 }
 
-/// Enable line wrapping.
+/// A command that sets the terminal title
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct EnableLineWrap;
+pub struct SetTitle<T>(pub T);
 
-impl Command for EnableLineWrap {
+impl<T: fmt::Display> Command for SetTitle<T> {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        f.write_str(csi!("?7h"))
+        write!(f, "\x1B]0;{}\x07", &self.0)
     }
 
     #[cfg(windows)]
     fn execute_winapi(&self) -> io::Result<()> {
-        let screen_buffer = ScreenBuffer::current()?;
-        let console_mode = ConsoleMode::from(screen_buffer.handle().clone());
-        let new_mode = console_mode.mode()? | ENABLE_WRAP_AT_EOL_OUTPUT;
-        console_mode.set_mode(new_mode)?;
-        Ok(())
+        sys::set_window_title(&self.0)
     }
 }
 
-/// A command that switches to alternate screen.
-///
-/// # Notes
+/// A command that saves the current terminal title on the title stack of the terminal.
 ///
-/// * Commands must be executed/queued for execution otherwise they do nothing.
-/// * Use [LeaveAlternateScreen](./struct.LeaveAlternateScreen.html) command to leave the entered alternate screen.
+/// Use [`PopTitle`](struct.PopTitle.html) to restore it, e.g. after changing the title with
+/// [`SetTitle`](struct.SetTitle.html), without having to query the original title.
 ///
 /// # Examples
 ///
 /// ```no_run
-/// use std::io::{self, Write};
-/// use crossterm::{execute, terminal::{EnterAlternateScreen, LeaveAlternateScreen}};
+/// use std::io;
+/// use crossterm::{execute, terminal::{PopTitle, PushTitle, SetTitle}};
 ///
 /// fn main() -> io::Result<()> {
-///     execute!(io::stdout(), EnterAlternateScreen)?;
-///
-///     // Do anything on the alternate screen
-///
-///     execute!(io::stdout(), LeaveAlternateScreen)
+///     execute!(io::stdout(), PushTitle, SetTitle("my app"))?;
+///     // ...
+///     execute!(io::stdout(), PopTitle)
 /// }
 /// ```
 ///
+/// # Notes
+///
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * Terminals which don't support the title stack ignore it.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct EnterAlternateScreen;
+pub struct PushTitle;
 
-impl Command for EnterAlternateScreen {
+impl Command for PushTitle {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        f.write_str(csi!("?1049h"))
+        f.write_str(csi!("22;0t"))
     }
 
     #[cfg(windows)]
     fn execute_winapi(&self) -> io::Result<()> {
-        let alternate_screen = ScreenBuffer::create()?;
-        alternate_screen.show()?;
-        Ok(())
+        sys::push_title()
     }
 }
 
-/// A command that switches back to the main screen.
+/// A command that restores the terminal title which was saved last with
+/// [`PushTitle`](struct.PushTitle.html).
 ///
 /// # Notes
 ///
 /// * Commands must be executed/queued for execution otherwise they do nothing.
-/// * Use [EnterAlternateScreen](./struct.EnterAlternateScreen.html) to enter the alternate screen.
+/// * Terminals which don't support the title stack ignore it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PopTitle;
+
+impl Command for PopTitle {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("23;0t"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        sys::pop_title()
+    }
+}
+
+/// A command that tells the terminal the current working directory (OSC 7).
+///
+/// Terminals use this to open new tabs or windows in the same directory, or to show it in the
+/// title. The path is sent as a `file://` URL containing the host name of this machine, bytes
+/// which aren't allowed in URLs, including the bytes of non-UTF-8 paths, are percent-encoded.
+///
+/// The path should be absolute, relative paths are sent as they are.
 ///
 /// # Examples
 ///
 /// ```no_run
-/// use std::io::{self, Write};
-/// use crossterm::{execute, terminal::{EnterAlternateScreen, LeaveAlternateScreen}};
-///
-/// fn main() -> io::Result<()> {
-///     execute!(io::stdout(), EnterAlternateScreen)?;
+/// use std::{env, io};
 ///
-///     // Do anything on the alternate screen
+/// use crossterm::{execute, terminal::SetCurrentDirectory};
 ///
-///     execute!(io::stdout(), LeaveAlternateScreen)
+/// fn main() -> io::Result<()> {
+///     let directory = env::current_dir()?;
+///     execute!(io::stdout(), SetCurrentDirectory::new(&directory))
 /// }
 /// ```
 ///
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct LeaveAlternateScreen;
+/// # Notes
+///
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * Terminals which don't support it ignore it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetCurrentDirectory<'a> {
+    path: &'a Path,
+    host: String,
+}
 
-impl Command for LeaveAlternateScreen {
+impl<'a> SetCurrentDirectory<'a> {
+    /// Creates a command for the given path, the host name of this machine is looked up once
+    /// here.
+    pub fn new(path: &'a Path) -> Self {
+        Self::with_host(path, sys::hostname().unwrap_or_default())
+    }
+
+    /// Creates a command for the given path on the given host, for example when running on a
+    /// remote machine under a different name.
+    pub fn with_host(path: &'a Path, host: impl Into<String>) -> Self {
+        Self {
+            path,
+            host: host.into(),
+        }
+    }
+}
+
+impl Command for SetCurrentDirectory<'_> {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        f.write_str(csi!("?1049l"))
+        f.write_str("\x1B]7;")?;
+        write_file_url(f, &self.host, self.path)?;
+        f.write_str("\x1B\\")
     }
 
     #[cfg(windows)]
     fn execute_winapi(&self) -> io::Result<()> {
-        let screen_buffer = ScreenBuffer::from(Handle::current_out_handle()?);
-        screen_buffer.show()?;
         Ok(())
     }
 }
 
-/// Different ways to clear the terminal buffer.
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Hash)]
-pub enum ClearType {
-    /// All cells.
-    All,
-    /// All plus history
-    Purge,
-    /// All cells from the cursor position downwards.
-    FromCursorDown,
-    /// All cells from the cursor position upwards.
-    FromCursorUp,
-    /// All cells at the cursor row.
-    CurrentLine,
-    /// All cells from the cursor position until the new line.
-    UntilNewLine,
+/// Writes `path` on `host` as a percent-encoded `file://` URL.
+fn write_file_url(f: &mut impl fmt::Write, host: &str, path: &Path) -> fmt::Result {
+    #[cfg(unix)]
+    let path = {
+        use std::os::unix::ffi::OsStrExt;
+        std::borrow::Cow::Borrowed(path.as_os_str().as_bytes())
+    };
+    // Windows paths become `/C:/Users/...`.
+    #[cfg(windows)]
+    let path = {
+        let path = path.to_string_lossy().replace('\\', "/");
+        std::borrow::Cow::<[u8]>::Owned(if path.starts_with('/') {
+            path.into_bytes()
+        } else {
+            format!("/{}", path).into_bytes()
+        })
+    };
+
+    write!(f, "file://{}", host)?;
+    for &byte in path.iter() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                f.write_char(byte as char)?
+            }
+            _ => write!(f, "%{:02X}", byte)?,
+        }
+    }
+    Ok(())
 }
 
-/// A command that scrolls the terminal screen a given number of rows up.
+/// A command that sets a tab stop at the current cursor column.
+///
+/// Tab characters and [`MoveToNextTab`](../cursor/struct.MoveToNextTab.html) move the cursor to
+/// the next tab stop. Terminals set a tab stop every 8 columns by default.
 ///
 /// # Notes
 ///
-/// Commands must be executed/queued for execution otherwise they do nothing.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * Most terminals support tab stops, but some emulators ignore custom ones. The Windows console
+///   has no tab stops, so this does nothing with WinAPI.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct ScrollUp(pub u16);
+pub struct SetTabStop;
 
-impl Command for ScrollUp {
+impl Command for SetTabStop {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        if self.0 != 0 {
-            write!(f, csi!("{}S"), self.0)?;
-        }
+        f.write_str("\x1BH")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
         Ok(())
     }
+}
+
+/// A command that clears the tab stop at the current cursor column.
+///
+/// # Notes
+///
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * The Windows console has no tab stops, so this does nothing with WinAPI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClearTabStop;
+
+impl Command for ClearTabStop {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("0g"))
+    }
 
     #[cfg(windows)]
     fn execute_winapi(&self) -> io::Result<()> {
-        sys::scroll_up(self.0)
+        Ok(())
     }
 }
 
-/// A command that scrolls the terminal screen a given number of rows down.
+/// A command that clears all tab stops.
 ///
 /// # Notes
 ///
-/// Commands must be executed/queued for execution otherwise they do nothing.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * The Windows console has no tab stops, so this does nothing with WinAPI.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct ScrollDown(pub u16);
+pub struct ClearAllTabStops;
 
-impl Command for ScrollDown {
+impl Command for ClearAllTabStops {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        if self.0 != 0 {
-            write!(f, csi!("{}T"), self.0)?;
-        }
+        f.write_str(csi!("3g"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
         Ok(())
     }
+}
+
+/// A command that rings the terminal bell.
+///
+/// # Notes
+///
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * Terminals may be configured to translate the bell into a visual bell, a notification
+///   or to ignore it completely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bell;
+
+impl Command for Bell {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x07")
+    }
 
     #[cfg(windows)]
     fn execute_winapi(&self) -> io::Result<()> {
-        sys::scroll_down(self.0)
+        sys::bell()
     }
 }
 
-/// A command that clears the terminal screen buffer.
+/// A command that flashes the terminal screen instead of ringing the audible bell.
 ///
-/// See the [`ClearType`](enum.ClearType.html) enum.
+/// Use [Bell](./struct.Bell.html) to ring the audible bell instead.
 ///
 /// # Notes
 ///
-/// Commands must be executed/queued for execution otherwise they do nothing.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * Not all terminals support the visual bell sequence, those that don't will ignore it.
+/// * On Windows without ANSI support the colors of the visible window are briefly inverted,
+///   which blocks for about 100 milliseconds.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Clear(pub ClearType);
+pub struct VisualBell;
 
-impl Command for Clear {
+impl Command for VisualBell {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        f.write_str(match self.0 {
-            ClearType::All => csi!("2J"),
-            ClearType::Purge => csi!("3J"),
-            ClearType::FromCursorDown => csi!("J"),
-            ClearType::FromCursorUp => csi!("1J"),
-            ClearType::CurrentLine => csi!("2K"),
-            ClearType::UntilNewLine => csi!("K"),
-        })
+        f.write_str("\x1Bg")
     }
 
     #[cfg(windows)]
     fn execute_winapi(&self) -> io::Result<()> {
-        sys::clear(self.0)
+        sys::visual_bell()
     }
 }
 
-/// A command that sets the terminal buffer size `(columns, rows)`.
+/// A command that inverts the colors of the whole screen (DECSCNM).
+///
+/// Unlike [`Attribute::Reverse`](crate::style::Attribute::Reverse), which swaps the foreground
+/// and background color of the text printed afterwards, this affects every cell on the screen,
+/// including those printed before, until [`DisableReverseScreen`] is executed. Enabling it
+/// briefly can be used as a flash effect, leaving it enabled as a high-contrast toggle.
 ///
 /// # Notes
 ///
-/// Commands must be executed/queued for execution otherwise they do nothing.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+/// * On Windows without ANSI support the colors of the cells in the visible window and of the
+///   text printed afterwards are swapped. Cells outside of the visible window aren't affected.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct SetSize(pub u16, pub u16);
+pub struct EnableReverseScreen;
 
-impl Command for SetSize {
+impl Command for EnableReverseScreen {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        write!(f, csi!("8;{};{}t"), self.1, self.0)
+        f.write_str(csi!("?5h"))
     }
 
     #[cfg(windows)]
     fn execute_winapi(&self) -> io::Result<()> {
-        sys::set_size(self.0, self.1)
+        sys::set_reverse_screen(true)
     }
 }
 
-/// A command that sets the terminal title
+/// A command that reverts the colors of the screen inverted by [`EnableReverseScreen`].
 ///
 /// # Notes
 ///
 /// Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct SetTitle<T>(pub T);
+pub struct DisableReverseScreen;
 
-impl<T: fmt::Display> Command for SetTitle<T> {
+impl Command for DisableReverseScreen {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        write!(f, "\x1B]0;{}\x07", &self.0)
+        f.write_str(csi!("?5l"))
     }
 
     #[cfg(windows)]
     fn execute_winapi(&self) -> io::Result<()> {
-        sys::set_window_title(&self.0)
+        sys::set_reverse_screen(false)
     }
 }
 
@@ -488,7 +2077,32 @@ impl Command for EndSynchronizedUpdate {
 impl_display!(for ScrollUp);
 impl_display!(for ScrollDown);
 impl_display!(for SetSize);
+impl_display!(for InsertLines);
+impl_display!(for DeleteLines);
+impl_display!(for InsertChars);
+impl_display!(for DeleteChars);
+impl_display!(for EraseChars);
 impl_display!(for Clear);
+impl_display!(for ClearAll);
+impl_display!(for SoftReset);
+impl_display!(for HardReset);
+impl_display!(for SetTabStop);
+impl_display!(for ClearTabStop);
+impl_display!(for ClearAllTabStops);
+impl_display!(for PushTitle);
+impl_display!(for PopTitle);
+impl_display!(for SetCurrentDirectory<'_>);
+impl_display!(for SetPrivateMode);
+impl_display!(for ResetPrivateMode);
+impl_display!(for EnableOriginMode);
+impl_display!(for DisableOriginMode);
+impl_display!(for EnableLeftRightMargins);
+impl_display!(for DisableLeftRightMargins);
+impl_display!(for SetLeftRightMargins);
+impl_display!(for Bell);
+impl_display!(for VisualBell);
+impl_display!(for EnableReverseScreen);
+impl_display!(for DisableReverseScreen);
 
 #[cfg(test)]
 mod tests {
@@ -498,6 +2112,56 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_size_fits_in() {
+        let size = Size::new(80, 24);
+        assert!(size.fits_in(size));
+        assert!(size.fits_in(Size::new(120, 40)));
+        assert!(!size.fits_in(Size::new(79, 40)));
+        assert!(!size.fits_in(Size::new(120, 23)));
+        assert!(Size::default().fits_in(size));
+    }
+
+    #[test]
+    fn test_size_tuple_conversion() {
+        let size = Size::from((80, 24));
+        assert_eq!(size.columns, 80);
+        assert_eq!(size.rows, 24);
+        assert_eq!(<(u16, u16)>::from(size), (80, 24));
+    }
+
+    #[test]
+    #[cfg(feature = "events")]
+    fn test_tui_options_sequences() {
+        let sequences = |options: TuiOptions| {
+            let (mut enter, mut leave) = (Vec::new(), Vec::new());
+            options.queue_enter(&mut enter).unwrap();
+            options.queue_leave(&mut leave).unwrap();
+            (
+                String::from_utf8(enter).unwrap(),
+                String::from_utf8(leave).unwrap(),
+            )
+        };
+
+        assert_eq!(
+            sequences(TuiOptions::default()),
+            (
+                "\x1B[?1049h\x1B[?25l".to_string(),
+                "\x1B[?25h\x1B[?1049l".to_string()
+            )
+        );
+
+        let (enter, leave) = sequences(TuiOptions {
+            mouse: true,
+            focus: true,
+            ..TuiOptions::default()
+        });
+        assert!(enter.starts_with("\x1B[?1049h\x1B[?25l\x1B[?1000h"));
+        assert!(enter.ends_with("\x1B[?1004h"));
+        assert!(leave.starts_with("\x1B[?1004l\x1B[?1006l"));
+        assert!(leave.ends_with("\x1B[?25h\x1B[?1049l"));
+    }
+
     // Test is disabled, because it's failing on Travis CI
     #[test]
     #[ignore]
@@ -520,6 +2184,178 @@ mod tests {
         assert_eq!((width, height), size().unwrap());
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_size_of_non_terminal_fails() {
+        use std::os::unix::io::AsRawFd;
+
+        let file = std::fs::File::open("Cargo.toml").unwrap();
+        assert!(size_of(file.as_raw_fd()).is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_set_size_of_rejects_zero() {
+        use std::os::unix::io::AsRawFd;
+
+        let file = std::fs::File::open("Cargo.toml").unwrap();
+        let err = set_size_of(file.as_raw_fd(), 0, 10).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        let err = set_size_of(file.as_raw_fd(), 10, 0).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(set_size_of(file.as_raw_fd(), 10, 10).is_err());
+    }
+
+    #[test]
+    fn test_origin_mode_ansi() {
+        assert_eq!(EnableOriginMode.to_string(), "\x1B[?6h");
+        assert_eq!(DisableOriginMode.to_string(), "\x1B[?6l");
+    }
+
+    #[test]
+    fn test_set_current_directory_ansi() {
+        assert_eq!(
+            SetCurrentDirectory::with_host(Path::new("/tmp"), "host").to_string(),
+            "\x1B]7;file://host/tmp\x1B\\"
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_file_url() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let url = |path: &Path| {
+            let mut url = String::new();
+            write_file_url(&mut url, "host", path).unwrap();
+            url
+        };
+
+        assert_eq!(url(Path::new("/home/user")), "file://host/home/user");
+        assert_eq!(
+            url(Path::new("/tmp/a b/100%")),
+            "file://host/tmp/a%20b/100%25"
+        );
+        assert_eq!(url(Path::new("/tmp/é")), "file://host/tmp/%C3%A9");
+        assert_eq!(
+            url(Path::new(OsStr::from_bytes(b"/tmp/caf\xE9"))),
+            "file://host/tmp/caf%E9"
+        );
+    }
+
+    #[test]
+    fn test_tab_stops_ansi() {
+        assert_eq!(SetTabStop.to_string(), "\x1BH");
+        assert_eq!(ClearTabStop.to_string(), "\x1B[0g");
+        assert_eq!(ClearAllTabStops.to_string(), "\x1B[3g");
+    }
+
+    #[test]
+    fn test_insert_and_delete_ansi() {
+        assert_eq!(InsertLines(3).to_string(), "\x1B[3L");
+        assert_eq!(DeleteLines(3).to_string(), "\x1B[3M");
+        assert_eq!(InsertChars(2).to_string(), "\x1B[2@");
+        assert_eq!(DeleteChars(2).to_string(), "\x1B[2P");
+        assert_eq!(InsertLines(0).to_string(), "");
+        assert_eq!(DeleteChars(0).to_string(), "");
+    }
+
+    #[test]
+    fn test_cell_size_of() {
+        let size = WindowSize {
+            rows: 24,
+            columns: 80,
+            width: 800,
+            height: 480,
+        };
+        assert_eq!(cell_size_of(size).unwrap(), (10, 20));
+
+        let without_pixels = WindowSize {
+            width: 0,
+            height: 0,
+            ..size
+        };
+        assert_eq!(
+            cell_size_of(without_pixels).unwrap_err().kind(),
+            io::ErrorKind::Unsupported
+        );
+    }
+
+    #[test]
+    fn test_private_mode_ansi() {
+        assert_eq!(SetPrivateMode(2026).to_string(), "\x1B[?2026h");
+        assert_eq!(ResetPrivateMode(2026).to_string(), "\x1B[?2026l");
+        assert_eq!(SetPrivateMode(7).to_string(), "\x1B[?7h");
+        assert_eq!(ResetPrivateMode(7).to_string(), "\x1B[?7l");
+    }
+
+    #[test]
+    fn test_title_stack_ansi() {
+        assert_eq!(PushTitle.to_string(), "\x1B[22;0t");
+        assert_eq!(PopTitle.to_string(), "\x1B[23;0t");
+    }
+
+    #[test]
+    fn test_erase_chars_ansi() {
+        assert_eq!(EraseChars(4).to_string(), "\x1B[4X");
+        assert_eq!(EraseChars(0).to_string(), "");
+    }
+
+    #[test]
+    fn test_clear_all_ansi() {
+        assert_eq!(ClearAll.to_string(), "\x1B[2J\x1B[H\x1B[r");
+    }
+
+    #[test]
+    fn test_bell_ansi() {
+        assert_eq!(Bell.to_string(), "\x07");
+    }
+
+    #[test]
+    fn test_visual_bell_ansi() {
+        assert_eq!(VisualBell.to_string(), "\x1Bg");
+    }
+
+    #[cfg(feature = "events")]
+    #[test]
+    fn test_mode_report() {
+        assert_eq!(ModeReport::from_value(0), ModeReport::NotRecognized);
+        assert_eq!(ModeReport::from_value(1), ModeReport::Set);
+        assert_eq!(ModeReport::from_value(2), ModeReport::Reset);
+        assert_eq!(ModeReport::from_value(3), ModeReport::PermanentlySet);
+        assert_eq!(ModeReport::from_value(4), ModeReport::PermanentlyReset);
+        assert_eq!(ModeReport::from_value(5), ModeReport::NotRecognized);
+
+        assert!(ModeReport::Set.is_set());
+        assert!(ModeReport::PermanentlySet.is_set());
+        assert!(!ModeReport::Reset.is_set());
+
+        assert!(ModeReport::Reset.is_supported());
+        assert!(!ModeReport::PermanentlyReset.is_supported());
+        assert!(!ModeReport::NotRecognized.is_supported());
+    }
+
+    #[test]
+    fn test_left_right_margins_ansi() {
+        assert_eq!(EnableLeftRightMargins.to_string(), "\x1B[?69h");
+        assert_eq!(DisableLeftRightMargins.to_string(), "\x1B[?69l");
+        assert_eq!(
+            SetLeftRightMargins { left: 0, right: 39 }.to_string(),
+            "\x1B[1;40s"
+        );
+    }
+
+    #[test]
+    fn test_hard_reset_ansi() {
+        assert_eq!(HardReset.to_string(), "\x1Bc");
+    }
+
+    #[test]
+    fn test_reverse_screen_ansi() {
+        assert_eq!(EnableReverseScreen.to_string(), "\x1B[?5h");
+        assert_eq!(DisableReverseScreen.to_string(), "\x1B[?5l");
+    }
+
     #[test]
     fn test_raw_mode() {
         // check we start from normal mode (may fail on some test harnesses)
@@ -1,18 +1,38 @@
 //! This module provides platform related functions.
 
+#[cfg(unix)]
+pub(crate) use self::unix::{
+    disable_cbreak_mode, disable_raw_mode, enable_cbreak_mode, enable_raw_mode,
+    enable_raw_mode_with, hostname, is_raw_mode_enabled, restore_termios, save_termios,
+    set_size_of, size, size_of, window_size,
+};
 #[cfg(unix)]
 #[cfg(feature = "events")]
-pub use self::unix::supports_keyboard_enhancement;
+pub(crate) use self::unix::{
+    primary_device_attributes, query_osc, query_private_mode, query_settings, raise_stop_signal,
+    size_via_escape, size_without_query, write_query,
+};
+#[cfg(unix)]
+#[cfg(feature = "event-stream")]
+pub(crate) use self::unix::{
+    private_mode_query, KEYBOARD_ENHANCEMENT_QUERY, PRIMARY_DEVICE_ATTRIBUTES_QUERY,
+    TERMINAL_VERSION_QUERY,
+};
 #[cfg(unix)]
-pub(crate) use self::unix::{disable_raw_mode, enable_raw_mode, is_raw_mode_enabled, size};
-#[cfg(windows)]
 #[cfg(feature = "events")]
-pub use self::windows::supports_keyboard_enhancement;
+pub use self::unix::{supports_keyboard_enhancement, terminal_version};
 #[cfg(windows)]
 pub(crate) use self::windows::{
-    clear, disable_raw_mode, enable_raw_mode, is_raw_mode_enabled, scroll_down, scroll_up,
-    set_size, set_window_title, size,
+    bell, clear, code_page, delete_chars, delete_lines, disable_cbreak_mode, disable_raw_mode,
+    enable_cbreak_mode, enable_raw_mode, enable_raw_mode_with, erase_chars, hard_reset, hostname,
+    input_code_page, insert_chars, insert_lines, is_raw_mode_enabled, output_handle,
+    output_screen_buffer, pop_title, push_title, scroll_down, scroll_up, set_code_page,
+    set_input_code_page, set_output_handle, set_reverse_screen, set_scrollback_lines, set_size,
+    set_size_of, set_window_title, size, size_of, soft_reset, visual_bell,
 };
+#[cfg(windows)]
+#[cfg(feature = "events")]
+pub use self::windows::{supports_keyboard_enhancement, terminal_version};
 
 #[cfg(windows)]
 mod windows;
@@ -1,9 +1,12 @@
 //! UNIX related logic for terminal manipulation.
 
-use crate::terminal::sys::file_descriptor::{tty_fd, FileDesc};
+use crate::terminal::{
+    sys::file_descriptor::{tty_fd, FileDesc},
+    RawModeConfig, WindowSize,
+};
 use libc::{
-    cfmakeraw, ioctl, tcgetattr, tcsetattr, termios as Termios, winsize, STDOUT_FILENO, TCSANOW,
-    TIOCGWINSZ,
+    cfmakeraw, ioctl, tcgetattr, tcsetattr, termios as Termios, winsize, ECHO, ICANON, ISIG, OPOST,
+    STDOUT_FILENO, TCSANOW, TIOCGWINSZ, TIOCSWINSZ, VMIN, VTIME,
 };
 use parking_lot::Mutex;
 use std::fs::File;
@@ -16,12 +19,99 @@ use std::{io, mem, process};
 // None -> we're not in the raw mode
 static TERMINAL_MODE_PRIOR_RAW_MODE: Mutex<Option<Termios>> = parking_lot::const_mutex(None);
 
+// Some(Termios) -> we're in the cbreak mode and this is the previous mode
+// None -> we're not in the cbreak mode
+static TERMINAL_MODE_PRIOR_CBREAK_MODE: Mutex<Option<Termios>> = parking_lot::const_mutex(None);
+
 pub(crate) fn is_raw_mode_enabled() -> bool {
     TERMINAL_MODE_PRIOR_RAW_MODE.lock().is_some()
 }
 
 #[allow(clippy::useless_conversion)]
 pub(crate) fn size() -> io::Result<(u16, u16)> {
+    match tty_size() {
+        Ok(size) => return Ok(size),
+        // The terminal reported a size of zero, ask it for its size with escape sequences instead.
+        #[cfg(feature = "events")]
+        Err(error) if error.kind() == io::ErrorKind::InvalidData => {
+            if let Ok(size) = size_via_escape() {
+                return Ok(size);
+            }
+        }
+        Err(_) => {}
+    }
+
+    tput_size().ok_or_else(|| std::io::Error::last_os_error().into())
+}
+
+/// Stops the process with `SIGTSTP`, returns once it's continued.
+#[cfg(feature = "events")]
+pub(crate) fn raise_stop_signal() -> io::Result<()> {
+    wrap_with_result(unsafe { libc::raise(libc::SIGTSTP) })
+}
+
+/// Returns the terminal size like [`size`], but never queries the terminal.
+///
+/// Used by the event sources, which hold the event reader while they determine the size. The
+/// reply to a query couldn't be read then.
+#[cfg(feature = "events")]
+pub(crate) fn size_without_query() -> io::Result<(u16, u16)> {
+    if let Ok(size) = tty_size() {
+        return Ok(size);
+    }
+
+    tput_size().ok_or_else(io::Error::last_os_error)
+}
+
+fn tty_size() -> io::Result<(u16, u16)> {
+    cells(tty_winsize()?)
+}
+
+/// Determines the terminal size by moving the cursor to the bottom right corner and querying its
+/// position.
+#[cfg(feature = "events")]
+pub(crate) fn size_via_escape() -> io::Result<(u16, u16)> {
+    // ESC 7            Save the cursor position.
+    // ESC [ 999;999 H  Move the cursor, the terminal stops it at the bottom right corner.
+    write_query(b"\x1B7\x1B[999;999H")?;
+    let position = crate::cursor::position();
+    // ESC 8            Restore the cursor position.
+    write_query(b"\x1B8")?;
+
+    let (column, row) = position?;
+    Ok((column + 1, row + 1))
+}
+
+pub(crate) fn window_size() -> io::Result<WindowSize> {
+    let size = tty_winsize()?;
+
+    Ok(WindowSize {
+        rows: size.ws_row,
+        columns: size.ws_col,
+        width: size.ws_xpixel,
+        height: size.ws_ypixel,
+    })
+}
+
+pub(crate) fn size_of(fd: RawFd) -> io::Result<(u16, u16)> {
+    cells(winsize_of(fd)?)
+}
+
+/// Returns the window size of the controlling terminal, or of stdout if `/dev/tty` is missing.
+fn tty_winsize() -> io::Result<winsize> {
+    let file = File::open("/dev/tty").map(|file| FileDesc::new(file.into_raw_fd(), true));
+    let fd = if let Ok(file) = &file {
+        file.raw_fd()
+    } else {
+        // Fallback to libc::STDOUT_FILENO if /dev/tty is missing
+        STDOUT_FILENO
+    };
+
+    winsize_of(fd)
+}
+
+#[allow(clippy::useless_conversion)]
+fn winsize_of(fd: RawFd) -> io::Result<winsize> {
     // http://rosettacode.org/wiki/Terminal_control/Dimensions#Library:_BSD_libc
     let mut size = winsize {
         ws_row: 0,
@@ -30,25 +120,54 @@ pub(crate) fn size() -> io::Result<(u16, u16)> {
         ws_ypixel: 0,
     };
 
-    let file = File::open("/dev/tty").map(|file| (FileDesc::new(file.into_raw_fd(), true)));
-    let fd = if let Ok(file) = &file {
-        file.raw_fd()
-    } else {
-        // Fallback to libc::STDOUT_FILENO if /dev/tty is missing
-        STDOUT_FILENO
-    };
+    wrap_with_result(unsafe { ioctl(fd, TIOCGWINSZ.into(), &mut size) })?;
+    Ok(size)
+}
 
-    if wrap_with_result(unsafe { ioctl(fd, TIOCGWINSZ.into(), &mut size) }).is_ok()
-        && size.ws_col != 0
-        && size.ws_row != 0
-    {
-        return Ok((size.ws_col, size.ws_row));
+/// Returns the number of columns and rows of a window size, a size of zero is an error.
+fn cells(size: winsize) -> io::Result<(u16, u16)> {
+    if size.ws_col == 0 || size.ws_row == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "the terminal reported a size of zero",
+        ));
     }
 
-    tput_size().ok_or_else(|| std::io::Error::last_os_error().into())
+    Ok((size.ws_col, size.ws_row))
+}
+
+#[allow(clippy::useless_conversion)]
+pub(crate) fn set_size_of(fd: RawFd, width: u16, height: u16) -> io::Result<()> {
+    if width == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "terminal width must be at least 1",
+        ));
+    }
+
+    if height == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "terminal height must be at least 1",
+        ));
+    }
+
+    let size = winsize {
+        ws_row: height,
+        ws_col: width,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+
+    wrap_with_result(unsafe { ioctl(fd, TIOCSWINSZ.into(), &size) })?;
+    Ok(())
 }
 
 pub(crate) fn enable_raw_mode() -> io::Result<()> {
+    enable_raw_mode_with(RawModeConfig::default())
+}
+
+pub(crate) fn enable_raw_mode_with(config: RawModeConfig) -> io::Result<()> {
     let mut original_mode = TERMINAL_MODE_PRIOR_RAW_MODE.lock();
 
     if original_mode.is_some() {
@@ -60,7 +179,7 @@ pub(crate) fn enable_raw_mode() -> io::Result<()> {
     let mut ios = get_terminal_attr(fd)?;
     let original_mode_ios = ios;
 
-    raw_terminal_attr(&mut ios);
+    raw_terminal_attr(&mut ios, config);
     set_terminal_attr(fd, &ios)?;
 
     // Keep it last - set the original mode only if we were able to switch to the raw mode
@@ -87,6 +206,78 @@ pub(crate) fn disable_raw_mode() -> io::Result<()> {
     Ok(())
 }
 
+/// Returns the current termios of the terminal.
+pub(crate) fn save_termios() -> io::Result<Termios> {
+    let tty = tty_fd()?;
+    get_terminal_attr(tty.raw_fd())
+}
+
+/// Applies the whole termios to the terminal.
+pub(crate) fn restore_termios(termios: &Termios) -> io::Result<()> {
+    let tty = tty_fd()?;
+    set_terminal_attr(tty.raw_fd(), termios)
+}
+
+pub(crate) fn enable_cbreak_mode() -> io::Result<()> {
+    let mut original_mode = TERMINAL_MODE_PRIOR_CBREAK_MODE.lock();
+
+    if original_mode.is_some() {
+        return Ok(());
+    }
+
+    let tty = tty_fd()?;
+    let fd = tty.raw_fd();
+    let mut ios = get_terminal_attr(fd)?;
+    let original_mode_ios = ios;
+
+    cbreak_terminal_attr(&mut ios);
+    set_terminal_attr(fd, &ios)?;
+
+    // Keep it last - set the original mode only if we were able to switch to the cbreak mode
+    *original_mode = Some(original_mode_ios);
+
+    Ok(())
+}
+
+/// Reset the cbreak mode.
+///
+/// Like [disable_raw_mode], this resets the whole termios mode to what it was before the first
+/// call to [enable_cbreak_mode].
+pub(crate) fn disable_cbreak_mode() -> io::Result<()> {
+    let mut original_mode = TERMINAL_MODE_PRIOR_CBREAK_MODE.lock();
+
+    if let Some(original_mode_ios) = original_mode.as_ref() {
+        let tty = tty_fd()?;
+        set_terminal_attr(tty.raw_fd(), original_mode_ios)?;
+        // Keep it last - remove the original mode only if we were able to switch back
+        *original_mode = None;
+    }
+
+    Ok(())
+}
+
+// ESC [ ? u        Query progressive keyboard enhancement flags (kitty protocol).
+// ESC [ c          Query primary device attributes.
+#[cfg(feature = "events")]
+pub(crate) const KEYBOARD_ENHANCEMENT_QUERY: &[u8] = b"\x1B[?u\x1B[c";
+
+// ESC [ > 0 q      Query the terminal name and version (XTVERSION).
+// ESC [ c          Query primary device attributes.
+#[cfg(feature = "events")]
+pub(crate) const TERMINAL_VERSION_QUERY: &[u8] = b"\x1B[>0q\x1B[c";
+
+// ESC [ c          Query primary device attributes.
+#[cfg(feature = "events")]
+pub(crate) const PRIMARY_DEVICE_ATTRIBUTES_QUERY: &[u8] = b"\x1B[c";
+
+/// Returns the query for the state of a private mode.
+#[cfg(feature = "events")]
+pub(crate) fn private_mode_query(mode: u16) -> String {
+    // ESC [ ? mode $ p     Request the state of the private mode (DECRQM).
+    // ESC [ c              Query primary device attributes.
+    format!("\x1B[?{}$p\x1B[c", mode)
+}
+
 /// Queries the terminal's support for progressive keyboard enhancement.
 ///
 /// On unix systems, this function will block and possibly time out while
@@ -114,7 +305,6 @@ fn read_supports_keyboard_enhancement_raw() -> io::Result<bool> {
         filter::{KeyboardEnhancementFlagsFilter, PrimaryDeviceAttributesFilter},
         poll_internal, read_internal, InternalEvent,
     };
-    use std::io::Write;
     use std::time::Duration;
 
     // This is the recommended method for testing support for the keyboard enhancement protocol.
@@ -124,19 +314,7 @@ fn read_supports_keyboard_enhancement_raw() -> io::Result<bool> {
     //
     // See <https://sw.kovidgoyal.net/kitty/keyboard-protocol/#detection-of-support-for-this-protocol>
 
-    // ESC [ ? u        Query progressive keyboard enhancement flags (kitty protocol).
-    // ESC [ c          Query primary device attributes.
-    const QUERY: &[u8] = b"\x1B[?u\x1B[c";
-
-    let result = File::open("/dev/tty").and_then(|mut file| {
-        file.write_all(QUERY)?;
-        file.flush()
-    });
-    if result.is_err() {
-        let mut stdout = io::stdout();
-        stdout.write_all(QUERY)?;
-        stdout.flush()?;
-    }
+    write_query(KEYBOARD_ENHANCEMENT_QUERY)?;
 
     loop {
         match poll_internal(
@@ -164,6 +342,256 @@ fn read_supports_keyboard_enhancement_raw() -> io::Result<bool> {
     }
 }
 
+/// Queries the name and version of the terminal with XTVERSION.
+///
+/// Returns the name and version as reported by the terminal, e.g. `XTerm(380)` or
+/// `kitty(0.26.5)`. The format isn't standardized, it's usually the name followed by the version
+/// in parentheses or separated by a space.
+///
+/// Returns `Ok(None)` if the terminal doesn't support the query. This is detected by sending a
+/// primary device attributes query after it, which all terminals answer. If the terminal answers
+/// neither query within two seconds, `Ok(None)` is returned as well.
+///
+/// On unix systems, this function will block and possibly time out while
+/// [`crossterm::event::read`](crate::event::read) or [`crossterm::event::poll`](crate::event::poll) are being called.
+#[cfg(feature = "events")]
+pub fn terminal_version() -> io::Result<Option<String>> {
+    if is_raw_mode_enabled() {
+        read_terminal_version_raw()
+    } else {
+        enable_raw_mode()?;
+        let version = read_terminal_version_raw();
+        disable_raw_mode()?;
+        version
+    }
+}
+
+#[cfg(feature = "events")]
+fn read_terminal_version_raw() -> io::Result<Option<String>> {
+    use crate::event::{
+        filter::{PrimaryDeviceAttributesFilter, TerminalVersionFilter},
+        poll_internal, read_internal, InternalEvent,
+    };
+    use std::time::Duration;
+
+    write_query(TERMINAL_VERSION_QUERY)?;
+
+    loop {
+        match poll_internal(Some(Duration::from_millis(2000)), &TerminalVersionFilter) {
+            Ok(true) => match read_internal(&TerminalVersionFilter) {
+                Ok(InternalEvent::TerminalVersion(version)) => {
+                    // Flush the PrimaryDeviceAttributes out of the event queue.
+                    read_internal(&PrimaryDeviceAttributesFilter).ok();
+                    return Ok(Some(version));
+                }
+                _ => return Ok(None),
+            },
+            Ok(false) => return Ok(None),
+            Err(_) => {}
+        }
+    }
+}
+
+/// Queries the primary device attributes (DA1) of the terminal.
+///
+/// Returns the architectural class of the terminal followed by its attributes, e.g. `[62, 4]`
+/// for a VT220 class terminal with Sixel graphics. Returns `Ok(None)` if the terminal doesn't
+/// answer within two seconds.
+#[cfg(feature = "events")]
+pub(crate) fn primary_device_attributes() -> io::Result<Option<Vec<u16>>> {
+    if is_raw_mode_enabled() {
+        read_primary_device_attributes_raw()
+    } else {
+        enable_raw_mode()?;
+        let attributes = read_primary_device_attributes_raw();
+        disable_raw_mode()?;
+        attributes
+    }
+}
+
+#[cfg(feature = "events")]
+fn read_primary_device_attributes_raw() -> io::Result<Option<Vec<u16>>> {
+    use crate::event::{
+        filter::PrimaryDeviceAttributesFilter, poll_internal, read_internal, InternalEvent,
+    };
+    use std::time::Duration;
+
+    write_query(PRIMARY_DEVICE_ATTRIBUTES_QUERY)?;
+
+    loop {
+        match poll_internal(
+            Some(Duration::from_millis(2000)),
+            &PrimaryDeviceAttributesFilter,
+        ) {
+            Ok(true) => match read_internal(&PrimaryDeviceAttributesFilter) {
+                Ok(InternalEvent::PrimaryDeviceAttributes(attributes)) => {
+                    return Ok(Some(attributes))
+                }
+                _ => return Ok(None),
+            },
+            Ok(false) => return Ok(None),
+            Err(_) => {}
+        }
+    }
+}
+
+/// Queries the state of a private mode with DECRQM.
+///
+/// Returns the value of the DECRPM reply: 0 if the mode isn't recognized, 1 if it's set, 2 if
+/// it's reset, 3 if it's permanently set and 4 if it's permanently reset. Returns `Ok(None)` if
+/// the terminal doesn't support DECRQM or doesn't answer within two seconds.
+#[cfg(feature = "events")]
+pub(crate) fn query_private_mode(mode: u16) -> io::Result<Option<u8>> {
+    if is_raw_mode_enabled() {
+        read_private_mode_raw(mode)
+    } else {
+        enable_raw_mode()?;
+        let value = read_private_mode_raw(mode);
+        disable_raw_mode()?;
+        value
+    }
+}
+
+#[cfg(feature = "events")]
+fn read_private_mode_raw(mode: u16) -> io::Result<Option<u8>> {
+    use crate::event::{
+        filter::{PrimaryDeviceAttributesFilter, PrivateModeReportFilter},
+        poll_internal, read_internal, InternalEvent,
+    };
+    use std::time::Duration;
+
+    write_query(private_mode_query(mode).as_bytes())?;
+
+    loop {
+        match poll_internal(Some(Duration::from_millis(2000)), &PrivateModeReportFilter) {
+            Ok(true) => match read_internal(&PrivateModeReportFilter) {
+                Ok(InternalEvent::PrivateModeReport(reported_mode, value))
+                    if reported_mode == mode =>
+                {
+                    // Flush the PrimaryDeviceAttributes out of the event queue.
+                    read_internal(&PrimaryDeviceAttributesFilter).ok();
+                    return Ok(Some(value));
+                }
+                // A report of another mode, e.g. an answer to an earlier query
+                Ok(InternalEvent::PrivateModeReport(_, _)) => {}
+                _ => return Ok(None),
+            },
+            Ok(false) => return Ok(None),
+            Err(_) => {}
+        }
+    }
+}
+
+/// Writes `query`, which contains one or more DECRQSS requests, and collects the DECRPSS
+/// replies.
+///
+/// The replies are returned in the order they arrived, `None` for a request the terminal
+/// considered invalid. The query is followed by a primary device attributes request, the replies
+/// are collected until its answer arrives or no input is received for two seconds.
+#[cfg(feature = "events")]
+pub(crate) fn query_settings(query: &[u8]) -> io::Result<Vec<Option<String>>> {
+    if is_raw_mode_enabled() {
+        read_settings_raw(query)
+    } else {
+        enable_raw_mode()?;
+        let settings = read_settings_raw(query);
+        disable_raw_mode()?;
+        settings
+    }
+}
+
+#[cfg(feature = "events")]
+fn read_settings_raw(query: &[u8]) -> io::Result<Vec<Option<String>>> {
+    use crate::event::{filter::SettingReportFilter, poll_internal, read_internal, InternalEvent};
+    use std::time::Duration;
+
+    // ESC [ c          Query primary device attributes.
+    let mut query = query.to_vec();
+    query.extend_from_slice(b"\x1B[c");
+    write_query(&query)?;
+
+    let mut settings = Vec::new();
+    loop {
+        match poll_internal(Some(Duration::from_millis(2000)), &SettingReportFilter) {
+            Ok(true) => match read_internal(&SettingReportFilter) {
+                Ok(InternalEvent::SettingReport(setting)) => settings.push(setting),
+                _ => return Ok(settings),
+            },
+            Ok(false) => return Ok(settings),
+            Err(_) => {}
+        }
+    }
+}
+
+/// Writes `query`, which contains one or more operating system commands, and collects the
+/// replies.
+///
+/// The replies are returned in the order they arrived, without the introducer and terminator.
+/// The query is followed by a primary device attributes request, the replies are collected until
+/// its answer arrives or no input is received for two seconds.
+#[cfg(feature = "events")]
+pub(crate) fn query_osc(query: &[u8]) -> io::Result<Vec<String>> {
+    if is_raw_mode_enabled() {
+        read_osc_raw(query)
+    } else {
+        enable_raw_mode()?;
+        let replies = read_osc_raw(query);
+        disable_raw_mode()?;
+        replies
+    }
+}
+
+#[cfg(feature = "events")]
+fn read_osc_raw(query: &[u8]) -> io::Result<Vec<String>> {
+    use crate::event::{filter::OscReplyFilter, poll_internal, read_internal, InternalEvent};
+    use std::time::Duration;
+
+    // ESC [ c          Query primary device attributes.
+    let mut query = query.to_vec();
+    query.extend_from_slice(PRIMARY_DEVICE_ATTRIBUTES_QUERY);
+    write_query(&query)?;
+
+    let mut replies = Vec::new();
+    loop {
+        match poll_internal(Some(Duration::from_millis(2000)), &OscReplyFilter) {
+            Ok(true) => match read_internal(&OscReplyFilter) {
+                Ok(InternalEvent::OscReply(reply)) => replies.push(reply),
+                _ => return Ok(replies),
+            },
+            Ok(false) => return Ok(replies),
+            Err(_) => {}
+        }
+    }
+}
+
+/// Writes a query to the terminal, preferring `/dev/tty` over stdout.
+#[cfg(feature = "events")]
+pub(crate) fn write_query(query: &[u8]) -> io::Result<()> {
+    use std::io::Write;
+
+    let result = File::open("/dev/tty").and_then(|mut file| {
+        file.write_all(query)?;
+        file.flush()
+    });
+    if result.is_err() {
+        let mut stdout = io::stdout();
+        stdout.write_all(query)?;
+        stdout.flush()?;
+    }
+    Ok(())
+}
+
+/// Returns the host name of this machine.
+pub(crate) fn hostname() -> Option<String> {
+    let mut buffer = [0u8; 256];
+    if unsafe { libc::gethostname(buffer.as_mut_ptr() as *mut libc::c_char, buffer.len()) } != 0 {
+        return None;
+    }
+
+    let len = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
+    String::from_utf8(buffer[..len].to_vec()).ok()
+}
+
 /// execute tput with the given argument and parse
 /// the output as a u16.
 ///
@@ -195,8 +623,30 @@ fn tput_size() -> Option<(u16, u16)> {
 }
 
 // Transform the given mode into an raw mode (non-canonical) mode.
-fn raw_terminal_attr(termios: &mut Termios) {
+fn raw_terminal_attr(termios: &mut Termios, config: RawModeConfig) {
     unsafe { cfmakeraw(termios) }
+
+    // Re-enable the features the config asks to keep.
+    if config.echo {
+        termios.c_lflag |= ECHO;
+    }
+    if config.canonical {
+        termios.c_lflag |= ICANON;
+    }
+    if config.signals {
+        termios.c_lflag |= ISIG;
+    }
+    if config.output_processing {
+        termios.c_oflag |= OPOST;
+    }
+}
+
+fn cbreak_terminal_attr(termios: &mut Termios) {
+    // Disable line buffering and echo, but keep generating signals for special keys.
+    termios.c_lflag &= !(ICANON | ECHO);
+    termios.c_lflag |= ISIG;
+    termios.c_cc[VMIN] = 1;
+    termios.c_cc[VTIME] = 0;
 }
 
 fn get_terminal_attr(fd: RawFd) -> io::Result<Termios> {
@@ -218,3 +668,66 @@ fn wrap_with_result(result: i32) -> io::Result<()> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::mem;
+
+    use libc::{termios as Termios, ECHO, ICANON, ISIG, OPOST, VMIN, VTIME};
+
+    use super::{cbreak_terminal_attr, raw_terminal_attr};
+    use crate::terminal::RawModeConfig;
+
+    #[cfg(feature = "events")]
+    #[test]
+    fn test_private_mode_query() {
+        assert_eq!(super::private_mode_query(2026), "\x1B[?2026$p\x1B[c");
+    }
+
+    #[test]
+    fn test_cbreak_terminal_attr() {
+        let mut termios: Termios = unsafe { mem::zeroed() };
+        termios.c_lflag = ICANON | ECHO;
+        termios.c_cc[VTIME] = 5;
+
+        cbreak_terminal_attr(&mut termios);
+
+        assert_eq!(termios.c_lflag & ICANON, 0);
+        assert_eq!(termios.c_lflag & ECHO, 0);
+        assert_eq!(termios.c_lflag & ISIG, ISIG);
+        assert_eq!(termios.c_cc[VMIN], 1);
+        assert_eq!(termios.c_cc[VTIME], 0);
+    }
+
+    #[test]
+    fn test_raw_terminal_attr() {
+        let mut termios: Termios = unsafe { mem::zeroed() };
+        termios.c_lflag = ICANON | ECHO | ISIG;
+        termios.c_oflag = OPOST;
+
+        raw_terminal_attr(&mut termios, RawModeConfig::default());
+
+        assert_eq!(termios.c_lflag & (ICANON | ECHO | ISIG), 0);
+        assert_eq!(termios.c_oflag & OPOST, 0);
+    }
+
+    #[test]
+    fn test_raw_terminal_attr_with_config() {
+        let mut termios: Termios = unsafe { mem::zeroed() };
+        termios.c_lflag = ICANON | ECHO | ISIG;
+        termios.c_oflag = OPOST;
+
+        raw_terminal_attr(
+            &mut termios,
+            RawModeConfig {
+                signals: true,
+                output_processing: true,
+                ..RawModeConfig::default()
+            },
+        );
+
+        assert_eq!(termios.c_lflag & (ICANON | ECHO), 0);
+        assert_eq!(termios.c_lflag & ISIG, ISIG);
+        assert_eq!(termios.c_oflag & OPOST, OPOST);
+    }
+}
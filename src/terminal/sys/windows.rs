@@ -1,19 +1,64 @@
 //! WinAPI related logic for terminal manipulation.
 
+use std::cell::RefCell;
 use std::fmt::{self, Write};
 use std::io::{self};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
 
-use crossterm_winapi::{Console, ConsoleMode, Coord, Handle, ScreenBuffer, Size};
+use crossterm_winapi::{Console, ConsoleMode, Coord, Handle, ScreenBuffer, Size, WindowPositions};
+use parking_lot::Mutex;
 use winapi::{
-    shared::minwindef::DWORD,
-    um::wincon::{SetConsoleTitleW, ENABLE_ECHO_INPUT, ENABLE_LINE_INPUT, ENABLE_PROCESSED_INPUT},
+    shared::minwindef::{BOOL, DWORD},
+    um::{
+        consoleapi::{GetConsoleCP, GetConsoleOutputCP},
+        wincon::{
+            GetConsoleTitleW, ReadConsoleOutputAttribute, ScrollConsoleScreenBufferW, SetConsoleCP,
+            SetConsoleOutputCP, SetConsoleScreenBufferSize, SetConsoleTitleW,
+            WriteConsoleOutputAttribute, CHAR_INFO, COORD, ENABLE_ECHO_INPUT, ENABLE_LINE_INPUT,
+            ENABLE_PROCESSED_INPUT, ENABLE_PROCESSED_OUTPUT, ENABLE_VIRTUAL_TERMINAL_PROCESSING,
+            ENABLE_WRAP_AT_EOL_OUTPUT, SMALL_RECT,
+        },
+        winuser::{MessageBeep, MB_OK},
+    },
 };
 
-use crate::{cursor, terminal::ClearType};
+use crate::{
+    cursor,
+    terminal::{ClearType, RawModeConfig},
+};
+
+thread_local! {
+    /// The handle the WinAPI fallback of commands writes to instead of the current output handle.
+    static OUTPUT_HANDLE: RefCell<Option<Handle>> = RefCell::new(None);
+}
+
+pub(crate) fn set_output_handle(handle: Option<Handle>) -> Option<Handle> {
+    OUTPUT_HANDLE.with(|output_handle| output_handle.replace(handle))
+}
+
+/// Returns the handle the WinAPI fallback of commands should write to.
+///
+/// This is the handle set with `set_output_handle` on this thread, or the current output handle.
+pub(crate) fn output_handle() -> io::Result<Handle> {
+    match OUTPUT_HANDLE.with(|output_handle| output_handle.borrow().clone()) {
+        Some(handle) => Ok(handle),
+        None => Handle::current_out_handle(),
+    }
+}
+
+/// Returns the screen buffer behind [`output_handle`].
+pub(crate) fn output_screen_buffer() -> io::Result<ScreenBuffer> {
+    Ok(ScreenBuffer::from(output_handle()?))
+}
 
 /// bits which can't be set in raw mode
 const NOT_RAW_MODE_MASK: DWORD = ENABLE_LINE_INPUT | ENABLE_ECHO_INPUT | ENABLE_PROCESSED_INPUT;
 
+/// bits which can't be set in cbreak mode
+const NOT_CBREAK_MODE_MASK: DWORD = ENABLE_LINE_INPUT | ENABLE_ECHO_INPUT;
+
 pub(crate) fn is_raw_mode_enabled() -> std::io::Result<bool> {
     let console_mode = ConsoleMode::from(Handle::current_in_handle()?);
 
@@ -26,11 +71,26 @@ pub(crate) fn is_raw_mode_enabled() -> std::io::Result<bool> {
 }
 
 pub(crate) fn enable_raw_mode() -> std::io::Result<()> {
+    enable_raw_mode_with(RawModeConfig::default())
+}
+
+pub(crate) fn enable_raw_mode_with(config: RawModeConfig) -> std::io::Result<()> {
     let console_mode = ConsoleMode::from(Handle::current_in_handle()?);
 
     let dw_mode = console_mode.mode()?;
 
-    let new_mode = dw_mode & !NOT_RAW_MODE_MASK;
+    let mut new_mode = dw_mode & !NOT_RAW_MODE_MASK;
+
+    // Re-enable the features the config asks to keep.
+    if config.echo {
+        new_mode |= ENABLE_ECHO_INPUT;
+    }
+    if config.canonical {
+        new_mode |= ENABLE_LINE_INPUT;
+    }
+    if config.signals {
+        new_mode |= ENABLE_PROCESSED_INPUT;
+    }
 
     console_mode.set_mode(new_mode)?;
 
@@ -49,8 +109,36 @@ pub(crate) fn disable_raw_mode() -> std::io::Result<()> {
     Ok(())
 }
 
+pub(crate) fn enable_cbreak_mode() -> std::io::Result<()> {
+    let console_mode = ConsoleMode::from(Handle::current_in_handle()?);
+
+    let dw_mode = console_mode.mode()?;
+
+    let new_mode = (dw_mode & !NOT_CBREAK_MODE_MASK) | ENABLE_PROCESSED_INPUT;
+
+    console_mode.set_mode(new_mode)?;
+
+    Ok(())
+}
+
+pub(crate) fn disable_cbreak_mode() -> std::io::Result<()> {
+    let console_mode = ConsoleMode::from(Handle::current_in_handle()?);
+
+    let dw_mode = console_mode.mode()?;
+
+    let new_mode = dw_mode | NOT_CBREAK_MODE_MASK;
+
+    console_mode.set_mode(new_mode)?;
+
+    Ok(())
+}
+
 pub(crate) fn size() -> io::Result<(u16, u16)> {
-    let terminal_size = ScreenBuffer::current()?.info()?.terminal_size();
+    size_of(&Handle::current_out_handle()?)
+}
+
+pub(crate) fn size_of(handle: &Handle) -> io::Result<(u16, u16)> {
+    let terminal_size = ScreenBuffer::from(handle.clone()).info()?.terminal_size();
     // windows starts counting at 0, unix at 1, add one to replicated unix behaviour.
     Ok((
         (terminal_size.width + 1) as u16,
@@ -66,8 +154,16 @@ pub fn supports_keyboard_enhancement() -> std::io::Result<bool> {
     Ok(false)
 }
 
+/// Queries the name and version of the terminal with XTVERSION.
+///
+/// This always returns `Ok(None)` on Windows.
+#[cfg(feature = "events")]
+pub fn terminal_version() -> std::io::Result<Option<String>> {
+    Ok(None)
+}
+
 pub(crate) fn clear(clear_type: ClearType) -> std::io::Result<()> {
-    let screen_buffer = ScreenBuffer::current()?;
+    let screen_buffer = output_screen_buffer()?;
     let csbi = screen_buffer.info()?;
 
     let pos = csbi.cursor_pos();
@@ -90,7 +186,7 @@ pub(crate) fn clear(clear_type: ClearType) -> std::io::Result<()> {
 }
 
 pub(crate) fn scroll_up(row_count: u16) -> std::io::Result<()> {
-    let csbi = ScreenBuffer::current()?;
+    let csbi = output_screen_buffer()?;
     let mut window = csbi.info()?.terminal_window();
 
     // check whether the window is too close to the screen buffer top
@@ -99,13 +195,13 @@ pub(crate) fn scroll_up(row_count: u16) -> std::io::Result<()> {
         window.top -= count; // move top down
         window.bottom -= count; // move bottom down
 
-        Console::output()?.set_console_info(true, window)?;
+        Console::from(output_handle()?).set_console_info(true, window)?;
     }
     Ok(())
 }
 
 pub(crate) fn scroll_down(row_count: u16) -> std::io::Result<()> {
-    let screen_buffer = ScreenBuffer::current()?;
+    let screen_buffer = output_screen_buffer()?;
     let csbi = screen_buffer.info()?;
     let mut window = csbi.terminal_window();
     let buffer_size = csbi.buffer_size();
@@ -116,12 +212,127 @@ pub(crate) fn scroll_down(row_count: u16) -> std::io::Result<()> {
         window.top += count; // move top down
         window.bottom += count; // move bottom down
 
-        Console::output()?.set_console_info(true, window)?;
+        Console::from(output_handle()?).set_console_info(true, window)?;
     }
     Ok(())
 }
 
+/// Inserts `count` blank lines at the cursor, moving the lines below it down.
+pub(crate) fn insert_lines(count: u16) -> std::io::Result<()> {
+    shift_cells(count, |cursor, window, buffer_width| {
+        let region = SMALL_RECT {
+            Left: 0,
+            Top: cursor.y,
+            Right: buffer_width - 1,
+            Bottom: window.bottom,
+        };
+        let destination = COORD {
+            X: 0,
+            Y: cursor.y.saturating_add(shift_count(count)),
+        };
+        (region, region, destination)
+    })
+}
+
+/// Deletes `count` lines starting at the cursor, moving the lines below them up.
+pub(crate) fn delete_lines(count: u16) -> std::io::Result<()> {
+    shift_cells(count, |cursor, window, buffer_width| {
+        let region = SMALL_RECT {
+            Left: 0,
+            Top: cursor.y,
+            Right: buffer_width - 1,
+            Bottom: window.bottom,
+        };
+        let source = SMALL_RECT {
+            Top: cursor.y.saturating_add(shift_count(count)),
+            ..region
+        };
+        let destination = COORD { X: 0, Y: cursor.y };
+        (source, region, destination)
+    })
+}
+
+/// Inserts `count` blank characters at the cursor, moving the rest of the line right.
+pub(crate) fn insert_chars(count: u16) -> std::io::Result<()> {
+    shift_cells(count, |cursor, _, buffer_width| {
+        let region = SMALL_RECT {
+            Left: cursor.x,
+            Top: cursor.y,
+            Right: buffer_width - 1,
+            Bottom: cursor.y,
+        };
+        let destination = COORD {
+            X: cursor.x.saturating_add(shift_count(count)),
+            Y: cursor.y,
+        };
+        (region, region, destination)
+    })
+}
+
+/// Deletes `count` characters starting at the cursor, moving the rest of the line left.
+pub(crate) fn delete_chars(count: u16) -> std::io::Result<()> {
+    shift_cells(count, |cursor, _, buffer_width| {
+        let region = SMALL_RECT {
+            Left: cursor.x,
+            Top: cursor.y,
+            Right: buffer_width - 1,
+            Bottom: cursor.y,
+        };
+        let source = SMALL_RECT {
+            Left: cursor.x.saturating_add(shift_count(count)),
+            ..region
+        };
+        let destination = COORD {
+            X: cursor.x,
+            Y: cursor.y,
+        };
+        (source, region, destination)
+    })
+}
+
+/// Converts the number of lines or characters to shift by to a coordinate offset. Counts beyond
+/// `i16::MAX` are clamped, they'd wrap around to a negative offset otherwise.
+fn shift_count(count: u16) -> i16 {
+    i16::try_from(count).unwrap_or(i16::MAX)
+}
+
+/// Moves a part of the screen buffer with `ScrollConsoleScreenBufferW`.
+///
+/// `regions` returns the cells to move, the cells which may be changed and the new position of
+/// the moved cells. Cells which are uncovered by the move are filled with blanks in the current
+/// colors.
+fn shift_cells(
+    count: u16,
+    regions: impl FnOnce(Coord, WindowPositions, i16) -> (SMALL_RECT, SMALL_RECT, COORD),
+) -> std::io::Result<()> {
+    if count == 0 {
+        return Ok(());
+    }
+
+    let screen_buffer = output_screen_buffer()?;
+    let csbi = screen_buffer.info()?;
+    let (source, clip, destination) = regions(
+        csbi.cursor_pos(),
+        csbi.terminal_window(),
+        csbi.buffer_size().width,
+    );
+
+    let mut fill: CHAR_INFO = unsafe { std::mem::zeroed() };
+    unsafe {
+        *fill.Char.UnicodeChar_mut() = ' ' as u16;
+    }
+    fill.Attributes = csbi.attributes();
+
+    api_result("ScrollConsoleScreenBufferW", unsafe {
+        ScrollConsoleScreenBufferW(**screen_buffer.handle(), &source, &clip, destination, &fill)
+    })
+}
+
 pub(crate) fn set_size(width: u16, height: u16) -> std::io::Result<()> {
+    set_size_of(&Handle::current_out_handle()?, width, height)
+}
+
+pub(crate) fn set_size_of(handle: &Handle, width: u16, height: u16) -> std::io::Result<()> {
     if width <= 1 {
         return Err(io::Error::new(
             io::ErrorKind::InvalidInput,
@@ -136,8 +347,8 @@ pub(crate) fn set_size(width: u16, height: u16) -> std::io::Result<()> {
         ));
     }
 
-    // get the position of the current console window
-    let screen_buffer = ScreenBuffer::current()?;
+    // get the position of the console window
+    let screen_buffer = ScreenBuffer::from(handle.clone());
     let console = Console::from(screen_buffer.handle().clone());
     let csbi = screen_buffer.info()?;
 
@@ -209,6 +420,204 @@ pub(crate) fn set_size(width: u16, height: u16) -> std::io::Result<()> {
     Ok(())
 }
 
+pub(crate) fn set_scrollback_lines(lines: u16) -> std::io::Result<()> {
+    let screen_buffer = output_screen_buffer()?;
+    let console = Console::from(screen_buffer.handle().clone());
+    let csbi = screen_buffer.info()?;
+
+    let buffer_size = csbi.buffer_size();
+    let mut window = csbi.terminal_window();
+    let window_height = window.bottom - window.top + 1;
+
+    if lines > i16::max_value() as u16 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "scrollback too large",
+        ));
+    }
+    let lines = lines as i16;
+    if lines < window_height {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "scrollback of {lines} lines is smaller than the window height {window_height}"
+            ),
+        ));
+    }
+
+    // The window has to fit into the buffer, move it up before the buffer shrinks.
+    if window.bottom >= lines {
+        window.top = lines - window_height;
+        window.bottom = lines - 1;
+        console.set_console_info(true, window)?;
+    }
+
+    api_result("SetConsoleScreenBufferSize", unsafe {
+        SetConsoleScreenBufferSize(
+            **screen_buffer.handle(),
+            COORD {
+                X: buffer_size.width,
+                Y: lines,
+            },
+        )
+    })
+}
+
+/// Returns the host name of this machine.
+pub(crate) fn hostname() -> Option<String> {
+    std::env::var("COMPUTERNAME").ok()
+}
+
+pub(crate) fn bell() -> std::io::Result<()> {
+    api_result("MessageBeep", unsafe { MessageBeep(MB_OK) })
+}
+
+/// Briefly inverts the colors of all cells in the visible window.
+pub(crate) fn visual_bell() -> std::io::Result<()> {
+    let screen_buffer = output_screen_buffer()?;
+    let original_rows = invert_window(&screen_buffer)?;
+
+    thread::sleep(Duration::from_millis(100));
+
+    restore_rows(screen_buffer.handle(), &original_rows)
+}
+
+/// Resets the output mode, the colors and the cursor visibility, keeping the screen buffer.
+pub(crate) fn soft_reset() -> std::io::Result<()> {
+    use crate::{cursor::Show, style::ResetColor, Command};
+
+    let screen_buffer = output_screen_buffer()?;
+    let console_mode = ConsoleMode::from(screen_buffer.handle().clone());
+    let vt_processing = console_mode.mode()? & ENABLE_VIRTUAL_TERMINAL_PROCESSING;
+    console_mode.set_mode(ENABLE_PROCESSED_OUTPUT | ENABLE_WRAP_AT_EOL_OUTPUT | vt_processing)?;
+
+    // The content is kept, so the inverted colors have to be reverted.
+    set_reverse_screen(false)?;
+    ResetColor.execute_winapi()?;
+    Show.execute_winapi()
+}
+
+/// Resets the output mode, the colors and the cursor, and clears the whole screen buffer.
+pub(crate) fn hard_reset() -> std::io::Result<()> {
+    use crate::{cursor::SetCursorStyle, Command};
+
+    soft_reset()?;
+    SetCursorStyle::DefaultUserShape.execute_winapi()?;
+
+    clear(ClearType::Purge)
+}
+
+/// Whether the colors of the screen are currently inverted by `set_reverse_screen`.
+static REVERSE_SCREEN: AtomicBool = AtomicBool::new(false);
+
+/// Inverts the colors of the visible window and of the text written afterwards, or reverts
+/// this. Nothing happens if the screen is already in the requested state.
+pub(crate) fn set_reverse_screen(enabled: bool) -> std::io::Result<()> {
+    if REVERSE_SCREEN.load(Ordering::Relaxed) == enabled {
+        return Ok(());
+    }
+
+    let screen_buffer = output_screen_buffer()?;
+    let original_rows = invert_window(&screen_buffer)?;
+
+    let result = screen_buffer.info().and_then(|info| {
+        Console::from(screen_buffer.handle().clone())
+            .set_text_attribute(swap_colors(info.attributes()))
+    });
+    if let Err(e) = result {
+        let _ = restore_rows(screen_buffer.handle(), &original_rows);
+        return Err(e);
+    }
+
+    // Only updated once the colors are swapped, so a failed attempt can be repeated.
+    REVERSE_SCREEN.store(enabled, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Swaps the foreground and the background color of a cell attribute.
+fn swap_colors(attribute: u16) -> u16 {
+    (attribute & !0xFF) | ((attribute & 0x0F) << 4) | ((attribute & 0xF0) >> 4)
+}
+
+/// Swaps the foreground and the background color of every cell in the visible window.
+///
+/// Returns the original attributes of every row, with the location the row starts at. If a row
+/// can't be inverted, the rows inverted so far are restored before the error is returned.
+fn invert_window(screen_buffer: &ScreenBuffer) -> io::Result<Vec<(COORD, Vec<u16>)>> {
+    let handle = screen_buffer.handle();
+    let window = screen_buffer.info()?.terminal_window();
+    let width = (window.right - window.left + 1) as u32;
+
+    let mut original_rows = Vec::with_capacity((window.bottom - window.top + 1) as usize);
+
+    for y in window.top..=window.bottom {
+        let start_location = COORD {
+            X: window.left,
+            Y: y,
+        };
+        if let Err(e) = invert_row(handle, start_location, width, &mut original_rows) {
+            let _ = restore_rows(handle, &original_rows);
+            return Err(e);
+        }
+    }
+
+    Ok(original_rows)
+}
+
+/// Inverts the colors of `width` cells from `start_location`, and adds their original attributes
+/// to `original_rows` before they're overwritten.
+fn invert_row(
+    handle: &Handle,
+    start_location: COORD,
+    width: u32,
+    original_rows: &mut Vec<(COORD, Vec<u16>)>,
+) -> io::Result<()> {
+    let mut attributes = vec![0u16; width as usize];
+    let mut cells_read = 0;
+    api_result("ReadConsoleOutputAttribute", unsafe {
+        ReadConsoleOutputAttribute(
+            **handle,
+            attributes.as_mut_ptr(),
+            width,
+            start_location,
+            &mut cells_read,
+        )
+    })?;
+
+    let inverted = attributes
+        .iter()
+        .map(|&attribute| swap_colors(attribute))
+        .collect::<Vec<u16>>();
+    original_rows.push((start_location, attributes));
+    write_attributes(handle, start_location, &inverted)
+}
+
+/// Writes back the attributes returned by `invert_window`. Every row is attempted even if
+/// writing one of them fails, the first error is returned.
+fn restore_rows(handle: &Handle, original_rows: &[(COORD, Vec<u16>)]) -> io::Result<()> {
+    let mut result = Ok(());
+    for (start_location, attributes) in original_rows {
+        let written = write_attributes(handle, *start_location, attributes);
+        if result.is_ok() {
+            result = written;
+        }
+    }
+    result
+}
+
+fn write_attributes(handle: &Handle, start_location: COORD, attributes: &[u16]) -> io::Result<()> {
+    let mut cells_written = 0;
+    api_result("WriteConsoleOutputAttribute", unsafe {
+        WriteConsoleOutputAttribute(
+            **handle,
+            attributes.as_ptr(),
+            attributes.len() as u32,
+            start_location,
+            &mut cells_written,
+        )
+    })
+}
+
 pub(crate) fn set_window_title(title: impl fmt::Display) -> std::io::Result<()> {
     struct Utf16Encoder(Vec<u16>);
     impl Write for Utf16Encoder {
@@ -223,11 +632,94 @@ pub(crate) fn set_window_title(title: impl fmt::Display) -> std::io::Result<()>
     title_utf16.0.push(0);
     let title = title_utf16.0;
 
-    let result = unsafe { SetConsoleTitleW(title.as_ptr()) };
+    api_result("SetConsoleTitleW", unsafe {
+        SetConsoleTitleW(title.as_ptr())
+    })
+}
+
+/// The titles saved by `push_title`, the console has no title stack of its own.
+static TITLE_STACK: Mutex<Vec<Vec<u16>>> = parking_lot::const_mutex(Vec::new());
+
+/// Saves the current console title on the title stack.
+pub(crate) fn push_title() -> std::io::Result<()> {
+    // The title can be at most 64K bytes long.
+    let mut title = vec![0u16; 32 * 1024];
+    let length = unsafe { GetConsoleTitleW(title.as_mut_ptr(), title.len() as DWORD) };
+    // The length is 0 for an empty title as well, so that isn't treated as an error.
+    title.truncate(length as usize);
+    title.push(0);
+
+    TITLE_STACK.lock().push(title);
+    Ok(())
+}
+
+/// Restores the console title which was saved last, does nothing if no title was saved.
+pub(crate) fn pop_title() -> std::io::Result<()> {
+    match TITLE_STACK.lock().pop() {
+        Some(title) => api_result("SetConsoleTitleW", unsafe {
+            SetConsoleTitleW(title.as_ptr())
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Returns the code page the console uses to translate output.
+pub(crate) fn code_page() -> io::Result<u32> {
+    code_page_result("GetConsoleOutputCP", unsafe { GetConsoleOutputCP() })
+}
+
+/// Sets the code page the console uses to translate output.
+pub(crate) fn set_code_page(code_page: u32) -> io::Result<()> {
+    api_result("SetConsoleOutputCP", unsafe {
+        SetConsoleOutputCP(code_page)
+    })
+}
+
+/// Returns the code page the console uses to translate input.
+pub(crate) fn input_code_page() -> io::Result<u32> {
+    code_page_result("GetConsoleCP", unsafe { GetConsoleCP() })
+}
+
+/// Sets the code page the console uses to translate input.
+pub(crate) fn set_input_code_page(code_page: u32) -> io::Result<()> {
+    api_result("SetConsoleCP", unsafe { SetConsoleCP(code_page) })
+}
+
+/// Turns the code page returned by a console API call into a result, 0 signals an error.
+fn code_page_result(api: &'static str, code_page: u32) -> io::Result<u32> {
+    api_result(api, BOOL::from(code_page != 0)).map(|()| code_page)
+}
+
+/// Turns the `BOOL` returned by a console API call into a result.
+///
+/// The error names the failed call in addition to the last OS error, because the OS error alone
+/// doesn't tell which of the calls done by a command failed. The OS error is kept as the source
+/// of the returned error, so its `raw_os_error` stays available.
+fn api_result(api: &'static str, result: BOOL) -> io::Result<()> {
     if result != 0 {
-        Ok(())
-    } else {
-        Err(io::Error::last_os_error())
+        return Ok(());
+    }
+
+    let error = io::Error::last_os_error();
+    Err(io::Error::new(error.kind(), ApiError { api, error }))
+}
+
+/// A failed console API call, see `api_result`.
+#[derive(Debug)]
+struct ApiError {
+    api: &'static str,
+    error: io::Error,
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} failed: {}", self.api, self.error)
+    }
+}
+
+impl std::error::Error for ApiError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
     }
 }
 
@@ -304,8 +796,8 @@ fn clear_current_line(
     // clear the whole current line
     clear_winapi(start_location, cells_to_write, current_attribute)?;
 
-    // put the cursor back at cell 1 on current row
-    cursor::sys::move_to(0, location.y as u16)?;
+    // put the cursor back at its original position, like `ESC [ 2 K` does
+    cursor::sys::move_to(location.x as u16, location.y as u16)?;
     Ok(())
 }
 
@@ -330,12 +822,31 @@ fn clear_until_line(
     Ok(())
 }
 
+/// Erases `count` cells starting at the cursor without moving it.
+pub(crate) fn erase_chars(count: u16) -> std::io::Result<()> {
+    let screen_buffer = output_screen_buffer()?;
+    let csbi = screen_buffer.info()?;
+    let location = csbi.cursor_pos();
+
+    let cells_to_write = erase_chars_count(location, csbi.buffer_size(), count);
+    if cells_to_write == 0 {
+        return Ok(());
+    }
+    clear_winapi(location, cells_to_write, csbi.attributes())
+}
+
+/// Returns the number of cells `erase_chars` fills, it never erases past the end of the line.
+fn erase_chars_count(location: Coord, buffer_size: Size, count: u16) -> u32 {
+    let remaining = (buffer_size.width - location.x).max(0) as u32;
+    remaining.min(count as u32)
+}
+
 fn clear_winapi(
     start_location: Coord,
     cells_to_write: u32,
     current_attribute: u16,
 ) -> std::io::Result<()> {
-    let console = Console::from(Handle::current_out_handle()?);
+    let console = Console::from(output_handle()?);
     console.fill_whit_character(start_location, cells_to_write, ' ')?;
     console.fill_whit_attribute(start_location, cells_to_write, current_attribute)?;
     Ok(())
@@ -345,10 +856,49 @@ fn clear_winapi(
 mod tests {
     use std::{ffi::OsString, os::windows::ffi::OsStringExt};
 
-    use crossterm_winapi::ScreenBuffer;
+    use crossterm_winapi::{Coord, ScreenBuffer, Size};
     use winapi::um::wincon::GetConsoleTitleW;
 
-    use super::{scroll_down, scroll_up, set_size, set_window_title, size};
+    use crate::{cursor, terminal::ClearType};
+
+    use super::{
+        api_result, clear, code_page, erase_chars_count, input_code_page, output_handle,
+        scroll_down, scroll_up, set_code_page, set_input_code_page, set_output_handle,
+        set_scrollback_lines, set_size, set_window_title, shift_count, size,
+    };
+
+    #[test]
+    fn test_clear_current_line_keeps_cursor_column() {
+        let (saved_x, saved_y) = cursor::sys::position().unwrap();
+
+        cursor::sys::move_to(5, saved_y).unwrap();
+        clear(ClearType::CurrentLine).unwrap();
+        assert_eq!(cursor::sys::position().unwrap(), (5, saved_y));
+
+        cursor::sys::move_to(saved_x, saved_y).unwrap();
+    }
+
+    #[test]
+    fn test_output_handle_override() {
+        let back_buffer = ScreenBuffer::create().unwrap();
+        let handle = back_buffer.handle().clone();
+
+        assert!(set_output_handle(Some(handle.clone())).is_none());
+        assert_eq!(*output_handle().unwrap(), *handle);
+
+        let previous = set_output_handle(None).unwrap();
+        assert_eq!(*previous, *handle);
+        assert_ne!(*output_handle().unwrap(), *handle);
+    }
+
+    #[test]
+    fn test_erase_chars_count() {
+        let buffer_size = Size::new(80, 25);
+
+        assert_eq!(erase_chars_count(Coord::new(0, 3), buffer_size, 5), 5);
+        assert_eq!(erase_chars_count(Coord::new(78, 3), buffer_size, 5), 2);
+        assert_eq!(erase_chars_count(Coord::new(10, 3), buffer_size, 0), 0);
+    }
 
     #[test]
     fn test_resize_winapi() {
@@ -362,6 +912,29 @@ mod tests {
         assert_eq!((width, height), size().unwrap());
     }
 
+    #[test]
+    fn test_set_scrollback_lines() {
+        let buffer_lines = || {
+            ScreenBuffer::current()
+                .unwrap()
+                .info()
+                .unwrap()
+                .buffer_size()
+                .height
+        };
+        let (width, height) = size().unwrap();
+        let lines = buffer_lines() as u16;
+
+        set_scrollback_lines(lines + 100).unwrap();
+        assert_eq!(buffer_lines() as u16, lines + 100);
+        assert_eq!((width, height), size().unwrap());
+
+        // reset to previous number of lines
+        set_scrollback_lines(lines).unwrap();
+        assert_eq!(buffer_lines() as u16, lines);
+        assert_eq!((width, height), size().unwrap());
+    }
+
     // Test is disabled, because it's failing on Travis CI
     #[test]
     #[ignore]
@@ -421,4 +994,39 @@ mod tests {
         let console_title = OsString::from_wide(&raw[..length]).into_string().unwrap();
         assert_eq!(test_title, &console_title[..]);
     }
+
+    #[test]
+    fn test_code_page_round_trip_winapi() {
+        let (original, original_input) = (code_page().unwrap(), input_code_page().unwrap());
+
+        set_code_page(65001).unwrap();
+        set_input_code_page(65001).unwrap();
+        assert_eq!(code_page().unwrap(), 65001);
+        assert_eq!(input_code_page().unwrap(), 65001);
+
+        set_code_page(original).unwrap();
+        set_input_code_page(original_input).unwrap();
+        assert_eq!(code_page().unwrap(), original);
+        assert_eq!(input_code_page().unwrap(), original_input);
+    }
+
+    #[test]
+    fn test_api_result_keeps_os_error() {
+        // Setting an invalid code page fails and leaves an OS error behind.
+        let error = set_code_page(0).unwrap_err();
+        let source = std::error::Error::source(error.get_ref().unwrap())
+            .and_then(|source| source.downcast_ref::<std::io::Error>())
+            .unwrap();
+        assert!(source.raw_os_error().is_some());
+        assert!(error.to_string().starts_with("SetConsoleOutputCP failed"));
+
+        assert!(api_result("MessageBeep", 1).is_ok());
+    }
+
+    #[test]
+    fn test_shift_count_clamps_large_counts() {
+        assert_eq!(shift_count(3), 3);
+        assert_eq!(shift_count(i16::MAX as u16), i16::MAX);
+        assert_eq!(shift_count(u16::MAX), i16::MAX);
+    }
 }